@@ -245,7 +245,7 @@ where
     /// If the key is contained in the heap this will also adjust the heap structure.
     /// In case the key is not contained the priority will still be adjusted. This is
     /// useful in case the key is going to be restored in the future to its former
-    /// priority.
+    /// priority. This also holds for keys that have never been pushed to the heap.
     ///
     /// # Errors
     ///
@@ -267,13 +267,7 @@ where
                 .expect("unexpected out of bounds key (priority update)");
             old_priority <= new_priority
         };
-        let position = self
-            .get_position(key)
-            .expect("unexpected uncontained key (update priority)");
-        if self
-            .contains(key)
-            .expect("encountered unexpected invalid key (contains query)")
-        {
+        if let Some(position) = self.get_position(key) {
             match is_priority_increased {
                 true => self.sift_up(position),
                 false => self.sift_down(position),
@@ -508,7 +502,7 @@ where
             let parent = child.parent().expect("encountered missing parent");
             let child_key = self.heap_entry(child);
             let parent_key = self.heap_entry(parent);
-            if self.cmp_priorities(parent_key, child_key) != Ordering::Greater {
+            if self.cmp_priorities(parent_key, child_key) == Ordering::Less {
                 return false
             }
         }
@@ -674,4 +668,40 @@ mod tests {
         assert_eq!(heap.len(), len + 1);
         assert_eq!(heap.pop(), Some((len, 40)));
     }
+
+    #[test]
+    fn update_priority_of_uncontained_key_works() {
+        let mut heap = BoundedHeap::default();
+        heap.resize_capacity(3);
+        heap.push_or_update(0, |_| 10).unwrap();
+        // The key 1 has never been pushed and the key 0 has been popped.
+        heap.update_priority(1, |w| w + 20).unwrap();
+        assert_eq!(heap.pop(), Some((0, 10)));
+        heap.update_priority(0, |w| w + 5).unwrap();
+        assert!(heap.is_empty());
+        assert_eq!(heap.update_priority(3, |w| w), Err(OutOfBoundsAccess));
+        // Restoring the keys restores their updated priorities.
+        heap.push_or_update(0, |w| w).unwrap();
+        heap.push_or_update(1, |w| w).unwrap();
+        assert_eq!(heap.pop(), Some((1, 20)));
+        assert_eq!(heap.pop(), Some((0, 15)));
+        assert!(heap.is_empty());
+    }
+
+    #[test]
+    fn satisfies_heap_property_with_equal_priorities() {
+        let test_priorities = [5, 5, 5, 3, 5, 3, 3, 5];
+        let mut heap = BoundedHeap::default();
+        heap.resize_capacity(test_priorities.len());
+        for (k, w) in test_priorities.iter().copied().enumerate() {
+            heap.push_or_update(k, |_| w).unwrap();
+            assert!(heap.satisfies_heap_property());
+        }
+        let mut removed_sequence = Vec::new();
+        while let Some((_, w)) = heap.pop() {
+            removed_sequence.push(w);
+            assert!(heap.satisfies_heap_property());
+        }
+        assert_eq!(removed_sequence, [5, 5, 5, 5, 5, 3, 3, 3]);
+    }
 }
@@ -148,13 +148,13 @@ impl<T> BoundedStack<T> {
 
     /// Returns an iterator yielding shared references to the values of the bounded stack.
     #[inline]
-    pub fn iter(&self) -> slice::Iter<T> {
+    pub fn iter(&self) -> slice::Iter<'_, T> {
         self.stack.iter()
     }
 
     /// Returns an iterator yielding exclusive references to the values of the bounded stack.
     #[inline]
-    pub fn iter_mut(&mut self) -> slice::IterMut<T> {
+    pub fn iter_mut(&mut self) -> slice::IterMut<'_, T> {
         self.stack.iter_mut()
     }
 
@@ -0,0 +1,148 @@
+use std::{
+    fmt,
+    fmt::Display,
+    io,
+    path::PathBuf,
+};
+
+/// The errors of the input parser of `.cnf` files.
+type CnfError = cnf_parser::Error<solver::Error>;
+
+/// Errors that prevent the driver from solving its input.
+#[derive(Debug)]
+pub enum Error {
+    /// The input file could not be read.
+    Read { path: PathBuf, error: io::Error },
+    /// The input file could not be decoded.
+    Decode { path: PathBuf, message: String },
+    /// The output file could not be written.
+    Write { path: PathBuf, error: io::Error },
+}
+
+impl Error {
+    /// Creates a new error for the input file that could not be read.
+    pub fn read(path: impl Into<PathBuf>, error: io::Error) -> Self {
+        Self::Read {
+            path: path.into(),
+            error,
+        }
+    }
+
+    /// Creates a new error for the output file that could not be written.
+    pub fn write(path: impl Into<PathBuf>, error: io::Error) -> Self {
+        Self::Write {
+            path: path.into(),
+            error,
+        }
+    }
+
+    /// Creates a new error for the input file that could not be decoded.
+    pub fn decode(path: impl Into<PathBuf>, message: impl Display) -> Self {
+        Self::Decode {
+            path: path.into(),
+            message: message.to_string(),
+        }
+    }
+
+    /// Creates a new error for the `.cnf` input file that could not be decoded.
+    pub fn decode_cnf(path: impl Into<PathBuf>, error: CnfError) -> Self {
+        Self::decode(path, describe_cnf_error(error))
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Read { path, error } => {
+                write!(f, "couldn't read input file {}: {}", path.display(), error)
+            }
+            Self::Decode { path, message } => {
+                write!(
+                    f,
+                    "couldn't decode input file {}: {}",
+                    path.display(),
+                    message
+                )
+            }
+            Self::Write { path, error } => {
+                write!(
+                    f,
+                    "couldn't write output file {}: {}",
+                    path.display(),
+                    error
+                )
+            }
+        }
+    }
+}
+
+/// Returns a human readable description of the byte or the end of input.
+fn describe_byte(byte: Option<u8>) -> String {
+    match byte {
+        Some(byte) => format!("{:?}", char::from(byte)),
+        None => "end of input".to_string(),
+    }
+}
+
+/// Returns a human readable description of the `.cnf` parser error.
+///
+/// # Note
+///
+/// All positions are byte offsets into the input.
+fn describe_cnf_error(error: CnfError) -> String {
+    match error {
+        CnfError::Output(error) => error.to_string(),
+        CnfError::UnexpectedByte {
+            at,
+            encountered,
+            expected: Some(expected),
+        } => {
+            format!(
+                "encountered {} at byte {} but expected {}",
+                describe_byte(encountered),
+                at,
+                describe_byte(Some(expected)),
+            )
+        }
+        CnfError::UnexpectedByte {
+            at,
+            encountered,
+            expected: None,
+        } => {
+            format!(
+                "encountered unexpected {} at byte {}",
+                describe_byte(encountered),
+                at
+            )
+        }
+        CnfError::InvalidTokenStart { at, encountered } => {
+            format!(
+                "encountered invalid start of token {} at byte {}",
+                describe_byte(Some(encountered)),
+                at
+            )
+        }
+        CnfError::ExpectedWhitespace { at, encountered } => {
+            format!(
+                "encountered {} at byte {} but expected whitespace",
+                describe_byte(encountered),
+                at
+            )
+        }
+        CnfError::OutOfRangeU32 { at, encountered } => {
+            format!(
+                "encountered out of range integer {} at byte {}",
+                encountered, at
+            )
+        }
+        CnfError::OutOfRangeI32 { at, encountered } => {
+            format!(
+                "encountered out of range literal {} at byte {}",
+                encountered, at
+            )
+        }
+        CnfError::DuplicateProblem { at, .. } => {
+            format!("encountered duplicate problem line at byte {}", at)
+        }
+    }
+}
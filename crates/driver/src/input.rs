@@ -0,0 +1,81 @@
+use crate::error::Error;
+use bzip2::read::MultiBzDecoder;
+use flate2::read::MultiGzDecoder;
+use std::{
+    ffi::OsStr,
+    fs::File,
+    io,
+    io::Read,
+    path::Path,
+};
+use xz2::read::XzDecoder;
+
+/// The path that denotes the standard input.
+const STDIN: &str = "-";
+
+/// The compression format of an input file.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+enum Compression {
+    Gzip,
+    Xz,
+    Bzip2,
+}
+
+impl Compression {
+    /// Returns the compression format denoted by the file extension if any.
+    fn from_extension(extension: &OsStr) -> Option<Self> {
+        match extension.to_str()? {
+            "gz" => Some(Self::Gzip),
+            "xz" => Some(Self::Xz),
+            "bz2" => Some(Self::Bzip2),
+            _ => None,
+        }
+    }
+}
+
+/// Returns `true` if the path denotes the standard input.
+pub fn is_stdin(path: &Path) -> bool {
+    path == Path::new(STDIN)
+}
+
+/// Returns the extension of the input format of the file.
+///
+/// # Note
+///
+/// The extension of a compression format is skipped so that the
+/// format of `instance.cnf.xz` is `cnf`.
+pub fn format_extension(path: &Path) -> Option<&OsStr> {
+    match path.extension() {
+        Some(extension) if Compression::from_extension(extension).is_some() => {
+            Path::new(path.file_stem()?).extension()
+        }
+        extension => extension,
+    }
+}
+
+/// Reads the contents of the input file or the standard input for `-`.
+///
+/// Files with the extension `.gz`, `.xz` or `.bz2` are decompressed.
+pub fn read(path: &Path) -> Result<Vec<u8>, Error> {
+    let mut contents = Vec::new();
+    let result = if is_stdin(path) {
+        io::stdin().lock().read_to_end(&mut contents)
+    } else {
+        let file = File::open(path).map_err(|error| Error::read(path, error))?;
+        let compression = path.extension().and_then(Compression::from_extension);
+        match compression {
+            None => io::BufReader::new(file).read_to_end(&mut contents),
+            Some(Compression::Gzip) => {
+                MultiGzDecoder::new(file).read_to_end(&mut contents)
+            }
+            Some(Compression::Xz) => {
+                XzDecoder::new_multi_decoder(file).read_to_end(&mut contents)
+            }
+            Some(Compression::Bzip2) => {
+                MultiBzDecoder::new(file).read_to_end(&mut contents)
+            }
+        }
+    };
+    result.map_err(|error| Error::read(path, error))?;
+    Ok(contents)
+}
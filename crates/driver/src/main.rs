@@ -1,37 +1,787 @@
 #![forbid(unsafe_code)]
 
+mod error;
+mod input;
+mod output;
+
+use crate::{
+    error::Error,
+    output::{
+        print_literals,
+        print_model,
+        print_positions,
+        print_statistics,
+        write_literals,
+        Format,
+        EXIT_ERROR,
+        EXIT_OPTIMUM,
+        EXIT_SATISFIABLE,
+        EXIT_SUCCESS,
+        EXIT_UNKNOWN,
+        EXIT_UNSATISFIABLE,
+    },
+};
 use solver::{
+    count::{
+        self,
+        ModelCount,
+        ModelCounter,
+    },
+    cube::{
+        CubeResult,
+        Cuber,
+        Icnf,
+        IcnfLine,
+    },
+    maxsat::{
+        MaxSatAlgorithm,
+        MaxSatResult,
+        MaxSatSolver,
+        Wcnf,
+    },
+    mcs::{
+        McsAlgorithm,
+        McsSolver,
+    },
+    mus::MusExtractor,
+    parallel::ParallelSolver,
+    Interrupter,
+    JsonTrace,
+    Literal,
+    Model,
+    Objective,
     SolveResult,
     Solver,
+    Variable,
 };
 use std::{
-    fs,
-    path::PathBuf,
+    fs::File,
+    io,
+    io::Write,
+    path::{
+        Path,
+        PathBuf,
+    },
+    process,
+    sync::{
+        Arc,
+        Mutex,
+    },
+    time::{
+        Duration,
+        Instant,
+    },
+};
+use structopt::{
+    clap::{
+        Error as ClapError,
+        ErrorKind,
+    },
+    StructOpt,
 };
-use structopt::StructOpt;
 
 #[derive(StructOpt, Debug)]
 struct Opt {
-    #[structopt(name = "input .cnf file", parse(from_os_str))]
-    input: PathBuf,
+    /// The input file in the DIMACS `.cnf`, the pseudo-Boolean `.opb` or the
+    /// incremental `.icnf` format.
+    ///
+    /// Reads `.cnf` input from the standard input for `-`. Input files with the
+    /// extension `.gz`, `.xz` or `.bz2` are decompressed. Every assumption line
+    /// of `.icnf` input is solved against the clauses before it.
+    #[structopt(name = "input .cnf, .opb or .icnf file", parse(from_os_str))]
+    input: Option<PathBuf>,
+    /// Enumerates all models instead of solving for a single one.
+    #[structopt(long)]
+    all_models: bool,
+    /// Counts the models of the `.cnf` input instead of solving for a single one.
+    #[structopt(long, conflicts_with = "all-models")]
+    count: bool,
+    /// The comma separated variables onto which the enumerated or counted models
+    /// are projected.
+    ///
+    /// Defaults to the variables of the `c p show` lines of `.cnf` input.
+    #[structopt(long, require_delimiter = true, parse(try_from_str = parse_variable))]
+    project: Option<Vec<Variable>>,
+    /// Writes the found model as `v` lines to the file.
+    #[structopt(long, parse(from_os_str))]
+    model_out: Option<PathBuf>,
+    /// The comma separated DIMACS literals that are assumed during solving.
+    #[structopt(
+        long,
+        require_delimiter = true,
+        allow_hyphen_values = true,
+        conflicts_with_all = &["all-models", "count"],
+        parse(try_from_str = parse_literal)
+    )]
+    assume: Option<Vec<Literal>>,
+    /// Gives up solving after the given number of seconds.
+    #[structopt(
+        long,
+        conflicts_with_all = &["all-models", "count"],
+        parse(try_from_str = parse_timeout)
+    )]
+    timeout: Option<Duration>,
+    /// Gives up every call to the solver after the given number of conflicts.
+    #[structopt(long, conflicts_with_all = &["all-models", "count"])]
+    conflicts: Option<u64>,
+    /// Seeds the randomized order in which the solver decides variables.
+    #[structopt(long)]
+    seed: Option<u64>,
+    /// Solves on the given number of threads with diversified configurations
+    /// that share their learned clauses.
+    ///
+    /// Defaults to a single thread.
+    #[structopt(
+        long,
+        conflicts_with_all = &["all-models", "count", "trace"],
+        parse(try_from_str = parse_threads)
+    )]
+    threads: Option<usize>,
+    /// Writes the events of the search as JSON lines to the file.
+    #[structopt(long, conflicts_with = "count", parse(from_os_str))]
+    trace: Option<PathBuf>,
+    #[structopt(subcommand)]
+    command: Option<Command>,
+}
+
+#[derive(StructOpt, Debug)]
+enum Command {
+    /// Solves a weighted partial MaxSAT instance to optimality.
+    Maxsat {
+        /// The input file in the `.wcnf` format.
+        #[structopt(name = "input .wcnf file", parse(from_os_str))]
+        input: PathBuf,
+        /// The MaxSAT algorithm: `linear` or `oll`.
+        #[structopt(long, default_value = "oll", parse(try_from_str = parse_algorithm))]
+        algorithm: MaxSatAlgorithm,
+    },
+    /// Enumerates the minimal correction subsets of the soft clauses.
+    Mcs {
+        /// The input file in the `.wcnf` format.
+        ///
+        /// The weights of the soft clauses are ignored.
+        #[structopt(name = "input .wcnf file", parse(from_os_str))]
+        input: PathBuf,
+        /// The MCS algorithm: `linear` or `cld`.
+        #[structopt(long, default_value = "cld", parse(try_from_str = parse_mcs_algorithm))]
+        algorithm: McsAlgorithm,
+        /// The maximum number of enumerated minimal correction subsets.
+        #[structopt(long)]
+        limit: Option<usize>,
+    },
+    /// Splits the instance into cubes with a lookahead cuber.
+    ///
+    /// Prints the clauses and the cubes in the iCNF format unless the cubes are solved.
+    Cube {
+        /// The input file in the DIMACS `.cnf` format.
+        #[structopt(name = "input .cnf file", parse(from_os_str))]
+        input: PathBuf,
+        /// The maximum number of decisions of every cube.
+        #[structopt(long, default_value = "10")]
+        depth: usize,
+        /// The maximum number of cubes.
+        #[structopt(long, default_value = "256")]
+        max_cubes: usize,
+        /// Solves the cubes instead of printing them.
+        #[structopt(long)]
+        solve: bool,
+        /// The number of threads that solve the cubes.
+        #[structopt(long, requires = "solve", parse(try_from_str = parse_threads))]
+        threads: Option<usize>,
+    },
+    /// Prints a minimal unsatisfiable subset of the clauses in the DIMACS format.
+    Mus {
+        /// The input file in the DIMACS `.cnf` format.
+        #[structopt(name = "input .cnf file", parse(from_os_str))]
+        input: PathBuf,
+    },
+}
+
+/// Parses the name of an MCS algorithm.
+fn parse_mcs_algorithm(name: &str) -> Result<McsAlgorithm, String> {
+    match name {
+        "linear" => Ok(McsAlgorithm::Linear),
+        "cld" => Ok(McsAlgorithm::Cld),
+        _ => Err(format!("unknown MCS algorithm: {}", name)),
+    }
+}
+
+/// Parses the name of a MaxSAT algorithm.
+fn parse_algorithm(name: &str) -> Result<MaxSatAlgorithm, String> {
+    match name {
+        "linear" => Ok(MaxSatAlgorithm::LinearSatUnsat),
+        "oll" => Ok(MaxSatAlgorithm::Oll),
+        _ => Err(format!("unknown MaxSAT algorithm: {}", name)),
+    }
+}
+
+/// Parses a variable given by its DIMACS index starting at `1`.
+fn parse_variable(index: &str) -> Result<Variable, String> {
+    match index.parse::<u32>() {
+        Ok(index) if index >= 1 => Ok(Variable::from(index - 1)),
+        _ => Err(format!("invalid variable: {}", index)),
+    }
+}
+
+/// Parses a literal given by its signed DIMACS variable index.
+fn parse_literal(literal: &str) -> Result<Literal, String> {
+    match literal.parse::<i32>() {
+        Ok(value) if value != 0 && value != i32::MIN => Ok(Literal::from(value)),
+        _ => Err(format!("invalid literal: {}", literal)),
+    }
+}
+
+/// Parses a positive number of threads.
+fn parse_threads(threads: &str) -> Result<usize, String> {
+    match threads.parse::<usize>() {
+        Ok(threads) if threads > 0 => Ok(threads),
+        _ => Err(format!("invalid number of threads: {}", threads)),
+    }
+}
+
+/// Parses a timeout given in seconds.
+fn parse_timeout(seconds: &str) -> Result<Duration, String> {
+    seconds
+        .parse::<f64>()
+        .ok()
+        .and_then(|seconds| Duration::try_from_secs_f64(seconds).ok())
+        .ok_or_else(|| format!("invalid timeout: {}", seconds))
 }
 
 fn main() {
     let opt = Opt::from_args();
-    let cnf_contents =
-        fs::read(opt.input).expect("couldn't read provided input .cnf file");
-    let mut solver = Solver::from_cnf(&mut &cnf_contents[..])
-        .expect("couldn't properly decode provided input .cnf file");
-    println!("start solving ...");
-    let result = solver
-        .solve(vec![])
-        .expect("encountered errors during solving");
-    match result {
-        SolveResult::Sat(model) => {
-            println!("SAT\nmodel = {}", model);
-        }
-        SolveResult::Unsat => {
-            println!("UNSAT");
+    if opt.project.is_some() && !opt.all_models && !opt.count {
+        ClapError::with_description(
+            "--project requires either --all-models or --count",
+            ErrorKind::MissingRequiredArgument,
+        )
+        .exit()
+    }
+    let code = match run(&opt) {
+        Ok(code) => code,
+        Err(error) => {
+            eprintln!("error: {}", error);
+            EXIT_ERROR
+        }
+    };
+    process::exit(code)
+}
+
+/// Runs the command given by the options and returns the exit code.
+fn run(opt: &Opt) -> Result<i32, Error> {
+    match (&opt.command, &opt.input) {
+        (Some(Command::Maxsat { input, algorithm }), _) => maxsat(input, *algorithm),
+        (
+            Some(Command::Mcs {
+                input,
+                algorithm,
+                limit,
+            }),
+            _,
+        ) => mcs(input, *algorithm, *limit),
+        (
+            Some(Command::Cube {
+                input,
+                depth,
+                max_cubes,
+                solve,
+                threads,
+            }),
+            _,
+        ) => {
+            cube(
+                input,
+                *depth,
+                *max_cubes,
+                solve.then(|| threads.unwrap_or(1)),
+            )
+        }
+        (Some(Command::Mus { input }), _) => mus(input),
+        (None, Some(input)) => sat(input, opt),
+        (None, None) => {
+            ClapError::with_description(
+                "the input file is required unless a subcommand is used",
+                ErrorKind::MissingRequiredArgument,
+            )
+            .exit()
+        }
+    }
+}
+
+/// Prints the status line for a solver that gave up due to the error.
+fn unknown(error: solver::Error) -> i32 {
+    println!("c error: {}", error);
+    println!("s UNKNOWN");
+    EXIT_UNKNOWN
+}
+
+/// Solves the `.cnf` or `.opb` input file and prints the result.
+fn sat(input: &Path, opt: &Opt) -> Result<i32, Error> {
+    let deadline = opt.timeout.map(|timeout| Instant::now() + timeout);
+    let contents = input::read(input)?;
+    let extension = match input::is_stdin(input) {
+        true => None,
+        false => input::format_extension(input),
+    };
+    let is_opb = extension
+        .map(|extension| extension == "opb")
+        .unwrap_or(false);
+    let is_icnf = extension
+        .map(|extension| extension == "icnf")
+        .unwrap_or(false);
+    if is_icnf && (opt.all_models || opt.count || opt.model_out.is_some()) {
+        ClapError::with_description(
+            "--all-models, --count and --model-out require .cnf or .opb input",
+            ErrorKind::InvalidValue,
+        )
+        .exit()
+    }
+    let projection = match &opt.project {
+        Some(projection) => Some(projection.clone()),
+        None if !is_opb => count::parse_show(&contents),
+        None => None,
+    };
+    if opt.count {
+        if is_opb {
+            ClapError::with_description(
+                "--count requires .cnf input",
+                ErrorKind::InvalidValue,
+            )
+            .exit()
+        }
+        let counter = ModelCounter::from_cnf(&mut &contents[..])
+            .map_err(|error| Error::decode_cnf(input, error))?;
+        println!("c start counting ...");
+        let count = counter.count(projection.as_deref());
+        return Ok(print_count(&count, projection.is_some()))
+    }
+    let mut icnf = None;
+    let (mut solver, objective, format) = if is_opb {
+        let (solver, objective) =
+            Solver::from_opb(&contents).map_err(|error| Error::decode(input, error))?;
+        (solver, objective, Format::Opb)
+    } else if is_icnf {
+        let parsed =
+            Icnf::parse(&contents).map_err(|error| Error::decode(input, error))?;
+        let mut solver = Solver::default();
+        solver.new_literal_chunk(parsed.len_variables());
+        icnf = Some(parsed);
+        (solver, None, Format::Dimacs)
+    } else {
+        let solver = Solver::from_cnf(&mut &contents[..])
+            .map_err(|error| Error::decode_cnf(input, error))?;
+        (solver, None, Format::Dimacs)
+    };
+    let assumptions = opt.assume.clone().unwrap_or_default();
+    if let Some(&literal) = assumptions.iter().find(|literal| {
+        literal.variable() >= Variable::from(solver.len_variables() as u32)
+    }) {
+        ClapError::with_description(
+            &format!(
+                "--assume encountered unknown variable of literal {}",
+                literal
+            ),
+            ErrorKind::InvalidValue,
+        )
+        .exit()
+    }
+    solver.set_deadline(deadline);
+    solver.set_conflict_limit(opt.conflicts);
+    if let Some(seed) = opt.seed {
+        solver.set_seed(seed);
+    }
+    // Model enumeration panics on interrupted searches so that SIGINT
+    // terminates it without statistics.
+    if !opt.all_models {
+        handle_interrupts(solver.interrupter());
+    }
+    let trace = match &opt.trace {
+        Some(path) => {
+            let file = File::create(path).map_err(|error| Error::write(path, error))?;
+            let trace = Arc::new(Mutex::new(JsonTrace::new(io::BufWriter::new(file))));
+            solver.set_observer(trace.clone());
+            Some((path, trace))
+        }
+        None => None,
+    };
+    let mut solver = ParallelSolver::new(solver, opt.threads.unwrap_or(1));
+    let (code, model) = match (icnf, objective) {
+        _ if opt.all_models => {
+            let solver = solver.solver_mut();
+            (enumerate(solver, projection.as_deref(), format), None)
+        }
+        (Some(icnf), _) => (solve_incremental(&mut solver, &icnf, &assumptions), None),
+        (None, Some(objective)) => minimize(&mut solver, &objective, &assumptions),
+        (None, None) => solve(&mut solver, &assumptions, format),
+    };
+    print_statistics(&solver.stats());
+    if let Some((path, trace)) = trace {
+        drop(solver.solver_mut().take_observer());
+        Arc::try_unwrap(trace)
+            .expect("encountered unexpected shared trace")
+            .into_inner()
+            .expect("encountered unexpected poisoned trace")
+            .into_inner()
+            .map_err(|error| Error::write(path, error))?;
+    }
+    if let (Some(path), Some(model)) = (&opt.model_out, model) {
+        write_model(path, &model, format)?;
+    }
+    Ok(code)
+}
+
+/// Interrupts the solver upon the first SIGINT so that its result and statistics
+/// are still printed and exits immediately upon the second one.
+///
+/// # Note
+///
+/// Only installed for solving without `--all-models`. Model enumeration,
+/// model counting and the subcommands are terminated by the first SIGINT
+/// without printing statistics.
+fn handle_interrupts(interrupter: Interrupter) {
+    ctrlc::set_handler(move || {
+        if interrupter.is_interrupted() {
+            process::exit(EXIT_UNKNOWN)
+        }
+        println!("c interrupted");
+        interrupter.interrupt();
+    })
+    .expect("couldn't install the SIGINT handler")
+}
+
+/// Writes the model as `v` lines in the format to the file.
+fn write_model(path: &Path, model: &Model, format: Format) -> Result<(), Error> {
+    let write = || -> io::Result<()> {
+        let mut file = io::BufWriter::new(File::create(path)?);
+        write_literals(&mut file, model, format)?;
+        file.flush()
+    };
+    write().map_err(|error| Error::write(path, error))
+}
+
+/// Solves the instance under the assumptions and prints the result.
+///
+/// Returns the exit code and the found model if any.
+fn solve(
+    solver: &mut ParallelSolver,
+    assumptions: &[Literal],
+    format: Format,
+) -> (i32, Option<Model>) {
+    println!("c start solving ...");
+    match solver.solve(assumptions.iter().copied()) {
+        Ok(SolveResult::Sat(sat)) => {
+            println!("s SATISFIABLE");
+            print_model(sat.model(), format);
+            (EXIT_SATISFIABLE, Some(sat.model().clone()))
+        }
+        Ok(SolveResult::Unsat) => {
+            if !assumptions.is_empty() {
+                let failed = solver
+                    .failed_assumptions()
+                    .iter()
+                    .map(|literal| literal.to_string())
+                    .collect::<Vec<_>>();
+                println!("c failed assumptions: {}", failed.join(" "));
+            }
+            println!("s UNSATISFIABLE");
+            (EXIT_UNSATISFIABLE, None)
+        }
+        Ok(SolveResult::Unknown) => {
+            println!("s UNKNOWN");
+            (EXIT_UNKNOWN, None)
+        }
+        Err(error) => (unknown(error), None),
+    }
+}
+
+/// Adds the clauses of the `.icnf` input in order and solves every query
+/// against the clauses before it under its assumptions and the given ones.
+///
+/// Prints the result of every query and returns the exit code of the last one.
+fn solve_incremental(
+    solver: &mut ParallelSolver,
+    icnf: &Icnf,
+    assumptions: &[Literal],
+) -> i32 {
+    let mut code = EXIT_SUCCESS;
+    let mut len_queries = 0;
+    for line in icnf.lines() {
+        match line {
+            IcnfLine::Clause(clause) => {
+                solver.solver_mut().consume_clause(clause.iter().copied());
+            }
+            IcnfLine::Assumptions(query) => {
+                len_queries += 1;
+                println!("c query {}", len_queries);
+                let query = assumptions.iter().chain(query).copied().collect::<Vec<_>>();
+                code = solve(solver, &query, Format::Dimacs).0;
+            }
+        }
+    }
+    code
+}
+
+/// Enumerates all models projected onto the variables and prints them.
+///
+/// # Note
+///
+/// Every model is printed as its own `v` lines after the status line.
+/// The objective function of `.opb` input is ignored.
+fn enumerate(
+    solver: &mut Solver,
+    projection: Option<&[Variable]>,
+    format: Format,
+) -> i32 {
+    println!("c start enumerating ...");
+    let mut len_models = 0;
+    for model in solver.enumerate_models(projection, None) {
+        if len_models == 0 {
+            println!("s SATISFIABLE");
+        }
+        match projection {
+            Some(projection) => {
+                let literals = model
+                    .into_iter()
+                    .filter(|literal| projection.contains(&literal.variable()));
+                print_literals(literals, format)
+            }
+            None => print_model(&model, format),
+        }
+        len_models += 1;
+    }
+    println!("c models = {}", len_models);
+    if len_models == 0 {
+        println!("s UNSATISFIABLE");
+        return EXIT_UNSATISFIABLE
+    }
+    EXIT_SATISFIABLE
+}
+
+/// Prints the model count in the format of the model counting competition.
+fn print_count(count: &ModelCount, is_projected: bool) -> i32 {
+    let code = if count.is_zero() {
+        println!("s UNSATISFIABLE");
+        EXIT_UNSATISFIABLE
+    } else {
+        println!("s SATISFIABLE");
+        EXIT_SATISFIABLE
+    };
+    let digits = count.to_string();
+    let prefix = &digits[..digits.len().min(15)];
+    let log10 = prefix
+        .parse::<f64>()
+        .expect("encountered unexpected non-decimal model count")
+        .log10()
+        + (digits.len() - prefix.len()) as f64;
+    println!("c s type {}", if is_projected { "pmc" } else { "mc" });
+    println!("c s log10-estimate {:.6}", log10);
+    println!("c s exact arb int {}", digits);
+    code
+}
+
+/// Minimizes the objective function under the assumptions and prints every
+/// improved solution.
+///
+/// Returns the exit code and the best found model if any.
+///
+/// # Note
+///
+/// Uses a linear search that constrains the objective function to be less
+/// than the value of the last found solution until the instance becomes
+/// unsatisfiable. Prints the result in the pseudo-Boolean competition format.
+/// If the search is interrupted the best found model is printed as satisfiable.
+fn minimize(
+    solver: &mut ParallelSolver,
+    objective: &Objective,
+    assumptions: &[Literal],
+) -> (i32, Option<Model>) {
+    println!("c start solving ...");
+    let mut best = None;
+    loop {
+        let result = match solver.solve(assumptions.iter().copied()) {
+            Ok(result) => result,
+            Err(error) => {
+                println!("c error: {}", error);
+                break
+            }
+        };
+        let value = match result {
+            SolveResult::Sat(sat) => {
+                let value = objective.evaluate(sat.model());
+                println!("o {}", value);
+                best = Some(sat.model().clone());
+                value
+            }
+            SolveResult::Unsat => {
+                let code = match &best {
+                    Some(model) => {
+                        println!("s OPTIMUM FOUND");
+                        print_model(model, Format::Opb);
+                        EXIT_OPTIMUM
+                    }
+                    None => {
+                        println!("s UNSATISFIABLE");
+                        EXIT_UNSATISFIABLE
+                    }
+                };
+                return (code, best)
+            }
+            SolveResult::Unknown => break,
+        };
+        // Require `sum(a_i * l_i) <= value - 1` for the next solution.
+        let negated = objective
+            .terms()
+            .iter()
+            .map(|&(coefficient, literal)| (-coefficient, literal));
+        solver.solver_mut().add_pseudo_boolean(negated, 1 - value);
+    }
+    match &best {
+        Some(model) => {
+            println!("s SATISFIABLE");
+            print_model(model, Format::Opb);
+            (EXIT_SATISFIABLE, best)
+        }
+        None => {
+            println!("s UNKNOWN");
+            (EXIT_UNKNOWN, None)
+        }
+    }
+}
+
+/// Solves the `.wcnf` input file to optimality.
+///
+/// # Note
+///
+/// Prints the result in the MaxSAT evaluation format: an `o` line for every
+/// improved solution, the `s` status line and the `v` line with the values
+/// of all variables of the optimal solution.
+fn maxsat(input: &Path, algorithm: MaxSatAlgorithm) -> Result<i32, Error> {
+    let contents = input::read(input)?;
+    let wcnf = Wcnf::parse(&contents).map_err(|error| Error::decode(input, error))?;
+    let mut solver = MaxSatSolver::from_wcnf(&wcnf);
+    let result = solver.solve(algorithm, |cost| println!("o {}", cost));
+    let code = match result {
+        MaxSatResult::Optimum { model, .. } => {
+            let values = model
+                .into_iter()
+                .take(wcnf.len_variables())
+                .map(|literal| if literal.sign().is_pos() { '1' } else { '0' })
+                .collect::<String>();
+            println!("s OPTIMUM FOUND\nv {}", values);
+            EXIT_OPTIMUM
+        }
+        MaxSatResult::Unsat => {
+            println!("s UNSATISFIABLE");
+            EXIT_UNSATISFIABLE
+        }
+    };
+    Ok(code)
+}
+
+/// Enumerates the minimal correction subsets of the soft clauses of the `.wcnf` input.
+///
+/// # Note
+///
+/// Prints every subset as the positions of its soft clauses among all soft
+/// clauses of the input starting at `1`.
+fn mcs(
+    input: &Path,
+    algorithm: McsAlgorithm,
+    limit: Option<usize>,
+) -> Result<i32, Error> {
+    let contents = input::read(input)?;
+    let wcnf = Wcnf::parse(&contents).map_err(|error| Error::decode(input, error))?;
+    let mut solver = McsSolver::from_wcnf(&wcnf);
+    println!("c start enumerating ...");
+    let mut len_mcses = 0;
+    for mcs in solver
+        .enumerate(algorithm)
+        .take(limit.unwrap_or(usize::MAX))
+    {
+        print_positions(mcs.iter().map(|index| index + 1));
+        len_mcses += 1;
+    }
+    println!("c mcses = {}", len_mcses);
+    Ok(EXIT_SUCCESS)
+}
+
+/// Splits the `.cnf` input file into cubes and either prints or solves them.
+///
+/// # Note
+///
+/// Prints the clauses and the cubes in the iCNF format unless the number of
+/// threads to solve them is given.
+fn cube(
+    input: &Path,
+    max_depth: usize,
+    max_cubes: usize,
+    threads: Option<usize>,
+) -> Result<i32, Error> {
+    let contents = input::read(input)?;
+    let mut cuber = Cuber::from_cnf(&mut &contents[..])
+        .map_err(|error| Error::decode_cnf(input, error))?;
+    let cubes = cuber.cubes(max_depth, max_cubes);
+    let len_threads = match threads {
+        Some(len_threads) => len_threads,
+        None => {
+            cuber
+                .write_icnf(&mut io::stdout().lock(), &cubes)
+                .expect("couldn't write to standard output");
+            return Ok(EXIT_SUCCESS)
+        }
+    };
+    println!("c solving {} cubes ...", cubes.len());
+    let code = match cuber.solve_cubes(&cubes, len_threads) {
+        Ok(CubeResult::Sat { cube, model }) => {
+            println!("c satisfiable cube {}", cube + 1);
+            println!("s SATISFIABLE");
+            print_model(&model, Format::Dimacs);
+            EXIT_SATISFIABLE
+        }
+        Ok(CubeResult::Unsat) => {
+            println!("s UNSATISFIABLE");
+            EXIT_UNSATISFIABLE
+        }
+        Ok(CubeResult::Unknown) => {
+            println!("s UNKNOWN");
+            EXIT_UNKNOWN
+        }
+        Err(error) => unknown(error),
+    };
+    Ok(code)
+}
+
+/// Extracts a minimal unsatisfiable subset of the clauses of the `.cnf` input file.
+///
+/// # Note
+///
+/// Prints the subset in the DIMACS format with a comment line that lists
+/// the positions of its clauses in the input starting at `1`.
+fn mus(input: &Path) -> Result<i32, Error> {
+    let contents = input::read(input)?;
+    let mut extractor = MusExtractor::from_cnf(&mut &contents[..])
+        .map_err(|error| Error::decode_cnf(input, error))?;
+    let indices = match extractor.extract() {
+        Some(indices) => indices,
+        None => {
+            println!("c the input clauses are satisfiable");
+            return Ok(EXIT_SUCCESS)
+        }
+    };
+    let positions = indices
+        .iter()
+        .map(|index| (index + 1).to_string())
+        .collect::<Vec<_>>();
+    println!(
+        "c minimal unsatisfiable subset of the input clauses {}",
+        positions.join(" ")
+    );
+    println!("p cnf {} {}", extractor.len_variables(), indices.len());
+    for index in indices {
+        for literal in extractor.clause(index) {
+            print!("{} ", literal);
         }
+        println!("0");
     }
+    Ok(EXIT_SUCCESS)
 }
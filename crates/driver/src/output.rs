@@ -0,0 +1,113 @@
+//! Output in the formats of the SAT, pseudo-Boolean and MaxSAT competitions.
+
+use solver::{
+    Literal,
+    Model,
+    Statistics,
+};
+use std::{
+    io,
+    io::Write,
+};
+
+/// The exit code if the result is unknown.
+pub const EXIT_UNKNOWN: i32 = 0;
+
+/// The exit code of subcommands that do not decide the satisfiability of their input.
+pub const EXIT_SUCCESS: i32 = 0;
+
+/// The exit code if the input could not be solved due to an error.
+pub const EXIT_ERROR: i32 = 1;
+
+/// The exit code if the instance is satisfiable.
+pub const EXIT_SATISFIABLE: i32 = 10;
+
+/// The exit code if the instance is unsatisfiable.
+pub const EXIT_UNSATISFIABLE: i32 = 20;
+
+/// The exit code if an optimal solution has been found.
+pub const EXIT_OPTIMUM: i32 = 30;
+
+/// The maximum width of `v` lines including the leading `v`.
+const LINE_WIDTH: usize = 80;
+
+/// Writes the values as `v` lines that are wrapped at the maximum line width.
+fn write_values<W, I>(out: &mut W, values: I) -> io::Result<()>
+where
+    W: Write,
+    I: IntoIterator,
+    I::Item: AsRef<str>,
+{
+    let mut line = String::from("v");
+    for value in values {
+        let value = value.as_ref();
+        if line.len() > 1 && line.len() + 1 + value.len() > LINE_WIDTH {
+            writeln!(out, "{}", line)?;
+            line.truncate(1);
+        }
+        line.push(' ');
+        line.push_str(value);
+    }
+    writeln!(out, "{}", line)
+}
+
+/// The format of the printed literals.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Format {
+    /// Literals are signed variable indices and terminated by `0`.
+    Dimacs,
+    /// Literals are signed variable names of the pseudo-Boolean competition.
+    Opb,
+}
+
+/// Writes the literals as `v` lines in the format.
+pub fn write_literals<W, I>(out: &mut W, literals: I, format: Format) -> io::Result<()>
+where
+    W: Write,
+    I: IntoIterator<Item = Literal>,
+{
+    let values = literals.into_iter().map(|literal| {
+        match format {
+            Format::Dimacs => literal.to_string(),
+            Format::Opb => format!("{}x{}", literal.sign(), literal.variable()),
+        }
+    });
+    match format {
+        Format::Dimacs => write_values(out, values.chain(Some("0".to_string()))),
+        Format::Opb => write_values(out, values),
+    }
+}
+
+/// Prints the literals as `v` lines in the format.
+pub fn print_literals<I>(literals: I, format: Format)
+where
+    I: IntoIterator<Item = Literal>,
+{
+    write_literals(&mut io::stdout().lock(), literals, format)
+        .expect("couldn't write to standard output")
+}
+
+/// Prints the one-based positions as `v` lines terminated by `0`.
+pub fn print_positions<I>(positions: I)
+where
+    I: IntoIterator<Item = usize>,
+{
+    let values = positions.into_iter().map(|position| position.to_string());
+    write_values(
+        &mut io::stdout().lock(),
+        values.chain(Some("0".to_string())),
+    )
+    .expect("couldn't write to standard output")
+}
+
+/// Prints the model as `v` lines in the format.
+pub fn print_model(model: &Model, format: Format) {
+    print_literals(model, format)
+}
+
+/// Prints the statistics of the solver as `c` lines.
+pub fn print_statistics(stats: &Statistics) {
+    for line in stats.to_string().lines() {
+        println!("c {}", line);
+    }
+}
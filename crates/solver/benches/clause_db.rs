@@ -1,4 +1,3 @@
-use core::iter::repeat;
 use criterion::{
     black_box,
     criterion_group,
@@ -33,11 +32,8 @@ criterion_main!(bench_clause_database);
 /// Returns a clause database with an amount of clauses of a given size.
 fn bench_database(params: BenchParams) -> (Vec<ClauseRef>, ClauseDatabase) {
     let mut db = ClauseDatabase::default();
-    let literals = (1i32..params.len_literals as i32)
-        .into_iter()
-        .map(Literal::from);
-    let clause_refs = repeat(literals)
-        .take(params.len_clauses)
+    let literals = (1i32..params.len_literals as i32).map(Literal::from);
+    let clause_refs = std::iter::repeat_n(literals, params.len_clauses)
         .map(|lits| db.alloc(lits))
         .collect::<Vec<_>>();
     (clause_refs, db)
@@ -84,7 +80,7 @@ fn alloc(c: &mut Criterion) {
             ClauseDatabase::default,
             |db| {
                 let literals = [1, 2, 3, 4, 5].map(Literal::from);
-                for lits in repeat(literals).take(count_clauses) {
+                for lits in std::iter::repeat_n(literals, count_clauses) {
                     black_box(db.alloc(lits));
                 }
             },
@@ -0,0 +1,287 @@
+use crate::{
+    assignment::{
+        Assignment,
+        DecisionLevel,
+        Reason,
+    },
+    clause_db::{
+        ClauseDatabase,
+        ClauseRef,
+    },
+    constraint_db::ConstraintDatabase,
+    decider::Decider,
+    literal::RegisterVariables,
+    Literal,
+    Variable,
+};
+use bounded::BoundedBitmap;
+
+/// The clause learned from a conflict.
+#[derive(Debug)]
+pub struct LearnedClause<'a> {
+    /// The literals of the learned clause.
+    ///
+    /// # Note
+    ///
+    /// - The first literal is the asserting literal that becomes unit after backjumping.
+    /// - If the clause has more than one literal the second literal is
+    ///   assigned at the backjump level.
+    pub literals: &'a [Literal],
+    /// The decision level to which the solver has to backjump.
+    pub backjump_level: DecisionLevel,
+    /// The literal block distance of the learned clause.
+    pub lbd: u32,
+    /// The clauses that have been resolved to derive the learned clause.
+    pub resolved: &'a [ClauseRef],
+}
+
+/// Analyzes conflicts using the first unique implication point (1UIP) scheme.
+#[derive(Debug, Default, Clone)]
+pub struct ConflictAnalyzer {
+    /// Variables that have been visited during the current analysis.
+    seen: BoundedBitmap<Variable, bool>,
+    /// The learned clause under construction.
+    learnt: Vec<Literal>,
+    /// Buffer for the clausal explanation of the currently resolved reason.
+    explanation: Vec<Literal>,
+    /// Buffer to compute the literal block distance of the learned clause.
+    levels: Vec<DecisionLevel>,
+    /// The clauses resolved during the current analysis.
+    resolved: Vec<ClauseRef>,
+}
+
+impl RegisterVariables for ConflictAnalyzer {
+    fn register_variables(&mut self, additional: usize) {
+        let total_variables = self.seen.len() + additional;
+        self.seen.resize_to_len(total_variables);
+    }
+}
+
+impl ConflictAnalyzer {
+    /// Returns `true` if the variable has been visited by the current analysis.
+    fn is_seen(seen: &BoundedBitmap<Variable, bool>, variable: Variable) -> bool {
+        seen.get(variable)
+            .expect("encountered unexpected invalid variable")
+    }
+
+    /// Marks the variable as visited or unvisited by the current analysis.
+    fn set_seen(
+        seen: &mut BoundedBitmap<Variable, bool>,
+        variable: Variable,
+        value: bool,
+    ) {
+        seen.set(variable, value)
+            .expect("encountered unexpected invalid variable")
+    }
+
+    /// Writes the clausal explanation of the reason into the explanation buffer.
+    ///
+    /// # Panics
+    ///
+    /// If the reason is a decision which cannot be explained.
+    pub fn explain(
+        explanation: &mut Vec<Literal>,
+        reason: Reason,
+        implied: Option<Literal>,
+        assignment: &Assignment,
+        clause_db: &ClauseDatabase,
+        constraint_db: &ConstraintDatabase,
+    ) {
+        match reason {
+            Reason::Clause(cref) => {
+                let clause = clause_db
+                    .resolve(cref)
+                    .expect("encountered unexpected invalid reason clause");
+                explanation.clear();
+                explanation.extend_from_slice(clause.literals().as_slice());
+            }
+            Reason::Constraint(cref) => {
+                constraint_db.explain(cref, implied, assignment, explanation)
+            }
+            Reason::Decision => {
+                panic!("encountered unexpected decision reason during conflict analysis")
+            }
+        }
+    }
+
+    /// Analyzes the conflict and returns the learned clause.
+    ///
+    /// Bumps the activity of all variables that take part in the conflict.
+    /// The clauses resolved on the way are reported with the learned clause.
+    ///
+    /// # Panics
+    ///
+    /// If the conflict occurred at the root decision level.
+    pub fn analyze(
+        &mut self,
+        conflict: Reason,
+        assignment: &Assignment,
+        clause_db: &ClauseDatabase,
+        constraint_db: &ConstraintDatabase,
+        decider: &mut Decider,
+    ) -> LearnedClause<'_> {
+        let current_level = assignment.current_decision_level();
+        assert!(
+            !current_level.is_root(),
+            "cannot analyze conflicts at the root level"
+        );
+        let Self {
+            seen,
+            learnt,
+            explanation,
+            levels,
+            resolved,
+        } = self;
+        learnt.clear();
+        resolved.clear();
+        // Placeholder for the asserting literal.
+        learnt.push(Literal::default());
+        let mut pending = 0_usize;
+        let mut reason = conflict;
+        let mut implied: Option<Literal> = None;
+        let mut position = assignment.trail_len();
+        let asserting = loop {
+            if let Reason::Clause(cref) = reason {
+                resolved.push(cref);
+            }
+            Self::explain(
+                explanation,
+                reason,
+                implied,
+                assignment,
+                clause_db,
+                constraint_db,
+            );
+            for &literal in explanation.iter() {
+                let variable = literal.variable();
+                if Some(variable) == implied.map(Literal::variable)
+                    || Self::is_seen(seen, variable)
+                {
+                    continue
+                }
+                let level = assignment.level(variable);
+                if level.is_root() {
+                    continue
+                }
+                Self::set_seen(seen, variable, true);
+                decider.bump_activity(variable);
+                if level == current_level {
+                    pending += 1;
+                } else {
+                    learnt.push(literal);
+                }
+            }
+            // Select the latest visited literal on the trail to be resolved next.
+            let next = loop {
+                position -= 1;
+                let literal = assignment.trail_literal(position);
+                if Self::is_seen(seen, literal.variable()) {
+                    break literal
+                }
+            };
+            Self::set_seen(seen, next.variable(), false);
+            pending -= 1;
+            if pending == 0 {
+                break !next
+            }
+            reason = assignment.reason(next.variable());
+            implied = Some(next);
+        };
+        learnt[0] = asserting;
+        for literal in &learnt[1..] {
+            Self::set_seen(seen, literal.variable(), false);
+        }
+        // Move the literal with the highest decision level to the second position
+        // so that it becomes watched together with the asserting literal.
+        let backjump_level = match learnt[1..]
+            .iter()
+            .enumerate()
+            .max_by_key(|(_, literal)| assignment.level(literal.variable()))
+        {
+            Some((index, literal)) => {
+                let level = assignment.level(literal.variable());
+                learnt.swap(1, index + 1);
+                level
+            }
+            None => DecisionLevel::ROOT,
+        };
+        levels.clear();
+        levels.extend(
+            learnt
+                .iter()
+                .map(|literal| assignment.level(literal.variable())),
+        );
+        levels.sort_unstable();
+        levels.dedup();
+        LearnedClause {
+            literals: learnt,
+            backjump_level,
+            lbd: levels.len() as u32,
+            resolved,
+        }
+    }
+
+    /// Computes the assumptions that imply the negation of the failed assumption.
+    ///
+    /// Writes the failed assumption followed by all assumptions that are
+    /// responsible for its falsification into `core`.
+    ///
+    /// # Note
+    ///
+    /// All decisions on the trail must be assumptions.
+    pub fn analyze_final(
+        &mut self,
+        failed: Literal,
+        assignment: &Assignment,
+        clause_db: &ClauseDatabase,
+        constraint_db: &ConstraintDatabase,
+        core: &mut Vec<Literal>,
+    ) {
+        let Self {
+            seen, explanation, ..
+        } = self;
+        core.clear();
+        core.push(failed);
+        let failed = failed.variable();
+        if assignment.level(failed).is_root() {
+            return
+        }
+        Self::set_seen(seen, failed, true);
+        let mut pending = 1_usize;
+        let mut position = assignment.trail_len();
+        while pending > 0 {
+            position -= 1;
+            let literal = assignment.trail_literal(position);
+            let variable = literal.variable();
+            if !Self::is_seen(seen, variable) {
+                continue
+            }
+            Self::set_seen(seen, variable, false);
+            pending -= 1;
+            let reason = assignment.reason(variable);
+            if reason == Reason::Decision {
+                core.push(literal);
+                continue
+            }
+            Self::explain(
+                explanation,
+                reason,
+                Some(literal),
+                assignment,
+                clause_db,
+                constraint_db,
+            );
+            for &antecedent in explanation.iter() {
+                let antecedent = antecedent.variable();
+                if antecedent == variable
+                    || Self::is_seen(seen, antecedent)
+                    || assignment.level(antecedent).is_root()
+                {
+                    continue
+                }
+                Self::set_seen(seen, antecedent, true);
+                pending += 1;
+            }
+        }
+    }
+}
@@ -3,27 +3,26 @@ mod partial;
 mod trail;
 mod watch_list;
 
+use self::watch_list::WatchList;
 pub use self::{
     model::{
         LastModel,
         Model,
-        ModelIter,
     },
     partial::PartialAssignment,
-};
-use self::{
     trail::{
         DecisionLevel,
         EnqueueLiteral,
+        Reason,
         Trail,
     },
-    watch_list::WatchList,
 };
 use crate::{
     clause_db::{
         ClauseRef,
         ResolvedClause,
     },
+    constraint_db::ConstraintDatabase,
     decider::RestoreVariable,
     ClauseDatabase,
     Literal,
@@ -31,7 +30,10 @@ use crate::{
     Sign,
     Variable,
 };
-use bounded::bounded_map;
+use bounded::{
+    bounded_map,
+    Index as _,
+};
 use core::fmt::{
     self,
     Display,
@@ -80,6 +82,8 @@ pub struct Assignment {
     trail: Trail,
     assignments: PartialAssignment,
     watchers: WatchList,
+    /// The number of literals propagated so far.
+    propagations: u64,
 }
 
 impl RegisterVariables for Assignment {
@@ -88,6 +92,10 @@ impl RegisterVariables for Assignment {
         self.assignments.register_variables(additional);
         self.watchers.register_variables(additional);
     }
+
+    fn recycle_variable(&mut self, variable: Variable) {
+        self.watchers.recycle_variable(variable);
+    }
 }
 
 impl Assignment {
@@ -103,21 +111,103 @@ impl Assignment {
         self.watchers.register_for_lit(!snd, fst, cref);
     }
 
+    /// Removes the watchers of clauses that have been deleted from the clause database.
+    pub fn remove_deleted_watchers(&mut self, clause_db: &ClauseDatabase) {
+        self.watchers.relocate(|cref| {
+            clause_db
+                .resolve(cref)
+                .filter(|clause| !clause.header().is_deleted())
+                .map(|_| cref)
+        });
+    }
+
+    /// Replaces the clause references of all watchers and reasons.
+    pub fn relocate_clauses<F>(&mut self, relocate: F)
+    where
+        F: Fn(ClauseRef) -> ClauseRef,
+    {
+        self.watchers.relocate(|cref| Some(relocate(cref)));
+        self.trail.relocate_reasons(relocate);
+    }
+
     /// Returns a view into the assignment.
     pub fn variable_assignment(&self) -> &PartialAssignment {
         &self.assignments
     }
 
-    /// Resets the assignment to the given decision level.
-    pub fn reset_to_level<D>(&mut self, level: DecisionLevel, decider: &mut D)
-    where
-        D: RestoreVariable,
-    {
+    /// Returns the current decision level.
+    pub fn current_decision_level(&self) -> DecisionLevel {
+        self.trail.current_decision_level()
+    }
+
+    /// Returns the number of literals propagated so far.
+    pub fn propagations(&self) -> u64 {
+        self.propagations
+    }
+
+    /// Resets the number of literals propagated so far.
+    pub fn reset_propagations(&mut self) {
+        self.propagations = 0;
+    }
+
+    /// Returns the number of assignments on the trail.
+    pub fn trail_len(&self) -> usize {
+        self.trail.len()
+    }
+
+    /// Returns the literal at the given position of the trail.
+    ///
+    /// # Panics
+    ///
+    /// If the position is out of bounds.
+    pub fn trail_literal(&self, position: usize) -> Literal {
+        self.trail.get(position)
+    }
+
+    /// Returns the reason why the assigned variable has been assigned.
+    pub fn reason(&self, variable: Variable) -> Reason {
+        self.trail.reason(variable)
+    }
+
+    /// Returns the decision level at which the assigned variable has been assigned.
+    pub fn level(&self, variable: Variable) -> DecisionLevel {
+        self.trail.level(variable)
+    }
+
+    /// Returns the trail position of the assigned variable.
+    pub fn position(&self, variable: Variable) -> usize {
+        self.trail.position(variable)
+    }
+
+    /// Returns `true` if the clause is the reason for a current assignment.
+    ///
+    /// # Note
+    ///
+    /// Clauses that are reasons must not be removed from the clause database.
+    pub fn is_reason(&self, cref: ClauseRef, clause: ResolvedClause) -> bool {
+        let implied = *clause.literals().first();
+        self.assignments.is_satisfied(implied) == Some(true)
+            && self.reason(implied.variable()) == Reason::Clause(cref)
+    }
+
+    /// Enqueues a literal with the given reason.
+    ///
+    /// This does not yet perform the actual unit propagation.
+    ///
+    /// # Errors
+    ///
+    /// - If the pushed literal is in conflict with the current assignment.
+    /// - If the literal has already been assigned.
+    pub fn enqueue(
+        &mut self,
+        literal: Literal,
+        reason: Reason,
+    ) -> Result<(), AssignmentError> {
         self.trail
-            .pop_to_level(level, &mut self.assignments, decider)
+            .enqueue_literal(literal, reason, &mut self.assignments)
     }
 
-    /// Enqueues a propagation literal.
+    /// Enqueues a propagation literal without a reason.
     ///
     /// This does not yet perform the actual unit propagation.
     ///
@@ -129,24 +219,25 @@ impl Assignment {
         &mut self,
         assumption: Literal,
     ) -> Result<(), AssignmentError> {
-        self.trail
-            .enqueue_literal(assumption, &mut self.assignments)
+        self.enqueue(assumption, Reason::Decision)
     }
 }
 
 /// The result of a propagation after a decision has been made.
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
 pub enum PropagationResult {
     /// Propagation led to a consistent assignment.
     Consistent,
     /// Propagation led to a conflicting assignment.
-    Conflict,
+    ///
+    /// Holds the clause or constraint that is falsified under the assignment.
+    Conflict(Reason),
 }
 
 impl PropagationResult {
     /// Returns `true` if the propagation yielded a conflict.
     pub fn is_conflict(self) -> bool {
-        matches!(self, Self::Conflict)
+        matches!(self, Self::Conflict(_))
     }
 }
 
@@ -156,42 +247,72 @@ impl Assignment {
         self.trail.bump_decision_level()
     }
 
-    /// Pops the decision level to the given level.
+    /// Pops the decision levels higher than the given level.
     ///
-    /// This also unassigned all variables assigned in the given decision level.
-    pub fn pop_decision_level<D>(&mut self, level: DecisionLevel, decider: &mut D)
-    where
+    /// This also unassigns all variables assigned in the popped decision levels.
+    pub fn pop_decision_level<D>(
+        &mut self,
+        level: DecisionLevel,
+        constraint_db: &mut ConstraintDatabase,
+        decider: &mut D,
+    ) where
         D: RestoreVariable,
     {
+        let new_len = self
+            .trail
+            .level_start(DecisionLevel::from_index(level.into_index() + 1));
+        constraint_db.backtrack(&self.trail, new_len);
         self.trail
             .pop_to_level(level, &mut self.assignments, decider)
     }
 
-    /// Propagates the enqueued assumptions.
-    pub fn propagate<D>(
-        &mut self,
-        clause_db: &mut ClauseDatabase,
-        decider: &mut D,
-    ) -> PropagationResult
+    /// Resets the assignment undoing all assignments including the root level.
+    pub fn reset<D>(&mut self, constraint_db: &mut ConstraintDatabase, decider: &mut D)
     where
         D: RestoreVariable,
     {
+        constraint_db.backtrack(&self.trail, 0);
+        self.trail.reset(&mut self.assignments, decider)
+    }
+
+    /// Propagates the enqueued assumptions.
+    ///
+    /// # Note
+    ///
+    /// Clauses are propagated before the constraints of the constraint database
+    /// since clause propagation is generally cheaper.
+    pub fn propagate(
+        &mut self,
+        clause_db: &mut ClauseDatabase,
+        constraint_db: &mut ConstraintDatabase,
+    ) -> PropagationResult {
         let Self {
             watchers,
             assignments,
             trail,
-            ..
+            propagations,
         } = self;
-        let level = trail.current_decision_level();
-        while let Some(propagation_literal) = trail.pop_enqueued() {
-            let result =
-                watchers.propagate(propagation_literal, clause_db, assignments, trail);
+        loop {
+            while let Some(propagation_literal) = trail.pop_enqueued() {
+                *propagations += 1;
+                let result = watchers.propagate(
+                    propagation_literal,
+                    clause_db,
+                    assignments,
+                    trail,
+                );
+                if result.is_conflict() {
+                    return result
+                }
+            }
+            let result = constraint_db.propagate(trail, assignments);
             if result.is_conflict() {
-                trail.pop_to_level(level, assignments, decider);
                 return result
             }
+            if trail.is_propagation_queue_empty() {
+                return PropagationResult::Consistent
+            }
         }
-        PropagationResult::Consistent
     }
 }
 
@@ -1,8 +1,5 @@
+pub use super::AssignmentError;
 use super::PartialAssignment;
-pub use super::{
-    Assignment,
-    AssignmentError,
-};
 use crate::{
     Bool,
     Literal,
@@ -50,6 +47,8 @@ impl LastModel {
 #[derive(Debug, Default, Clone, PartialEq, Eq)]
 pub struct Model {
     assignment: BoundedBitmap<Variable, Sign>,
+    /// Yields `true` for the variables that have been removed from the model.
+    removed: BoundedBitmap<Variable, bool>,
 }
 
 impl Display for Model {
@@ -86,6 +85,7 @@ impl Model {
             return Err(AssignmentError::IndeterminateAssignment)
         }
         self.assignment.resize_to_len(assignment.len());
+        self.removed = BoundedBitmap::default();
         for assiged in assignment {
             self.assignment
                 .set(assiged.variable(), assiged.sign())
@@ -94,8 +94,58 @@ impl Model {
         Ok(())
     }
 
+    /// Removes the assignments of all variables with an index of at least `len`.
+    pub(crate) fn truncate(&mut self, len: usize) {
+        if len >= self.assignment.len() {
+            return
+        }
+        let mut assignment = BoundedBitmap::with_len(len);
+        for (index, sign) in self.assignment.iter().enumerate().take(len) {
+            assignment
+                .set(Variable::from_index(index), sign)
+                .expect("unexpected invalid variable");
+        }
+        self.assignment = assignment;
+        if self.removed.len() > len {
+            let mut removed = BoundedBitmap::with_len(len);
+            for (index, is_removed) in self.removed.iter().enumerate().take(len) {
+                removed
+                    .set(Variable::from_index(index), is_removed)
+                    .expect("unexpected invalid variable");
+            }
+            self.removed = removed;
+        }
+    }
+
+    /// Removes the assignment of the variable.
+    ///
+    /// # Panics
+    ///
+    /// If the variable is not assigned by the model.
+    pub(crate) fn remove(&mut self, variable: Variable) {
+        let len = self.assignment.len();
+        assert!(
+            variable.into_index() < len,
+            "encountered unexpected invalid variable"
+        );
+        if self.removed.len() < len {
+            self.removed.resize_to_len(len);
+        }
+        self.removed
+            .set(variable, true)
+            .expect("unexpected invalid variable");
+    }
+
+    /// Returns `true` if the assignment of the variable has been removed.
+    fn is_removed(&self, variable: Variable) -> bool {
+        self.removed.get(variable).unwrap_or(false)
+    }
+
     /// Resolves the assingment of the given variable.
     fn resolve(&self, variable: Variable) -> Result<Sign, AssignmentError> {
+        if self.is_removed(variable) {
+            return Err(AssignmentError::InvalidVariable)
+        }
         self.assignment
             .get(variable)
             .map_err(|_| AssignmentError::InvalidVariable)
@@ -120,12 +170,14 @@ impl<'a> IntoIterator for &'a Model {
 }
 
 pub struct ModelIter<'a> {
+    model: &'a Model,
     iter: iter::Enumerate<bounded_bitmap::Iter<'a, Variable, Sign>>,
 }
 
 impl<'a> ModelIter<'a> {
     pub fn new(model: &'a Model) -> Self {
         Self {
+            model,
             iter: model.assignment.iter().enumerate(),
         }
     }
@@ -135,8 +187,12 @@ impl<'a> Iterator for ModelIter<'a> {
     type Item = Literal;
 
     fn next(&mut self) -> Option<Self::Item> {
-        self.iter.next().map(|(index, assignment)| {
-            Literal::new(Variable::from_index(index), assignment)
+        self.iter.by_ref().find_map(|(index, assignment)| {
+            let variable = Variable::from_index(index);
+            if self.model.is_removed(variable) {
+                return None
+            }
+            Some(Literal::new(variable, assignment))
         })
     }
 }
@@ -3,12 +3,15 @@ use super::{
     PartialAssignment,
 };
 use crate::{
+    clause_db::ClauseRef,
+    constraint_db::ConstraintRef,
     decider::RestoreVariable,
     Literal,
     RegisterVariables,
     Variable,
 };
 use bounded::{
+    BoundedArray,
     BoundedStack,
     Index,
 };
@@ -28,7 +31,13 @@ impl Index for TrailLimit {
     }
 }
 
-#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
+/// The decision level of an assignment.
+///
+/// # Note
+///
+/// Decision level 0 is the root level that holds all assignments
+/// that are implied by the problem facts without any decision.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
 #[repr(transparent)]
 pub struct DecisionLevel(u32);
 
@@ -43,47 +52,72 @@ impl Index for DecisionLevel {
     }
 }
 
-#[derive(Debug, Clone)]
-pub struct TrailLimits {
-    limits: Vec<TrailLimit>,
-}
+impl DecisionLevel {
+    /// The root decision level.
+    pub const ROOT: Self = Self(0);
 
-impl Default for TrailLimits {
-    fn default() -> Self {
-        Self {
-            limits: vec![TrailLimit(0)],
-        }
+    /// Returns `true` if this is the root decision level.
+    #[inline]
+    pub fn is_root(self) -> bool {
+        self == Self::ROOT
     }
 }
 
+/// The reason why a variable has been assigned.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub enum Reason {
+    /// The variable was assigned without an antecedent.
+    ///
+    /// This is the case for decisions, assumptions and problem facts.
+    #[default]
+    Decision,
+    /// The variable was implied by the referenced clause.
+    Clause(ClauseRef),
+    /// The variable was implied by the referenced constraint.
+    ///
+    /// # Note
+    ///
+    /// The clausal explanation of the implication is generated lazily
+    /// by the constraint database upon conflict analysis.
+    Constraint(ConstraintRef),
+}
+
+/// Information stored for every assigned variable.
+#[derive(Debug, Default, Copy, Clone)]
+struct AssignmentInfo {
+    /// The reason why the variable has been assigned.
+    reason: Reason,
+    /// The decision level at which the variable has been assigned.
+    level: DecisionLevel,
+    /// The position of the assignment on the trail.
+    position: u32,
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct TrailLimits {
+    limits: Vec<TrailLimit>,
+}
+
 impl TrailLimits {
-    /// Pushes a new limit to the trail limits.
+    /// Pushes a new limit to the trail limits and returns the new decision level.
     pub fn push(&mut self, new_limit: TrailLimit) -> DecisionLevel {
-        let index = self.limits.len();
         self.limits.push(new_limit);
-        DecisionLevel::from_index(index)
-    }
-
-    /// Returns the last trail limit.
-    pub fn last(&self) -> TrailLimit {
-        *self
-            .limits
-            .last()
-            .expect("encountered unexpected empty trail limits")
+        self.current_decision_level()
     }
 
     /// Pops the trail limits to the given decision level.
-    pub fn pop_to_level(&mut self, level: DecisionLevel) -> TrailLimit {
-        assert!(level.into_index() >= 1);
-        assert!(level.into_index() < self.limits.len());
-        self.limits.truncate(level.into_index() + 1);
-        self.last()
+    ///
+    /// Returns the trail limit of the first popped decision level
+    /// or `None` if no decision level has been popped.
+    pub fn pop_to_level(&mut self, level: DecisionLevel) -> Option<TrailLimit> {
+        let limit = self.limits.get(level.into_index()).copied();
+        self.limits.truncate(level.into_index());
+        limit
     }
 
     /// Returns the current decision level.
     pub fn current_decision_level(&self) -> DecisionLevel {
-        let index = self.limits.len();
-        DecisionLevel::from_index(index)
+        DecisionLevel::from_index(self.limits.len())
     }
 }
 
@@ -99,10 +133,10 @@ pub trait EnqueueLiteral {
     ///
     /// - If the enqueued literal has already been satisfied.
     /// - If the enqueued literal is in conflict with the current assignment.
-    ///   This also clears the propagation queue.
     fn enqueue_literal(
         &mut self,
         literal: Literal,
+        reason: Reason,
         assignment: &mut PartialAssignment,
     ) -> Result<(), AssignmentError>;
 }
@@ -112,6 +146,7 @@ impl EnqueueLiteral for Trail {
     fn enqueue_literal(
         &mut self,
         literal: Literal,
+        reason: Reason,
         assignment: &mut PartialAssignment,
     ) -> Result<(), AssignmentError> {
         match assignment.is_conflicting(literal) {
@@ -119,6 +154,14 @@ impl EnqueueLiteral for Trail {
             Some(false) => return Err(AssignmentError::AlreadyAssigned),
             None => (),
         }
+        let info = AssignmentInfo {
+            reason,
+            level: self.current_decision_level(),
+            position: self.decisions_and_implications.len() as u32,
+        };
+        self.infos
+            .update(literal.variable(), info)
+            .expect("encountered unexpected invalid variable");
         self.decisions_and_implications.push(literal);
         assignment.assign(literal.variable(), literal.sign());
         Ok(())
@@ -131,6 +174,7 @@ pub struct Trail {
     propagate_head: usize,
     decisions_and_implications: BoundedStack<Literal>,
     limits: TrailLimits,
+    infos: BoundedArray<Variable, AssignmentInfo>,
 }
 
 impl RegisterVariables for Trail {
@@ -138,6 +182,7 @@ impl RegisterVariables for Trail {
         let total_variables = self.len_variables() + additional;
         self.decisions_and_implications
             .resize_capacity(total_variables);
+        self.infos.resize_with(total_variables, Default::default);
     }
 }
 
@@ -147,6 +192,66 @@ impl Trail {
         self.decisions_and_implications.capacity()
     }
 
+    /// Returns the number of assignments on the trail.
+    pub fn len(&self) -> usize {
+        self.decisions_and_implications.len()
+    }
+
+    /// Returns the literal at the given trail position.
+    ///
+    /// # Panics
+    ///
+    /// If the position is out of bounds.
+    pub fn get(&self, position: usize) -> Literal {
+        self.decisions_and_implications[position]
+    }
+
+    /// Returns the information stored for the assigned variable.
+    ///
+    /// # Note
+    ///
+    /// The returned information is meaningless for unassigned variables.
+    fn info(&self, variable: Variable) -> &AssignmentInfo {
+        self.infos
+            .get(variable)
+            .expect("encountered unexpected invalid variable")
+    }
+
+    /// Returns the reason of the assigned variable.
+    pub fn reason(&self, variable: Variable) -> Reason {
+        self.info(variable).reason
+    }
+
+    /// Returns the decision level of the assigned variable.
+    pub fn level(&self, variable: Variable) -> DecisionLevel {
+        self.info(variable).level
+    }
+
+    /// Returns the trail position of the assigned variable.
+    pub fn position(&self, variable: Variable) -> usize {
+        self.info(variable).position as usize
+    }
+
+    /// Replaces the clause references of the reasons of all assigned variables.
+    pub fn relocate_reasons<F>(&mut self, mut relocate: F)
+    where
+        F: FnMut(ClauseRef) -> ClauseRef,
+    {
+        let Self {
+            decisions_and_implications,
+            infos,
+            ..
+        } = self;
+        for literal in decisions_and_implications.iter() {
+            let info = infos
+                .get_mut(literal.variable())
+                .expect("encountered unexpected invalid variable");
+            if let Reason::Clause(cref) = info.reason {
+                info.reason = Reason::Clause(relocate(cref));
+            }
+        }
+    }
+
     /// Pushes a new decision level and returns it.
     pub fn bump_decision_level(&mut self) -> DecisionLevel {
         let limit = TrailLimit::from_index(self.decisions_and_implications.len());
@@ -158,11 +263,22 @@ impl Trail {
         self.limits.current_decision_level()
     }
 
-    /// Returns `true` if the propagation queue is empty.
-    fn is_propagation_queue_empty(&self) -> bool {
-        if self.decisions_and_implications.is_empty() {
-            return true
+    /// Returns the trail length at the start of the given decision level.
+    ///
+    /// Returns the current trail length if the decision level does not exist.
+    pub fn level_start(&self, level: DecisionLevel) -> usize {
+        if level.is_root() {
+            return 0
         }
+        self.limits
+            .limits
+            .get(level.into_index() - 1)
+            .map(|limit| limit.into_index())
+            .unwrap_or_else(|| self.len())
+    }
+
+    /// Returns `true` if the propagation queue is empty.
+    pub fn is_propagation_queue_empty(&self) -> bool {
         self.propagate_head == self.decisions_and_implications.len()
     }
 
@@ -177,6 +293,9 @@ impl Trail {
     }
 
     /// Backjumps the trail to the given decision level.
+    ///
+    /// All assignments of decision levels higher than `level` are undone.
+    /// Does nothing if the current decision level is not higher than `level`.
     pub fn pop_to_level<D>(
         &mut self,
         level: DecisionLevel,
@@ -185,14 +304,34 @@ impl Trail {
     ) where
         D: RestoreVariable,
     {
-        let level = DecisionLevel::from_index(level.into_index() - 1);
-        let limit = self.limits.pop_to_level(level);
-        self.propagate_head = limit.into_index();
-        self.decisions_and_implications
-            .pop_to(limit.into_index(), |popped| {
-                let variable = popped.variable();
-                assignments.unassign(variable);
-                decider.restore_variable(variable)
-            });
+        if let Some(limit) = self.limits.pop_to_level(level) {
+            self.pop_to(limit.into_index(), assignments, decider)
+        }
+    }
+
+    /// Resets the trail undoing all assignments including the root level.
+    pub fn reset<D>(&mut self, assignments: &mut PartialAssignment, decider: &mut D)
+    where
+        D: RestoreVariable,
+    {
+        self.limits.pop_to_level(DecisionLevel::ROOT);
+        self.pop_to(0, assignments, decider)
+    }
+
+    /// Pops all assignments from the trail until it has the given length.
+    fn pop_to<D>(
+        &mut self,
+        new_len: usize,
+        assignments: &mut PartialAssignment,
+        decider: &mut D,
+    ) where
+        D: RestoreVariable,
+    {
+        self.propagate_head = self.propagate_head.min(new_len);
+        self.decisions_and_implications.pop_to(new_len, |popped| {
+            let variable = popped.variable();
+            assignments.unassign(variable);
+            decider.restore_variable(variable)
+        });
     }
 }
@@ -3,6 +3,7 @@ use super::{
     EnqueueLiteral,
     PartialAssignment,
     PropagationResult,
+    Reason,
 };
 use crate::{
     clause_db::{
@@ -77,6 +78,11 @@ impl VariableWatchers {
     ///
     /// Returns a propagation result that either tells that the propagation
     /// yielded a consistent assignemnt or a conflict.
+    ///
+    /// # Note
+    ///
+    /// Watchers of clauses that have been removed from the clause database
+    /// are dropped lazily when they are encountered.
     fn propagate<Q, W>(
         &mut self,
         literal: Literal,
@@ -89,11 +95,11 @@ impl VariableWatchers {
         Q: EnqueueLiteral,
         W: EnqueueWatcher,
     {
-        let mut seen_conflict = false;
+        let mut conflict = None;
         let watchers = self.literal_watchers_mut(literal);
         watchers.retain(|&watcher| {
             // Closure returns `false` if the watcher needs to be removed.
-            if seen_conflict {
+            if conflict.is_some() {
                 return true
             }
             if let Some(true) = assignment.is_satisfied(watcher.blocker) {
@@ -101,17 +107,22 @@ impl VariableWatchers {
                 return true
             }
             let watcher = watcher.watcher;
-            let result = clause_db
+            let mut clause = clause_db
                 .resolve_mut(watcher)
-                .expect("encountered unexpected invalid clause ID")
-                .literals_mut()
-                .propagate(literal, assignment);
+                .expect("encountered unexpected invalid clause ID");
+            if clause.header().is_deleted() {
+                return false
+            }
+            let result = clause.literals_mut().propagate(literal, assignment);
             match result {
                 ClausePropagationResult::UnitUnderAssignment(unit_literal) => {
-                    let enqueue_result =
-                        propagation_queue.enqueue_literal(unit_literal, assignment);
+                    let enqueue_result = propagation_queue.enqueue_literal(
+                        unit_literal,
+                        Reason::Clause(watcher),
+                        assignment,
+                    );
                     if let Err(AssignmentError::ConflictingAssignment) = enqueue_result {
-                        seen_conflict = true;
+                        conflict = Some(watcher);
                     }
                     true
                 }
@@ -124,9 +135,9 @@ impl VariableWatchers {
                 }
             }
         });
-        match seen_conflict {
-            true => PropagationResult::Conflict,
-            false => PropagationResult::Consistent,
+        match conflict {
+            Some(cref) => PropagationResult::Conflict(Reason::Clause(cref)),
+            None => PropagationResult::Consistent,
         }
     }
 }
@@ -196,6 +207,16 @@ impl RegisterVariables for WatchList {
         let total_variables = self.len_variables() + additional;
         self.watchers.resize_with(total_variables, Default::default);
     }
+
+    fn recycle_variable(&mut self, variable: Variable) {
+        // Only watchers of deleted clauses may remain for a released variable.
+        let watchers = self
+            .watchers
+            .get_mut(variable)
+            .expect("encountered unexpected invalid variable");
+        watchers.pos.clear();
+        watchers.neg.clear();
+    }
 }
 
 impl WatchList {
@@ -217,6 +238,28 @@ impl WatchList {
             .register_for_lit(watched, blocker, watcher)
     }
 
+    /// Replaces the clause references of all watchers.
+    ///
+    /// Watchers for which `relocate` returns `None` are removed.
+    pub fn relocate<F>(&mut self, mut relocate: F)
+    where
+        F: FnMut(ClauseRef) -> Option<ClauseRef>,
+    {
+        for watchers in self.watchers.iter_mut() {
+            for literal_watchers in [&mut watchers.pos, &mut watchers.neg] {
+                literal_watchers.retain_mut(|watcher| {
+                    match relocate(watcher.watcher) {
+                        Some(relocated) => {
+                            watcher.watcher = relocated;
+                            true
+                        }
+                        None => false,
+                    }
+                });
+            }
+        }
+    }
+
     /// Propagates the literal assignment to the watching clauses.
     pub fn propagate<Q>(
         &mut self,
@@ -0,0 +1,101 @@
+use crate::{
+    Literal,
+    Model,
+    SolveResult,
+    Solver,
+};
+
+/// The number of candidates whose negations are assumed at once.
+const CHUNK_SIZE: usize = 20;
+
+/// Computes the backbone of the solver under the assumptions.
+///
+/// Returns `None` if the solver is unsatisfiable under the assumptions.
+///
+/// # Note
+///
+/// Starts with the literals of a model as backbone candidates except for the
+/// released variables and the activation variables of open scopes and processes
+/// them in chunks by assuming the negations of all candidates of a chunk at
+/// once. Every found model removes all candidates it falsifies. If no model
+/// exists and the unsatisfiable core contains the negation of a single candidate
+/// it is a backbone literal. Otherwise the candidates of the core are tested
+/// individually later on. Found backbone literals are assumed for the rest of
+/// the computation.
+pub(crate) fn backbone(
+    solver: &mut Solver,
+    mut assumptions: Vec<Literal>,
+) -> Option<Vec<Literal>> {
+    let mut candidates = match solve(solver, &assumptions) {
+        Some(model) => {
+            model
+                .into_iter()
+                .filter(|literal| !solver.is_internal(literal.variable()))
+                .collect::<Vec<_>>()
+        }
+        None => return None,
+    };
+    let mut backbone = Vec::new();
+    let mut singles = Vec::new();
+    let len_assumptions = assumptions.len();
+    while !candidates.is_empty() {
+        let split = candidates.len().saturating_sub(CHUNK_SIZE);
+        let mut chunk = candidates.split_off(split);
+        while !chunk.is_empty() {
+            assumptions.truncate(len_assumptions);
+            assumptions.extend(backbone.iter().copied());
+            assumptions.extend(chunk.iter().map(|&literal| !literal));
+            match solve(solver, &assumptions) {
+                Some(model) => {
+                    // All candidates of the chunk are falsified by the model.
+                    chunk.clear();
+                    filter(&mut candidates, &model);
+                    filter(&mut singles, &model);
+                }
+                None => {
+                    let core = solver.failed_assumptions();
+                    let (core_chunk, rest) = chunk
+                        .iter()
+                        .partition::<Vec<_>, _>(|&&literal| core.contains(&!literal));
+                    match *core_chunk {
+                        [] => panic!("encountered unexpected unsatisfiable formula"),
+                        [literal] => backbone.push(literal),
+                        _ => singles.extend(core_chunk),
+                    }
+                    chunk = rest;
+                }
+            }
+        }
+        while let Some(literal) = singles.pop() {
+            assumptions.truncate(len_assumptions);
+            assumptions.extend(backbone.iter().copied());
+            assumptions.push(!literal);
+            match solve(solver, &assumptions) {
+                Some(model) => {
+                    filter(&mut candidates, &model);
+                    filter(&mut singles, &model);
+                }
+                None => backbone.push(literal),
+            }
+        }
+    }
+    backbone.sort_unstable_by_key(|literal| literal.variable());
+    Some(backbone)
+}
+
+/// Solves under the assumptions and returns the model if any.
+fn solve(solver: &mut Solver, assumptions: &[Literal]) -> Option<Model> {
+    let result = solver
+        .solve(assumptions.iter().copied())
+        .expect("encountered unexpected error during solving");
+    match result {
+        SolveResult::Sat(sat) => Some(sat.model().clone()),
+        SolveResult::Unsat => None,
+        SolveResult::Unknown => panic!("encountered unexpected interrupted search"),
+    }
+}
+
+/// Removes all candidates that are falsified by the model.
+fn filter(candidates: &mut Vec<Literal>, model: &Model) {
+    candidates.retain(|&literal| model.is_satisfied(literal) == Ok(true));
+}
@@ -1,8 +1,11 @@
 use crate::{
     Error,
     Literal,
+    Sign,
     Solver,
+    Variable,
 };
+use bounded::Index as _;
 use cnf_parser::Output;
 
 #[derive(Debug, Default)]
@@ -58,3 +61,75 @@ impl Output for SolverBuilder {
         Ok(())
     }
 }
+
+/// Types that consume the variables and clauses of `.cnf` input.
+pub trait ConsumeCnf: Default {
+    /// Registers the given amount of new variables.
+    fn new_variables(&mut self, amount: usize);
+
+    /// Consumes the clause of the input.
+    fn consume_cnf_clause(&mut self, literals: &[Literal]);
+}
+
+/// Feeds `.cnf` input to any [`ConsumeCnf`] implementer.
+#[derive(Debug, Default)]
+pub struct CnfBuilder<T> {
+    output: T,
+    num_variables: Option<usize>,
+    current_clause: Vec<Literal>,
+}
+
+impl<T> CnfBuilder<T>
+where
+    T: ConsumeCnf,
+{
+    fn finalize_current_clause(&mut self) -> Result<(), <Self as Output>::Error> {
+        if self.num_variables.is_none() {
+            return Err("missing problem line before clause inputs".into())
+        }
+        self.output.consume_cnf_clause(&self.current_clause);
+        self.current_clause.clear();
+        Ok(())
+    }
+
+    pub fn finalize(self) -> T {
+        self.output
+    }
+}
+
+impl<T> Output for CnfBuilder<T>
+where
+    T: ConsumeCnf,
+{
+    type Error = Error;
+
+    fn problem(
+        &mut self,
+        num_variables: u32,
+        _num_clauses: u32,
+    ) -> Result<(), Self::Error> {
+        let num_variables = num_variables as usize;
+        self.num_variables = Some(num_variables);
+        self.output.new_variables(num_variables);
+        Ok(())
+    }
+
+    fn literal(&mut self, literal: cnf_parser::Literal) -> Result<(), Self::Error> {
+        let value = literal.into_value().get();
+        let variable = Variable::from_index(value.unsigned_abs() as usize - 1);
+        let sign = if value > 0 { Sign::POS } else { Sign::NEG };
+        self.current_clause.push(Literal::new(variable, sign));
+        Ok(())
+    }
+
+    fn finalize_clause(&mut self) -> Result<(), Self::Error> {
+        self.finalize_current_clause()
+    }
+
+    fn finish(&mut self) -> Result<(), Self::Error> {
+        if !self.current_clause.is_empty() {
+            self.finalize_current_clause()?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,458 @@
+//! A Boolean circuit builder that encodes into clauses.
+//!
+//! Circuits are represented as and-inverter graphs: every gate is a binary
+//! conjunction and negations are stored on the edges. All other gates are
+//! expressed in terms of conjunctions and negations.
+
+#[cfg(test)]
+mod tests;
+
+use crate::{
+    Error,
+    Literal,
+    Model,
+    Sign,
+    Solver,
+    Variable,
+};
+use ahash::AHashMap;
+use core::ops::Not;
+
+/// A possibly negated output of a node of a [`Circuit`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Signal(u32);
+
+impl Signal {
+    /// The constant `false` signal.
+    pub const FALSE: Self = Self(0);
+    /// The constant `true` signal.
+    pub const TRUE: Self = Self(1);
+
+    /// Creates a new signal for the node at the index.
+    fn new(node: usize, negated: bool) -> Self {
+        let node = u32::try_from(node).expect("encountered too many circuit nodes");
+        Self((node << 1) | u32::from(negated))
+    }
+
+    /// Returns the index of the node of the signal.
+    fn node(self) -> usize {
+        (self.0 >> 1) as usize
+    }
+
+    /// Returns `true` if the signal negates the output of its node.
+    pub fn is_negated(self) -> bool {
+        self.0 & 1 != 0
+    }
+
+    /// Returns `true` if the signal is either constant `true` or `false`.
+    pub fn is_constant(self) -> bool {
+        self.node() == 0
+    }
+
+    /// Returns the polarity in which the node of the signal is used.
+    fn polarity(self) -> Polarity {
+        if self.is_negated() {
+            Polarity::Negative
+        } else {
+            Polarity::Positive
+        }
+    }
+}
+
+impl Not for Signal {
+    type Output = Self;
+
+    fn not(self) -> Self::Output {
+        Self(self.0 ^ 1)
+    }
+}
+
+/// The encoding used to encode the gates of a [`Circuit`] into clauses.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub enum GateEncoding {
+    /// Encodes the equivalence between every gate and its literal.
+    #[default]
+    Tseitin,
+    /// Encodes only the implications between gates and their literals that
+    /// are required for the polarities in which the gates are used.
+    ///
+    /// # Note
+    ///
+    /// This requires fewer clauses but the literals of gates used in only one
+    /// polarity are not determined by their inputs.
+    PlaistedGreenbaum,
+}
+
+/// The polarity in which a node is used.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+enum Polarity {
+    /// The literal of the node implies the node.
+    Positive,
+    /// The node implies the literal of the node.
+    Negative,
+}
+
+impl Not for Polarity {
+    type Output = Self;
+
+    fn not(self) -> Self::Output {
+        match self {
+            Self::Positive => Self::Negative,
+            Self::Negative => Self::Positive,
+        }
+    }
+}
+
+/// A node of the and-inverter graph.
+#[derive(Debug, Copy, Clone)]
+enum Node {
+    /// The constant `false` node.
+    False,
+    /// An input node that refers to a variable of the solver.
+    Input(Variable),
+    /// The conjunction of both signals.
+    And(Signal, Signal),
+}
+
+/// A Boolean circuit that is lazily encoded into clauses of a [`Solver`].
+///
+/// # Note
+///
+/// - Gates are structurally hashed so that building the same gate twice
+///   yields the same signal.
+/// - Constant inputs and trivial gates such as `a & !a` are simplified
+///   while building the circuit.
+/// - Only the gates that are reachable from the encoded signals are encoded.
+#[derive(Debug, Clone)]
+pub struct Circuit {
+    /// The encoding used for the gates.
+    encoding: GateEncoding,
+    /// All nodes of the circuit where the first node is the constant `false` node.
+    ///
+    /// Nodes only refer to nodes with smaller indices.
+    nodes: Vec<Node>,
+    /// The literal of every node that has already been encoded.
+    literals: Vec<Option<Literal>>,
+    /// The polarities in which every node has already been encoded.
+    encoded: Vec<[bool; 2]>,
+    /// The conjunction nodes indexed by their normalized inputs.
+    gates: AHashMap<(Signal, Signal), usize>,
+    /// The input nodes indexed by their variables.
+    inputs: AHashMap<Variable, usize>,
+}
+
+impl Default for Circuit {
+    fn default() -> Self {
+        Self::new(GateEncoding::default())
+    }
+}
+
+impl Circuit {
+    /// Creates a new empty circuit using the gate encoding.
+    pub fn new(encoding: GateEncoding) -> Self {
+        Self {
+            encoding,
+            nodes: vec![Node::False],
+            literals: vec![None],
+            encoded: vec![[false; 2]],
+            gates: AHashMap::default(),
+            inputs: AHashMap::default(),
+        }
+    }
+
+    /// Returns the number of conjunction gates of the circuit.
+    pub fn len_gates(&self) -> usize {
+        self.gates.len()
+    }
+
+    /// Pushes a new node and returns its index.
+    fn push_node(&mut self, node: Node) -> usize {
+        let index = self.nodes.len();
+        self.nodes.push(node);
+        self.literals.push(None);
+        self.encoded.push([false; 2]);
+        index
+    }
+
+    /// Returns the signal for the solver literal.
+    pub fn input(&mut self, literal: Literal) -> Signal {
+        let variable = literal.variable();
+        let node = match self.inputs.get(&variable) {
+            Some(&node) => node,
+            None => {
+                let node = self.push_node(Node::Input(variable));
+                self.inputs.insert(variable, node);
+                node
+            }
+        };
+        Signal::new(node, literal.sign().is_neg())
+    }
+
+    /// Returns the signal for the conjunction of both signals.
+    pub fn and(&mut self, a: Signal, b: Signal) -> Signal {
+        if a == Signal::FALSE || b == Signal::FALSE || a == !b {
+            return Signal::FALSE
+        }
+        if a == Signal::TRUE || a == b {
+            return b
+        }
+        if b == Signal::TRUE {
+            return a
+        }
+        let key = (a.min(b), a.max(b));
+        let node = match self.gates.get(&key) {
+            Some(&node) => node,
+            None => {
+                let node = self.push_node(Node::And(key.0, key.1));
+                self.gates.insert(key, node);
+                node
+            }
+        };
+        Signal::new(node, false)
+    }
+
+    /// Returns the signal for the disjunction of both signals.
+    pub fn or(&mut self, a: Signal, b: Signal) -> Signal {
+        !self.and(!a, !b)
+    }
+
+    /// Returns the signal for the exclusive disjunction of both signals.
+    pub fn xor(&mut self, a: Signal, b: Signal) -> Signal {
+        let a_not_b = self.and(a, !b);
+        let b_not_a = self.and(!a, b);
+        self.or(a_not_b, b_not_a)
+    }
+
+    /// Returns the signal that is `true` if both signals are equal.
+    pub fn eq(&mut self, a: Signal, b: Signal) -> Signal {
+        !self.xor(a, b)
+    }
+
+    /// Returns the signal for `then` if `condition` is `true` and `otherwise` else.
+    pub fn ite(&mut self, condition: Signal, then: Signal, otherwise: Signal) -> Signal {
+        if then == otherwise {
+            return then
+        }
+        let then = self.and(condition, then);
+        let otherwise = self.and(!condition, otherwise);
+        self.or(then, otherwise)
+    }
+
+    /// Combines the signals pairwise with the binary gate into a balanced tree.
+    fn balanced<I, F>(&mut self, signals: I, neutral: Signal, mut gate: F) -> Signal
+    where
+        I: IntoIterator<Item = Signal>,
+        F: FnMut(&mut Self, Signal, Signal) -> Signal,
+    {
+        let mut signals = signals.into_iter().collect::<Vec<_>>();
+        while signals.len() > 1 {
+            signals = signals
+                .chunks(2)
+                .map(|pair| {
+                    match *pair {
+                        [a, b] => gate(self, a, b),
+                        [a] => a,
+                        _ => unreachable!("encountered unexpected empty chunk"),
+                    }
+                })
+                .collect();
+        }
+        signals.pop().unwrap_or(neutral)
+    }
+
+    /// Returns the signal for the conjunction of all signals.
+    ///
+    /// # Note
+    ///
+    /// Returns [`Signal::TRUE`] for no signals.
+    pub fn and_all<I>(&mut self, signals: I) -> Signal
+    where
+        I: IntoIterator<Item = Signal>,
+    {
+        self.balanced(signals, Signal::TRUE, Self::and)
+    }
+
+    /// Returns the signal for the disjunction of all signals.
+    ///
+    /// # Note
+    ///
+    /// Returns [`Signal::FALSE`] for no signals.
+    pub fn or_all<I>(&mut self, signals: I) -> Signal
+    where
+        I: IntoIterator<Item = Signal>,
+    {
+        self.balanced(signals, Signal::FALSE, Self::or)
+    }
+
+    /// Returns the signal for the exclusive disjunction of all signals.
+    ///
+    /// # Note
+    ///
+    /// Returns [`Signal::FALSE`] for no signals.
+    pub fn xor_all<I>(&mut self, signals: I) -> Signal
+    where
+        I: IntoIterator<Item = Signal>,
+    {
+        self.balanced(signals, Signal::FALSE, Self::xor)
+    }
+
+    /// Returns the signal that is `true` if all signals are equal.
+    ///
+    /// # Note
+    ///
+    /// Returns [`Signal::TRUE`] for less than two signals.
+    pub fn eq_all<I>(&mut self, signals: I) -> Signal
+    where
+        I: IntoIterator<Item = Signal>,
+    {
+        let signals = signals.into_iter().collect::<Vec<_>>();
+        let all_true = self.and_all(signals.iter().copied());
+        let all_false = self.and_all(signals.iter().map(|&signal| !signal));
+        self.or(all_true, all_false)
+    }
+
+    /// Returns the literal of the node and creates it if necessary.
+    fn node_literal(&mut self, solver: &mut Solver, node: usize) -> Literal {
+        if let Some(literal) = self.literals[node] {
+            return literal
+        }
+        let literal = match self.nodes[node] {
+            Node::Input(variable) => Literal::new(variable, Sign::POS),
+            Node::False => {
+                let literal = solver.new_literal();
+                solver.consume_clause([!literal]);
+                literal
+            }
+            Node::And(_, _) => solver.new_literal(),
+        };
+        self.literals[node] = Some(literal);
+        literal
+    }
+
+    /// Returns the literal of the signal and creates it if necessary.
+    fn signal_literal(&mut self, solver: &mut Solver, signal: Signal) -> Literal {
+        let literal = self.node_literal(solver, signal.node());
+        if signal.is_negated() {
+            !literal
+        } else {
+            literal
+        }
+    }
+
+    /// Encodes all gates reachable from the node for the polarity.
+    fn encode_node(&mut self, solver: &mut Solver, node: usize, polarity: Polarity) {
+        let mut stack = vec![(node, polarity)];
+        while let Some((node, polarity)) = stack.pop() {
+            let polarities = match self.encoding {
+                GateEncoding::Tseitin => {
+                    [Some(Polarity::Positive), Some(Polarity::Negative)]
+                }
+                GateEncoding::PlaistedGreenbaum => [Some(polarity), None],
+            };
+            for polarity in polarities.into_iter().flatten() {
+                let encoded = &mut self.encoded[node][polarity as usize];
+                if *encoded {
+                    continue
+                }
+                *encoded = true;
+                let (a, b) = match self.nodes[node] {
+                    Node::And(a, b) => (a, b),
+                    Node::False | Node::Input(_) => continue,
+                };
+                let output = self.node_literal(solver, node);
+                let a_literal = self.signal_literal(solver, a);
+                let b_literal = self.signal_literal(solver, b);
+                match polarity {
+                    Polarity::Positive => {
+                        solver.consume_clause([!output, a_literal]);
+                        solver.consume_clause([!output, b_literal]);
+                        stack.push((a.node(), a.polarity()));
+                        stack.push((b.node(), b.polarity()));
+                    }
+                    Polarity::Negative => {
+                        solver.consume_clause([!a_literal, !b_literal, output]);
+                        stack.push((a.node(), !a.polarity()));
+                        stack.push((b.node(), !b.polarity()));
+                    }
+                }
+            }
+        }
+    }
+
+    /// Encodes the signal into the solver and returns a literal equivalent to it.
+    ///
+    /// # Note
+    ///
+    /// Only the gates reachable from the signal that have not yet been encoded
+    /// are encoded. The returned literal can be used for assumptions as well
+    /// as in clauses of either polarity.
+    ///
+    /// # Panics
+    ///
+    /// If the signal does not belong to this circuit.
+    pub fn literal(&mut self, solver: &mut Solver, signal: Signal) -> Literal {
+        self.encode_node(solver, signal.node(), Polarity::Positive);
+        self.encode_node(solver, signal.node(), Polarity::Negative);
+        self.signal_literal(solver, signal)
+    }
+
+    /// Encodes the signal into the solver and requires it to be `true`.
+    ///
+    /// # Note
+    ///
+    /// With the Plaisted–Greenbaum encoding only the clauses required for the
+    /// signal to be `true` are encoded.
+    ///
+    /// # Panics
+    ///
+    /// If the signal does not belong to this circuit.
+    pub fn assert(&mut self, solver: &mut Solver, signal: Signal) {
+        if signal == Signal::TRUE {
+            return
+        }
+        if signal == Signal::FALSE {
+            solver.consume_clause([]);
+            return
+        }
+        self.encode_node(solver, signal.node(), signal.polarity());
+        let literal = self.signal_literal(solver, signal);
+        solver.consume_clause([literal]);
+    }
+
+    /// Evaluates the signal under the model.
+    ///
+    /// # Errors
+    ///
+    /// If the model does not assign a variable of an input the signal depends on.
+    ///
+    /// # Panics
+    ///
+    /// If the signal does not belong to this circuit.
+    pub fn evaluate(&self, signal: Signal, model: &Model) -> Result<bool, Error> {
+        let root = signal.node();
+        let mut in_cone = vec![false; root + 1];
+        in_cone[root] = true;
+        for node in (0..=root).rev() {
+            if let (true, Node::And(a, b)) = (in_cone[node], self.nodes[node]) {
+                in_cone[a.node()] = true;
+                in_cone[b.node()] = true;
+            }
+        }
+        let mut values = vec![false; root + 1];
+        let value =
+            |values: &[bool], signal: Signal| values[signal.node()] ^ signal.is_negated();
+        for node in 0..=root {
+            if !in_cone[node] {
+                continue
+            }
+            values[node] = match self.nodes[node] {
+                Node::False => false,
+                Node::Input(variable) => {
+                    model.is_satisfied(Literal::new(variable, Sign::POS))?
+                }
+                Node::And(a, b) => value(&values, a) && value(&values, b),
+            };
+        }
+        Ok(value(&values, signal))
+    }
+}
@@ -0,0 +1,290 @@
+use super::*;
+use crate::{
+    tests::Lcg,
+    SolveResult,
+};
+
+const LEN_INPUTS: usize = 4;
+
+/// A signal together with its truth table over all assignments of the inputs.
+///
+/// The `n`-th bit of the truth table is the value of the signal under the
+/// assignment where the `i`-th input is `true` if the `i`-th bit of `n` is set.
+#[derive(Debug, Copy, Clone)]
+struct Tabled {
+    signal: Signal,
+    table: u16,
+}
+
+/// Returns the truth table of the `i`-th input.
+fn input_table(i: usize) -> u16 {
+    (0..1 << LEN_INPUTS)
+        .filter(|n| n & (1 << i) != 0)
+        .fold(0, |table, n| table | (1 << n))
+}
+
+/// Returns the assumptions for the inputs under the `n`-th assignment.
+fn assumptions(inputs: &[Literal], n: usize) -> Vec<Literal> {
+    inputs
+        .iter()
+        .enumerate()
+        .map(|(i, &input)| if n & (1 << i) != 0 { input } else { !input })
+        .collect()
+}
+
+/// Builds a random circuit over the inputs and returns all of its signals.
+fn random_circuit(
+    circuit: &mut Circuit,
+    inputs: &[Literal],
+    rng: &mut Lcg,
+) -> Vec<Tabled> {
+    let mut signals = vec![
+        Tabled {
+            signal: Signal::FALSE,
+            table: 0,
+        },
+        Tabled {
+            signal: Signal::TRUE,
+            table: u16::MAX,
+        },
+    ];
+    for (i, &input) in inputs.iter().enumerate() {
+        signals.push(Tabled {
+            signal: circuit.input(input),
+            table: input_table(i),
+        });
+        signals.push(Tabled {
+            signal: circuit.input(!input),
+            table: !input_table(i),
+        });
+    }
+    for _ in 0..40 {
+        let a = signals[rng.next(signals.len())];
+        let b = signals[rng.next(signals.len())];
+        let c = signals[rng.next(signals.len())];
+        let tabled = match rng.next(9) {
+            0 => {
+                Tabled {
+                    signal: circuit.and(a.signal, b.signal),
+                    table: a.table & b.table,
+                }
+            }
+            1 => {
+                Tabled {
+                    signal: circuit.or(a.signal, b.signal),
+                    table: a.table | b.table,
+                }
+            }
+            2 => {
+                Tabled {
+                    signal: circuit.xor(a.signal, b.signal),
+                    table: a.table ^ b.table,
+                }
+            }
+            3 => {
+                Tabled {
+                    signal: circuit.eq(a.signal, b.signal),
+                    table: !(a.table ^ b.table),
+                }
+            }
+            4 => {
+                Tabled {
+                    signal: circuit.ite(a.signal, b.signal, c.signal),
+                    table: (a.table & b.table) | (!a.table & c.table),
+                }
+            }
+            5 => {
+                Tabled {
+                    signal: circuit.and_all([a.signal, b.signal, c.signal]),
+                    table: a.table & b.table & c.table,
+                }
+            }
+            6 => {
+                Tabled {
+                    signal: circuit.or_all([a.signal, b.signal, c.signal]),
+                    table: a.table | b.table | c.table,
+                }
+            }
+            7 => {
+                Tabled {
+                    signal: circuit.xor_all([a.signal, b.signal, c.signal]),
+                    table: a.table ^ b.table ^ c.table,
+                }
+            }
+            _ => {
+                Tabled {
+                    signal: circuit.eq_all([a.signal, b.signal, c.signal]),
+                    table: (a.table & b.table & c.table)
+                        | (!a.table & !b.table & !c.table),
+                }
+            }
+        };
+        signals.push(tabled);
+    }
+    signals
+}
+
+/// Returns a fresh solver with the inputs.
+fn solver_with_inputs() -> (Solver, Vec<Literal>) {
+    let mut solver = Solver::default();
+    let inputs = solver
+        .new_literal_chunk(LEN_INPUTS)
+        .into_iter()
+        .collect::<Vec<_>>();
+    (solver, inputs)
+}
+
+#[test]
+fn structural_hashing_works() {
+    let (_, inputs) = solver_with_inputs();
+    let mut circuit = Circuit::default();
+    let a = circuit.input(inputs[0]);
+    let b = circuit.input(inputs[1]);
+    assert_eq!(circuit.input(!inputs[0]), !a);
+    assert_eq!(circuit.and(a, b), circuit.and(b, a));
+    assert_eq!(circuit.len_gates(), 1);
+    assert_eq!(circuit.or(a, b), !circuit.and(!b, !a));
+    assert_eq!(circuit.xor(a, b), !circuit.eq(b, a));
+    assert_eq!(circuit.len_gates(), 5);
+}
+
+#[test]
+fn constant_propagation_works() {
+    let (_, inputs) = solver_with_inputs();
+    let mut circuit = Circuit::default();
+    let a = circuit.input(inputs[0]);
+    let b = circuit.input(inputs[1]);
+    assert_eq!(circuit.and(a, Signal::FALSE), Signal::FALSE);
+    assert_eq!(circuit.and(Signal::TRUE, a), a);
+    assert_eq!(circuit.and(a, !a), Signal::FALSE);
+    assert_eq!(circuit.and(a, a), a);
+    assert_eq!(circuit.or(a, Signal::TRUE), Signal::TRUE);
+    assert_eq!(circuit.or(a, !a), Signal::TRUE);
+    assert_eq!(circuit.xor(a, Signal::FALSE), a);
+    assert_eq!(circuit.xor(a, Signal::TRUE), !a);
+    assert_eq!(circuit.xor(a, a), Signal::FALSE);
+    assert_eq!(circuit.eq(a, a), Signal::TRUE);
+    assert_eq!(circuit.ite(Signal::TRUE, a, b), a);
+    assert_eq!(circuit.ite(Signal::FALSE, a, b), b);
+    assert_eq!(circuit.ite(b, a, a), a);
+    assert_eq!(circuit.and_all([]), Signal::TRUE);
+    assert_eq!(circuit.or_all([]), Signal::FALSE);
+    assert_eq!(circuit.xor_all([a]), a);
+    assert_eq!(circuit.eq_all([a]), Signal::TRUE);
+    assert_eq!(circuit.len_gates(), 0);
+}
+
+#[test]
+fn evaluate_works() {
+    let mut rng = Lcg(42);
+    for _ in 0..20 {
+        let (mut solver, inputs) = solver_with_inputs();
+        let mut circuit = Circuit::default();
+        let signals = random_circuit(&mut circuit, &inputs, &mut rng);
+        for n in 0..1 << LEN_INPUTS {
+            let result = solver.solve(assumptions(&inputs, n)).unwrap();
+            let model = match result {
+                SolveResult::Sat(sat) => sat.model(),
+                _ => panic!("encountered unexpected non-SAT result"),
+            };
+            for tabled in &signals {
+                let expected = tabled.table & (1 << n) != 0;
+                assert_eq!(circuit.evaluate(tabled.signal, model), Ok(expected));
+            }
+        }
+    }
+}
+
+/// Asserts that the literal of every signal encoded into a single solver
+/// is equivalent to the signal.
+fn assert_literals_work(encoding: GateEncoding) {
+    let mut rng = Lcg(42);
+    for _ in 0..10 {
+        let (mut solver, inputs) = solver_with_inputs();
+        let mut circuit = Circuit::new(encoding);
+        let signals = random_circuit(&mut circuit, &inputs, &mut rng);
+        for _ in 0..10 {
+            let tabled = signals[rng.next(signals.len())];
+            let literal = circuit.literal(&mut solver, tabled.signal);
+            for n in 0..1 << LEN_INPUTS {
+                let expected = tabled.table & (1 << n) != 0;
+                let mut assumptions = assumptions(&inputs, n);
+                assumptions.push(literal);
+                let result = solver.solve(assumptions.clone()).unwrap();
+                assert_eq!(result.is_sat(), expected);
+                *assumptions.last_mut().unwrap() = !literal;
+                let result = solver.solve(assumptions).unwrap();
+                assert_eq!(result.is_sat(), !expected);
+            }
+        }
+    }
+}
+
+/// Asserts that asserting a signal is satisfiable exactly if the signal is `true`.
+fn assert_assert_works(encoding: GateEncoding) {
+    let mut rng = Lcg(42);
+    for _ in 0..10 {
+        let (_, inputs) = solver_with_inputs();
+        let mut circuit = Circuit::new(encoding);
+        let signals = random_circuit(&mut circuit, &inputs, &mut rng);
+        for _ in 0..10 {
+            let tabled = signals[rng.next(signals.len())];
+            let (mut solver, _) = solver_with_inputs();
+            let mut circuit = circuit.clone();
+            circuit.assert(&mut solver, tabled.signal);
+            for n in 0..1 << LEN_INPUTS {
+                let expected = tabled.table & (1 << n) != 0;
+                let result = solver.solve(assumptions(&inputs, n)).unwrap();
+                assert_eq!(result.is_sat(), expected);
+                if let SolveResult::Sat(sat) = result {
+                    assert_eq!(circuit.evaluate(tabled.signal, sat.model()), Ok(true));
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn tseitin_literals_work() {
+    assert_literals_work(GateEncoding::Tseitin)
+}
+
+#[test]
+fn tseitin_assert_works() {
+    assert_assert_works(GateEncoding::Tseitin)
+}
+
+#[test]
+fn plaisted_greenbaum_literals_work() {
+    assert_literals_work(GateEncoding::PlaistedGreenbaum)
+}
+
+#[test]
+fn plaisted_greenbaum_assert_works() {
+    assert_assert_works(GateEncoding::PlaistedGreenbaum)
+}
+
+#[test]
+fn plaisted_greenbaum_completes_polarities() {
+    let (mut solver, inputs) = solver_with_inputs();
+    let mut circuit = Circuit::new(GateEncoding::PlaistedGreenbaum);
+    let a = circuit.input(inputs[0]);
+    let b = circuit.input(inputs[1]);
+    let c = circuit.input(inputs[2]);
+    let a_and_b = circuit.and(a, b);
+    let gate = circuit.or(a_and_b, c);
+    // Only encodes `l(a & b) -> a & b` for the gate to be `true`.
+    circuit.assert(&mut solver, gate);
+    // Requesting the literal also encodes `a & b -> l(a & b)`.
+    let literal = circuit.literal(&mut solver, a_and_b);
+    for n in 0..1 << LEN_INPUTS {
+        let [a, b, c] = [0, 1, 2].map(|i| n & (1 << i) != 0);
+        let mut assumptions = assumptions(&inputs, n);
+        assumptions.push(literal);
+        let result = solver.solve(assumptions.clone()).unwrap();
+        assert_eq!(result.is_sat(), a && b);
+        *assumptions.last_mut().unwrap() = !literal;
+        let result = solver.solve(assumptions).unwrap();
+        assert_eq!(result.is_sat(), !(a && b) && c);
+    }
+}
@@ -2,8 +2,10 @@ use super::{
     ClauseDatabase,
     ClauseHeader,
     ClauseLength,
+    ClauseOffset,
     ClauseRef,
     ClauseWord,
+    LiveClauses,
     ResolvedClause,
     ResolvedClauseMut,
 };
@@ -12,16 +14,14 @@ use core::{
     fmt,
     fmt::{
         Debug,
+        Display,
         Formatter,
     },
     mem,
 };
 
 impl ClauseDatabase {
-    /// The maximum possible clause reference value.
-    const MAX_CLAUSE_REF: usize = u32::MAX as usize;
-
-    /// The maximum possible clause reference value.
+    /// The maximum possible clause length value.
     const MAX_CLAUSE_LEN: usize = u32::MAX as usize;
 
     /// Allocates a new clause to the clause database with the given literals.
@@ -32,41 +32,93 @@ impl ClauseDatabase {
     /// - If the newly allocated clause has more literals than allowed.
     /// - If the resulting clause reference would be out of valid bounds.
     pub fn alloc<I>(&mut self, literals: I) -> ClauseRef
+    where
+        I: IntoIterator,
+        I::IntoIter: ExactSizeIterator<Item = Literal>,
+    {
+        self.alloc_with_header(ClauseHeader::default(), literals)
+    }
+
+    /// Allocates a new clause with the given header and literals.
+    ///
+    /// # Panics
+    ///
+    /// - If the newly allocated clause has less than 2 literals.
+    /// - If the newly allocated clause has more literals than allowed.
+    /// - If the resulting clause reference would be out of valid bounds.
+    pub fn alloc_with_header<I>(&mut self, header: ClauseHeader, literals: I) -> ClauseRef
+    where
+        I: IntoIterator,
+        I::IntoIter: ExactSizeIterator<Item = Literal>,
+    {
+        self.try_alloc_with_header(header, literals)
+            .unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    /// Allocates a new clause to the clause database with the given literals.
+    ///
+    /// # Errors
+    ///
+    /// - If the newly allocated clause has less than 2 literals.
+    /// - If the newly allocated clause has more literals than allowed.
+    /// - If the resulting clause reference would be out of valid bounds
+    ///   or the memory for the clause cannot be allocated.
+    pub fn try_alloc<I>(&mut self, literals: I) -> Result<ClauseRef, AllocError>
+    where
+        I: IntoIterator,
+        I::IntoIter: ExactSizeIterator<Item = Literal>,
+    {
+        self.try_alloc_with_header(ClauseHeader::default(), literals)
+    }
+
+    /// Allocates a new clause with the given header and literals.
+    ///
+    /// # Errors
+    ///
+    /// - If the newly allocated clause has less than 2 literals.
+    /// - If the newly allocated clause has more literals than allowed.
+    /// - If the resulting clause reference would be out of valid bounds
+    ///   or the memory for the clause cannot be allocated.
+    pub fn try_alloc_with_header<I>(
+        &mut self,
+        header: ClauseHeader,
+        literals: I,
+    ) -> Result<ClauseRef, AllocError>
     where
         I: IntoIterator,
         I::IntoIter: ExactSizeIterator<Item = Literal>,
     {
         let literals = literals.into_iter();
         let len = literals.len();
-        assert!(
-            len >= 2,
-            "encountered short clause with less than 2 literals",
-        );
-        assert!(
-            len < Self::MAX_CLAUSE_LEN,
-            "encountered clause with too many literals"
-        );
+        if len < 2 {
+            return Err(AllocError::ShortClause)
+        }
+        if len >= Self::MAX_CLAUSE_LEN {
+            return Err(AllocError::TooManyLiterals)
+        }
         let current = self.words.len();
-        assert!(
-            current <= Self::MAX_CLAUSE_REF,
-            "out of memory to allocate more clauses"
-        );
+        if ClauseOffset::try_from(current).is_err() {
+            return Err(AllocError::OutOfMemory)
+        }
+        self.words
+            .try_reserve(len + 2)
+            .map_err(|_| AllocError::OutOfMemory)?;
         self.words.extend(
             [
-                ClauseWord::from(ClauseHeader::default()),
+                ClauseWord::from(header),
                 ClauseWord::from(ClauseLength::new(len as u32)),
             ]
             .into_iter()
             .chain(literals.map(ClauseWord::from)),
         );
         self.len_clauses += 1;
-        ClauseRef(current as u32)
+        Ok(ClauseRef::from_index(current))
     }
 
     /// Returns a shared reference to the clause words if the clause reference was valid.
     #[allow(unsafe_code)]
-    fn clause_words(words: &[ClauseWord], cref: ClauseRef) -> Option<ResolvedClause> {
-        let index = cref.into_u32() as usize;
+    fn clause_words(words: &[ClauseWord], cref: ClauseRef) -> Option<ResolvedClause<'_>> {
+        let index = cref.into_index();
         words
             .get(index + 1)
             .copied()
@@ -85,8 +137,8 @@ impl ClauseDatabase {
     fn clause_words_mut(
         words: &mut [ClauseWord],
         cref: ClauseRef,
-    ) -> Option<ResolvedClauseMut> {
-        let index = cref.into_u32() as usize;
+    ) -> Option<ResolvedClauseMut<'_>> {
+        let index = cref.into_index();
         words
             .get(index + 1)
             .copied()
@@ -101,12 +153,12 @@ impl ClauseDatabase {
     }
 
     /// Resolves the unresolved clause to a shared reference if it is valid.
-    pub fn resolve(&self, cref: ClauseRef) -> Option<ResolvedClause> {
+    pub fn resolve(&self, cref: ClauseRef) -> Option<ResolvedClause<'_>> {
         Self::clause_words(&self.words, cref)
     }
 
     /// Resolves the unresolved clause to an exclusive reference if it is valid.
-    pub fn resolve_mut(&mut self, cref: ClauseRef) -> Option<ResolvedClauseMut> {
+    pub fn resolve_mut(&mut self, cref: ClauseRef) -> Option<ResolvedClauseMut<'_>> {
         Self::clause_words_mut(&mut self.words, cref)
     }
 
@@ -169,8 +221,8 @@ impl ClauseDatabase {
                         // We cannot use `copy_from_slice` since slices might overlap.
                         words[alive + n] = words[current + n];
                     }
-                    let from_id = ClauseRef(current as u32);
-                    let into_id = ClauseRef(alive as u32);
+                    let from_id = ClauseRef::from_index(current);
+                    let into_id = ClauseRef::from_index(alive);
                     report(from_id, into_id);
                 }
                 alive += clause_len;
@@ -192,11 +244,60 @@ impl ClauseDatabase {
         self.len_clauses
     }
 
+    /// Returns the number of clause words including the words of deleted clauses.
+    #[inline]
+    pub fn len_words(&self) -> usize {
+        self.words.len()
+    }
+
+    /// Returns the number of clause words of deleted clauses that have not
+    /// yet been sweeped by the garbage collector.
+    #[inline]
+    pub fn len_freed_words(&self) -> usize {
+        self.freed_words
+    }
+
     /// Returns `true` if the clause database is empty.
     #[inline]
     pub fn is_empty(&self) -> bool {
         self.len() == 0
     }
+
+    /// Returns an iterator over the living clauses and their clause references.
+    ///
+    /// # Note
+    ///
+    /// Unlike iterating over `&ClauseDatabase` this skips clauses marked
+    /// as deleted that have not yet been sweeped by the garbage collector.
+    #[inline]
+    pub fn live_clauses(&self) -> LiveClauses<'_> {
+        LiveClauses::new(&self.words)
+    }
+}
+
+/// Errors that may be encountered when allocating a clause.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum AllocError {
+    /// The clause has less than 2 literals.
+    ShortClause,
+    /// The clause has more literals than a clause may have.
+    TooManyLiterals,
+    /// The clause database cannot store or refer to more clause words.
+    OutOfMemory,
+}
+
+impl Display for AllocError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::ShortClause => {
+                write!(f, "encountered short clause with less than 2 literals")
+            }
+            Self::TooManyLiterals => {
+                write!(f, "encountered clause with too many literals")
+            }
+            Self::OutOfMemory => write!(f, "out of memory to allocate more clauses"),
+        }
+    }
 }
 
 /// Tells a user if a clause was successfully removed and how many bytes it freed.
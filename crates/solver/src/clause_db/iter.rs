@@ -1,8 +1,13 @@
 use super::{
     ClauseDatabase,
+    ClauseRef,
     ClauseWord,
     ResolvedClause,
 };
+use core::ops::{
+    Bound,
+    RangeBounds,
+};
 
 impl<'a> IntoIterator for &'a ClauseDatabase {
     type IntoIter = ClauseDatabaseIter<'a>;
@@ -17,6 +22,12 @@ impl<'a> IntoIterator for &'a ClauseDatabase {
 }
 
 /// An iterator over the clauses stored in the clause database.
+///
+/// # Note
+///
+/// This also yields clauses marked as deleted that have not yet been
+/// sweeped by the garbage collector. Use [`ClauseDatabase::live_clauses`]
+/// to iterate over the clauses that have not been deleted.
 pub struct ClauseDatabaseIter<'a> {
     remaining_words: &'a [ClauseWord],
 }
@@ -37,3 +48,101 @@ impl<'a> Iterator for ClauseDatabaseIter<'a> {
         Some(ResolvedClause::new(clause_words))
     }
 }
+
+/// An iterator over the clauses of the clause database that have not been deleted.
+///
+/// # Note
+///
+/// The yielded clauses can be restricted to irredundant or learned clauses
+/// and to a range of clause lengths.
+pub struct LiveClauses<'a> {
+    words: &'a [ClauseWord],
+    /// The position of the next clause in the clause words.
+    position: usize,
+    /// Only clauses whose learned status equals this are yielded if any.
+    learnt: Option<bool>,
+    /// Only clauses with at least this many literals are yielded.
+    min_len: usize,
+    /// Only clauses with at most this many literals are yielded.
+    max_len: usize,
+}
+
+impl<'a> LiveClauses<'a> {
+    /// Creates an unfiltered iterator over the living clauses of the clause words.
+    pub(super) fn new(words: &'a [ClauseWord]) -> Self {
+        Self {
+            words,
+            position: 0,
+            learnt: None,
+            min_len: 0,
+            max_len: usize::MAX,
+        }
+    }
+
+    /// Only yields irredundant clauses, i.e. clauses that have not been learned.
+    pub fn irredundant(mut self) -> Self {
+        self.learnt = Some(false);
+        self
+    }
+
+    /// Only yields clauses that have been learned during conflict analysis.
+    pub fn learnt(mut self) -> Self {
+        self.learnt = Some(true);
+        self
+    }
+
+    /// Only yields clauses whose number of literals is within the range.
+    pub fn len_range<R>(mut self, range: R) -> Self
+    where
+        R: RangeBounds<usize>,
+    {
+        self.min_len = match range.start_bound() {
+            Bound::Included(&start) => start,
+            Bound::Excluded(&start) => start.saturating_add(1),
+            Bound::Unbounded => 0,
+        };
+        self.max_len = match range.end_bound() {
+            Bound::Included(&end) => end,
+            Bound::Excluded(&0) => {
+                // The empty range is expressed by a minimum above the maximum.
+                self.min_len = 1;
+                0
+            }
+            Bound::Excluded(&end) => end - 1,
+            Bound::Unbounded => usize::MAX,
+        };
+        self
+    }
+
+    /// Returns `true` if the clause is yielded by the iterator.
+    fn is_selected(&self, clause: ResolvedClause) -> bool {
+        let header = clause.header();
+        let len = clause.literals().len();
+        !header.is_deleted()
+            && self
+                .learnt
+                .is_none_or(|learnt| learnt == header.is_learnt())
+            && self.min_len <= len
+            && len <= self.max_len
+    }
+}
+
+impl<'a> Iterator for LiveClauses<'a> {
+    type Item = (ClauseRef, ResolvedClause<'a>);
+
+    #[allow(unsafe_code)]
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.position < self.words.len() {
+            let cref = ClauseRef::from_index(self.position);
+            // SAFETY: It is guaranteed that the clause word after the header is the clause length.
+            let len = unsafe { self.words[self.position + 1].as_len_words() };
+            let clause_words = &self.words[self.position..self.position + len];
+            self.position += len;
+            let clause = ResolvedClause::new(clause_words);
+            if self.is_selected(clause) {
+                return Some((cref, clause))
+            }
+        }
+        None
+    }
+}
@@ -11,8 +11,14 @@ use self::words::{
     ClauseWord,
 };
 pub use self::{
-    impls::ClauseRemoval,
-    iter::ClauseDatabaseIter,
+    impls::{
+        AllocError,
+        ClauseRemoval,
+    },
+    iter::{
+        ClauseDatabaseIter,
+        LiveClauses,
+    },
     resolved::{
         Literals,
         LiteralsMut,
@@ -26,15 +32,36 @@ pub use self::{
     },
 };
 
+/// The word offset of a clause in the clause database.
+///
+/// # Note
+///
+/// The `large-arena` crate feature enables 64-bit offsets so that the
+/// clause database may store more than `u32::MAX` clause words.
+#[cfg(not(feature = "large-arena"))]
+type ClauseOffset = u32;
+#[cfg(feature = "large-arena")]
+type ClauseOffset = u64;
+
 /// An unresolved reference to a clause stored in the clause database.
-#[derive(Debug, Copy, Clone, PartialEq, Eq)]
-pub struct ClauseRef(u32);
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct ClauseRef(ClauseOffset);
 
 impl ClauseRef {
-    /// Returns the `u32` representation of the unresolved clause reference.
+    /// Creates a clause reference from the word offset of the clause.
+    ///
+    /// # Note
+    ///
+    /// The offset must not exceed [`ClauseOffset::MAX`].
+    #[inline]
+    fn from_index(index: usize) -> Self {
+        Self(index as ClauseOffset)
+    }
+
+    /// Returns the word offset of the referenced clause.
     #[inline]
-    fn into_u32(self) -> u32 {
-        self.0
+    fn into_index(self) -> usize {
+        self.0 as usize
     }
 }
 
@@ -71,6 +71,24 @@ impl<'a> ResolvedClause<'a> {
         //         after the first two are the clause literals.
         unsafe { Literals::new(ClauseWord::as_lits(&self.clause_words[2..])) }
     }
+
+    /// Returns `true` if the referenced clause has been learned during conflict analysis.
+    #[inline]
+    pub fn is_learnt(&self) -> bool {
+        self.header().is_learnt()
+    }
+
+    /// Returns the literal block distance (LBD) of the referenced clause.
+    #[inline]
+    pub fn lbd(&self) -> u32 {
+        self.header().lbd()
+    }
+
+    /// Returns the activity of the referenced clause.
+    #[inline]
+    pub fn activity(&self) -> u32 {
+        self.header().activity()
+    }
 }
 
 /// A shared reference to the literals of a resolved clause.
@@ -189,7 +207,7 @@ impl<'a> ResolvedClauseMut<'a> {
     /// Returns a shared reference to the literals of the resolved clause.
     #[inline]
     #[allow(unsafe_code)]
-    pub fn literals(&self) -> Literals {
+    pub fn literals(&self) -> Literals<'_> {
         // SAFETY: At this point it is guaranteed that the clause words
         //         after the first two are the clause literals.
         unsafe { Literals::new(ClauseWord::as_lits(&self.clause_words[2..])) }
@@ -198,7 +216,7 @@ impl<'a> ResolvedClauseMut<'a> {
     /// Returns an exclusive reference to the literals of the resolved clause.
     #[inline]
     #[allow(unsafe_code)]
-    pub fn literals_mut(&mut self) -> LiteralsMut {
+    pub fn literals_mut(&mut self) -> LiteralsMut<'_> {
         // SAFETY: At this point it is guaranteed that the clause words
         //         after the first two are the clause literals.
         unsafe { LiteralsMut::new(ClauseWord::as_lits_mut(&mut self.clause_words[2..])) }
@@ -80,3 +80,94 @@ fn resolve_mut_works() {
     }
     assert_eq!(rc2_lits, &mut clause([-4, -5, -6]));
 }
+
+#[test]
+fn live_clauses_work() {
+    let mut db = ClauseDatabase::default();
+    let learnt = |lbd| ClauseHeader::build().learnt(true).lbd(lbd).finish();
+    let c1 = db.alloc(clause([1, 2, 3]));
+    let c2 = db.alloc_with_header(learnt(2), clause([-1, -2]));
+    let c3 = db.alloc(clause([4, 5, 6, 7]));
+    let c4 = db.alloc_with_header(learnt(3), clause([-4, -5, -6]));
+    let crefs = |clauses: LiveClauses| clauses.map(|(cref, _)| cref).collect::<Vec<_>>();
+    assert_eq!(crefs(db.live_clauses()), vec![c1, c2, c3, c4]);
+    assert_eq!(crefs(db.live_clauses().irredundant()), vec![c1, c3]);
+    assert_eq!(crefs(db.live_clauses().learnt()), vec![c2, c4]);
+    assert_eq!(crefs(db.live_clauses().len_range(3..4)), vec![c1, c4]);
+    assert_eq!(crefs(db.live_clauses().len_range(..=2)), vec![c2]);
+    assert_eq!(crefs(db.live_clauses().len_range(..0)), vec![]);
+    assert_eq!(crefs(db.live_clauses().learnt().len_range(3..)), vec![c4]);
+    // The yielded clauses carry the metadata of their headers.
+    let (_, clause4) = db.live_clauses().last().unwrap();
+    assert!(clause4.is_learnt());
+    assert_eq!(clause4.lbd(), 3);
+    assert_eq!(clause4.activity(), 0);
+    assert_eq!(clause4.literals().as_slice(), &clause([-4, -5, -6]));
+    // Deleted clauses are skipped before and after the garbage collection.
+    db.remove_clause(c1);
+    db.remove_clause(c2);
+    assert_eq!(crefs(db.live_clauses()), vec![c3, c4]);
+    assert_eq!((&db).into_iter().count(), 4);
+    let mut moved = Vec::new();
+    db.gc(|from, into| moved.push((from, into)));
+    let live = crefs(db.live_clauses());
+    assert_eq!(
+        live,
+        moved.iter().map(|&(_, into)| into).collect::<Vec<_>>()
+    );
+    assert_eq!(
+        db.resolve(live[0]).unwrap().literals().as_slice(),
+        &clause([4, 5, 6, 7])
+    );
+}
+
+/// An iterator that claims to yield more literals than a clause may have.
+struct OversizedClause;
+
+impl Iterator for OversizedClause {
+    type Item = Literal;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        panic!("encountered unexpected iteration of oversized clause")
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (u32::MAX as usize, Some(u32::MAX as usize))
+    }
+}
+
+impl ExactSizeIterator for OversizedClause {}
+
+#[test]
+fn try_alloc_works() {
+    let mut db = ClauseDatabase::default();
+    assert_eq!(db.try_alloc(clause([])), Err(AllocError::ShortClause));
+    assert_eq!(db.try_alloc(clause([1])), Err(AllocError::ShortClause));
+    assert_eq!(
+        db.try_alloc(OversizedClause),
+        Err(AllocError::TooManyLiterals)
+    );
+    assert!(db.is_empty());
+    assert_eq!(db.len_words(), 0);
+    let c1 = db.try_alloc(clause([1, 2])).unwrap();
+    let header = ClauseHeader::build().learnt(true).lbd(2).finish();
+    let c2 = db
+        .try_alloc_with_header(header, clause([-1, -2, 3]))
+        .unwrap();
+    assert_eq!(db.len(), 2);
+    assert_eq!(
+        db.resolve(c1).unwrap().literals().as_slice(),
+        &clause([1, 2])
+    );
+    assert_eq!(db.resolve(c2).unwrap().header(), &header);
+    assert_eq!(
+        AllocError::OutOfMemory.to_string(),
+        "out of memory to allocate more clauses"
+    );
+}
+
+#[test]
+fn clause_ref_size_depends_on_arena() {
+    let expected = if cfg!(feature = "large-arena") { 8 } else { 4 };
+    assert_eq!(mem::size_of::<ClauseRef>(), expected);
+}
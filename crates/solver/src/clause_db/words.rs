@@ -65,6 +65,13 @@ impl ClauseHeaderBuilder {
         Self { inner: self.inner }
     }
 
+    /// Sets the literal block distance (LBD) of the built clause header.
+    #[inline]
+    pub fn lbd(mut self, lbd: u32) -> Self {
+        self.inner.set_lbd(lbd);
+        Self { inner: self.inner }
+    }
+
     /// Finalizes building of the clause header.
     #[inline]
     pub fn finish(self) -> ClauseHeader {
@@ -76,11 +83,23 @@ impl Debug for ClauseHeader {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         f.debug_struct("ClauseHeader")
             .field("deleted", &self.is_deleted())
+            .field("learnt", &self.is_learnt())
+            .field("lbd", &self.lbd())
+            .field("activity", &self.activity())
             .finish()
     }
 }
 
 impl ClauseHeader {
+    /// The maximum literal block distance that can be stored in a clause header.
+    pub const MAX_LBD: u32 = (1 << Self::LBD_BITS) - 1;
+
+    /// The maximum activity that can be stored in a clause header.
+    pub const MAX_ACTIVITY: u32 = u32::MAX >> (2 + Self::LBD_BITS);
+
+    /// The number of bits of the literal block distance.
+    const LBD_BITS: u32 = 16;
+
     /// Returns a clause header builder.
     pub fn build() -> ClauseHeaderBuilder {
         ClauseHeaderBuilder {
@@ -100,16 +119,44 @@ impl ClauseHeader {
         self.inner & 0b01 != 0
     }
 
-    /// Returns `true` if the clause has been deleted from the clause database.
+    /// Returns `true` if the clause has been learned during conflict analysis.
+    #[inline]
+    pub fn is_learnt(self) -> bool {
+        self.inner & 0b10 != 0
+    }
+
+    /// Returns the literal block distance (LBD) of the clause.
     ///
     /// # Note
     ///
-    /// If a clause stored in the clause database is deleted it won't be removed
-    /// right away. Instead it is marked as deleted and removed in the next garbage
-    /// collection sweep.
+    /// The LBD is the number of distinct decision levels of the clause literals
+    /// at the time the clause was learned. It is `0` for clauses that have not
+    /// been learned.
     #[inline]
-    pub fn is_learnt(self) -> bool {
-        self.inner & 0b10 != 0
+    pub fn lbd(self) -> u32 {
+        (self.inner >> 2) & Self::MAX_LBD
+    }
+
+    /// Returns the activity of the clause.
+    ///
+    /// # Note
+    ///
+    /// The activity counts how often a learned clause took part in conflict
+    /// analysis. It saturates at [`ClauseHeader::MAX_ACTIVITY`] and is halved
+    /// whenever the learned clauses are reduced.
+    #[inline]
+    pub fn activity(self) -> u32 {
+        self.inner >> (2 + Self::LBD_BITS)
+    }
+
+    /// Increases the activity of the clause by one unless it is saturated.
+    pub(crate) fn bump_activity(&mut self) {
+        self.set_activity(self.activity().saturating_add(1));
+    }
+
+    /// Halves the activity of the clause.
+    pub(crate) fn decay_activity(&mut self) {
+        self.set_activity(self.activity() / 2);
     }
 
     /// Marks the clause as deleted.
@@ -142,6 +189,28 @@ impl ClauseHeader {
             self.inner &= !0b10;
         }
     }
+
+    /// Sets the literal block distance of the clause.
+    ///
+    /// # Note
+    ///
+    /// Values greater than [`ClauseHeader::MAX_LBD`] are saturated.
+    fn set_lbd(&mut self, lbd: u32) {
+        let lbd_mask = Self::MAX_LBD << 2;
+        self.inner = (self.inner & !lbd_mask) | (lbd.min(Self::MAX_LBD) << 2);
+    }
+
+    /// Sets the activity of the clause.
+    ///
+    /// # Note
+    ///
+    /// Values greater than [`ClauseHeader::MAX_ACTIVITY`] are saturated.
+    fn set_activity(&mut self, activity: u32) {
+        let shift = 2 + Self::LBD_BITS;
+        let activity_mask = Self::MAX_ACTIVITY << shift;
+        self.inner =
+            (self.inner & !activity_mask) | (activity.min(Self::MAX_ACTIVITY) << shift);
+    }
 }
 
 /// A 32-bit word of the clause database.
@@ -301,4 +370,48 @@ mod tests {
         assert_for(true, false);
         assert_for(true, true);
     }
+
+    #[test]
+    fn builder_lbd_works() {
+        for lbd in [0, 1, 2, 42, ClauseHeader::MAX_LBD] {
+            let header = ClauseHeader::build()
+                .learnt(true)
+                .deleted(true)
+                .lbd(lbd)
+                .finish();
+            assert_eq!(header.lbd(), lbd);
+            assert!(header.is_learnt());
+            assert!(header.is_deleted());
+        }
+        let saturated = ClauseHeader::build().lbd(u32::MAX).finish();
+        assert_eq!(saturated.lbd(), ClauseHeader::MAX_LBD);
+        assert!(!saturated.is_learnt());
+        assert!(!saturated.is_deleted());
+    }
+
+    #[test]
+    fn activity_works() {
+        let mut header = ClauseHeader::build()
+            .learnt(true)
+            .lbd(ClauseHeader::MAX_LBD)
+            .finish();
+        assert_eq!(header.activity(), 0);
+        for _ in 0..5 {
+            header.bump_activity();
+        }
+        assert_eq!(header.activity(), 5);
+        header.decay_activity();
+        assert_eq!(header.activity(), 2);
+        header.set_activity(u32::MAX);
+        assert_eq!(header.activity(), ClauseHeader::MAX_ACTIVITY);
+        header.bump_activity();
+        assert_eq!(header.activity(), ClauseHeader::MAX_ACTIVITY);
+        // The activity does not interfere with the other fields.
+        assert_eq!(header.lbd(), ClauseHeader::MAX_LBD);
+        assert!(header.is_learnt());
+        assert!(!header.is_deleted());
+        header.set_lbd(3);
+        assert_eq!(header.lbd(), 3);
+        assert_eq!(header.activity(), ClauseHeader::MAX_ACTIVITY);
+    }
 }
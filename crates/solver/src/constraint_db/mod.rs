@@ -0,0 +1,384 @@
+#[cfg(test)]
+mod tests;
+
+use crate::{
+    assignment::{
+        Assignment,
+        EnqueueLiteral,
+        PartialAssignment,
+        PropagationResult,
+        Reason,
+        Trail,
+    },
+    Literal,
+    RegisterVariables,
+    Sign,
+    Variable,
+};
+use bounded::BoundedArray;
+use core::cmp::Reverse;
+
+/// An unresolved reference to a constraint stored in the constraint database.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct ConstraintRef(u32);
+
+impl ConstraintRef {
+    /// Returns the index of the referenced constraint.
+    #[inline]
+    fn into_index(self) -> usize {
+        self.0 as usize
+    }
+}
+
+/// A literal weighted by its coefficient within a pseudo-Boolean constraint.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+struct Term {
+    /// The positive coefficient of the literal.
+    coefficient: u64,
+    /// The weighted literal.
+    literal: Literal,
+}
+
+/// A pseudo-Boolean constraint requiring `sum(a_i * l_i) >= degree`.
+///
+/// # Note
+///
+/// - All coefficients are positive and at most as large as the degree.
+/// - The terms are sorted by their coefficients in decreasing order.
+/// - Cardinality constraints are represented as pseudo-Boolean
+///   constraints where all coefficients are `1`.
+/// - At-most constraints are stored as at-least constraints over their
+///   negated literals.
+#[derive(Debug, Clone)]
+struct PseudoBoolean {
+    /// The weighted literals of the constraint.
+    terms: Box<[Term]>,
+    /// The slack of the constraint if none of its literals are `false`.
+    ///
+    /// This is the sum of all coefficients minus the degree.
+    max_slack: i64,
+    /// The slack of the constraint under the propagated assignment.
+    ///
+    /// # Note
+    ///
+    /// - This is the sum of the coefficients of all literals that are not `false`
+    ///   minus the degree of the constraint.
+    /// - The constraint is violated if the slack is negative.
+    /// - All unassigned literals with a coefficient greater than the slack
+    ///   are implied to be `true`.
+    /// - The slack is only updated for assignments that have already been
+    ///   propagated by the constraint database.
+    slack: i64,
+}
+
+impl PseudoBoolean {
+    /// Returns the greatest coefficient of the constraint.
+    fn max_coefficient(&self) -> u64 {
+        self.terms[0].coefficient
+    }
+}
+
+/// An occurrence of a literal within a constraint.
+#[derive(Debug, Copy, Clone)]
+struct Occurrence {
+    /// The constraint in which the literal occurs.
+    cref: ConstraintRef,
+    /// The coefficient of the literal in the constraint.
+    coefficient: u64,
+}
+
+/// The constraints that are affected when a variable is assigned.
+///
+/// Stores the occurrences for the positive and negative polarities of the variable.
+#[derive(Debug, Clone, Default)]
+struct VariableOccurrences {
+    /// Occurrences of literals that become `false` when the variable is assigned to `true`.
+    pos: Vec<Occurrence>,
+    /// Occurrences of literals that become `false` when the variable is assigned to `false`.
+    neg: Vec<Occurrence>,
+}
+
+impl VariableOccurrences {
+    /// Returns the occurrences affected by assigning the literal to `true`.
+    fn literal_occurrences(&self, literal: Literal) -> &[Occurrence] {
+        match literal.sign() {
+            Sign::POS => &self.pos,
+            Sign::NEG => &self.neg,
+        }
+    }
+
+    /// Returns the occurrences affected by assigning the literal to `true`.
+    fn literal_occurrences_mut(&mut self, literal: Literal) -> &mut Vec<Occurrence> {
+        match literal.sign() {
+            Sign::POS => &mut self.pos,
+            Sign::NEG => &mut self.neg,
+        }
+    }
+}
+
+/// A constraint database storing native cardinality and pseudo-Boolean constraints.
+///
+/// # Note
+///
+/// - The constraints are propagated by maintaining their slack.
+///   Every constraint is watched by all of its literals and only scanned
+///   for implied literals if its slack drops below its greatest coefficient.
+/// - The database propagates the assignments on the trail in order and keeps
+///   track of how far it has propagated so that the counters can be restored
+///   upon backtracking.
+/// - Explanations for implied literals and conflicts are generated lazily
+///   as clauses upon conflict analysis.
+#[derive(Debug, Default, Clone)]
+pub struct ConstraintDatabase {
+    /// All stored constraints.
+    constraints: Vec<PseudoBoolean>,
+    /// The constraints affected by an assignment per variable.
+    occurrences: BoundedArray<Variable, VariableOccurrences>,
+    /// The number of trail assignments that have been propagated.
+    propagate_head: usize,
+}
+
+impl RegisterVariables for ConstraintDatabase {
+    fn register_variables(&mut self, additional: usize) {
+        let total_variables = self.occurrences.len() + additional;
+        self.occurrences
+            .resize_with(total_variables, Default::default);
+    }
+}
+
+impl ConstraintDatabase {
+    /// Returns the number of stored constraints.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.constraints.len()
+    }
+
+    /// Returns `true` if the constraint database is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns a shared reference to the referenced constraint.
+    ///
+    /// # Panics
+    ///
+    /// If the constraint reference is invalid.
+    fn get(&self, cref: ConstraintRef) -> &PseudoBoolean {
+        &self.constraints[cref.into_index()]
+    }
+
+    /// Adds a constraint requiring `sum(a_i * l_i) >= degree` for the given terms.
+    ///
+    /// # Note
+    ///
+    /// - The terms must neither contain duplicate nor complementary literals.
+    /// - All coefficients must be positive and at most as large as the degree.
+    /// - The constraint must neither imply any of its literals without
+    ///   assignments nor be representable by a single clause. This means
+    ///   that the greatest coefficient must be at most the maximum slack and
+    ///   the smallest coefficient must be less than the degree.
+    /// - Constraints must only be added when no variables are assigned.
+    ///
+    /// # Panics
+    ///
+    /// - If the constraint is not in the required normalized form.
+    /// - If the sum of all coefficients does not fit into an `i64`.
+    pub fn push_pseudo_boolean<I>(&mut self, terms: I, degree: u64) -> ConstraintRef
+    where
+        I: IntoIterator<Item = (u64, Literal)>,
+    {
+        assert_eq!(
+            self.propagate_head, 0,
+            "cannot add constraints to a non-empty assignment"
+        );
+        let mut terms = terms
+            .into_iter()
+            .map(|(coefficient, literal)| {
+                Term {
+                    coefficient,
+                    literal,
+                }
+            })
+            .collect::<Box<[_]>>();
+        terms.sort_by_key(|term| Reverse(term.coefficient));
+        let sum = terms
+            .iter()
+            .try_fold(0_i64, |sum, term| {
+                i64::try_from(term.coefficient)
+                    .ok()
+                    .and_then(|coefficient| sum.checked_add(coefficient))
+            })
+            .expect("encountered overflowing pseudo-Boolean constraint coefficients");
+        let max_slack = sum - degree as i64;
+        let (max_coefficient, min_coefficient) = match (terms.first(), terms.last()) {
+            (Some(first), Some(last)) => (first.coefficient, last.coefficient),
+            _ => panic!("encountered non-normalized empty pseudo-Boolean constraint"),
+        };
+        assert!(
+            0 < min_coefficient
+                && min_coefficient < degree
+                && max_coefficient as i64 <= max_slack,
+            "encountered non-normalized pseudo-Boolean constraint"
+        );
+        let cref = ConstraintRef(self.constraints.len() as u32);
+        for &Term {
+            coefficient,
+            literal,
+        } in terms.iter()
+        {
+            self.occurrences
+                .get_mut(literal.variable())
+                .expect("encountered unexpected unregistered literal")
+                .literal_occurrences_mut(!literal)
+                .push(Occurrence { cref, coefficient });
+        }
+        self.constraints.push(PseudoBoolean {
+            terms,
+            max_slack,
+            slack: max_slack,
+        });
+        cref
+    }
+
+    /// Propagates all trail assignments that have not yet been propagated.
+    ///
+    /// Enqueues all literals implied by the constraints to the trail.
+    pub fn propagate(
+        &mut self,
+        trail: &mut Trail,
+        assignment: &mut PartialAssignment,
+    ) -> PropagationResult {
+        if self.is_empty() {
+            self.propagate_head = trail.len();
+            return PropagationResult::Consistent
+        }
+        while self.propagate_head < trail.len() {
+            let literal = trail.get(self.propagate_head);
+            self.propagate_head += 1;
+            let Self {
+                constraints,
+                occurrences,
+                ..
+            } = self;
+            let occurrences = occurrences
+                .get(literal.variable())
+                .expect("encountered unexpected invalid propagation literal")
+                .literal_occurrences(literal);
+            // Update all slacks before propagating so that they remain
+            // consistent for backtracking even if a conflict is encountered.
+            for occurrence in occurrences {
+                constraints[occurrence.cref.into_index()].slack -=
+                    occurrence.coefficient as i64;
+            }
+            for &Occurrence { cref, .. } in occurrences {
+                let constraint = &constraints[cref.into_index()];
+                let slack = constraint.slack;
+                if slack < 0 {
+                    return PropagationResult::Conflict(Reason::Constraint(cref))
+                }
+                if constraint.max_coefficient() as i64 <= slack {
+                    continue
+                }
+                for term in constraint.terms.iter() {
+                    if term.coefficient as i64 <= slack {
+                        break
+                    }
+                    if assignment.is_satisfied(term.literal).is_none() {
+                        trail
+                            .enqueue_literal(
+                                term.literal,
+                                Reason::Constraint(cref),
+                                assignment,
+                            )
+                            .expect("encountered unexpected assigned literal");
+                    }
+                }
+            }
+        }
+        PropagationResult::Consistent
+    }
+
+    /// Restores the constraint slacks before the trail is popped to the new length.
+    ///
+    /// # Note
+    ///
+    /// This must be called before the trail assignments are actually popped.
+    pub fn backtrack(&mut self, trail: &Trail, new_len: usize) {
+        while self.propagate_head > new_len {
+            self.propagate_head -= 1;
+            let literal = trail.get(self.propagate_head);
+            let Self {
+                constraints,
+                occurrences,
+                ..
+            } = self;
+            let occurrences = occurrences
+                .get(literal.variable())
+                .expect("encountered unexpected invalid trail literal")
+                .literal_occurrences(literal);
+            for occurrence in occurrences {
+                constraints[occurrence.cref.into_index()].slack +=
+                    occurrence.coefficient as i64;
+            }
+        }
+    }
+
+    /// Writes the clausal explanation of the referenced constraint into `explanation`.
+    ///
+    /// - If `implied` is `Some` the explanation is the reason clause for the
+    ///   implied literal. The implied literal is the first literal of the clause.
+    /// - Otherwise the explanation is the conflict clause of the constraint.
+    ///
+    /// # Note
+    ///
+    /// - All literals of the explanation except for the implied literal are `false`
+    ///   under the given assignment.
+    /// - The explanation greedily picks the `false` literals with the greatest
+    ///   coefficients until they suffice to imply the literal or the conflict.
+    pub fn explain(
+        &self,
+        cref: ConstraintRef,
+        implied: Option<Literal>,
+        assignment: &Assignment,
+        explanation: &mut Vec<Literal>,
+    ) {
+        explanation.clear();
+        let constraint = self.get(cref);
+        // The explanation must falsify more than `threshold` coefficients.
+        let (before, threshold) = match implied {
+            Some(implied) => {
+                explanation.push(implied);
+                let coefficient = constraint
+                    .terms
+                    .iter()
+                    .find(|term| term.literal == implied)
+                    .map(|term| term.coefficient)
+                    .expect("encountered implied literal not in constraint");
+                (
+                    assignment.position(implied.variable()),
+                    constraint.max_slack - coefficient as i64,
+                )
+            }
+            None => (usize::MAX, constraint.max_slack),
+        };
+        let partial = assignment.variable_assignment();
+        let mut falsified = 0;
+        for term in constraint.terms.iter() {
+            if falsified > threshold {
+                break
+            }
+            let literal = term.literal;
+            if partial.is_conflicting(literal) == Some(true)
+                && assignment.position(literal.variable()) < before
+            {
+                explanation.push(literal);
+                falsified += term.coefficient as i64;
+            }
+        }
+        debug_assert!(
+            falsified > threshold,
+            "encountered insufficient explanation for constraint"
+        );
+    }
+}
@@ -0,0 +1,237 @@
+use super::*;
+use crate::{
+    assignment::DecisionLevel,
+    clause_db::ClauseDatabase,
+    decider::Decider,
+};
+use bounded::Index as _;
+
+/// Convenience function to easily create a vector of literals.
+fn literals<I>(literals: I) -> Vec<Literal>
+where
+    I: IntoIterator<Item = i32>,
+{
+    literals.into_iter().map(Literal::from).collect()
+}
+
+/// Convenience function to easily create a vector of weighted literals.
+fn terms<I>(terms: I) -> Vec<(u64, Literal)>
+where
+    I: IntoIterator<Item = (u64, i32)>,
+{
+    terms
+        .into_iter()
+        .map(|(coefficient, literal)| (coefficient, Literal::from(literal)))
+        .collect()
+}
+
+impl ConstraintDatabase {
+    /// Adds a constraint requiring at least `min_true` of the literals to be `true`.
+    fn push_at_least(&mut self, literals: Vec<Literal>, min_true: u64) -> ConstraintRef {
+        self.push_pseudo_boolean(
+            literals.into_iter().map(|literal| (1, literal)),
+            min_true,
+        )
+    }
+}
+
+/// All the solver parts required to propagate constraints.
+#[derive(Default)]
+struct Setup {
+    clauses: ClauseDatabase,
+    constraints: ConstraintDatabase,
+    assignment: Assignment,
+    decider: Decider,
+}
+
+impl Setup {
+    fn new(len_variables: usize) -> Self {
+        let mut setup = Self::default();
+        setup.constraints.register_variables(len_variables);
+        setup.assignment.register_variables(len_variables);
+        setup.decider.register_variables(len_variables);
+        setup
+    }
+
+    /// Decides the literal at a new decision level and propagates it.
+    fn decide(&mut self, literal: i32) -> PropagationResult {
+        self.assignment.bump_decision_level();
+        self.assignment
+            .enqueue_assumption(Literal::from(literal))
+            .unwrap();
+        self.assignment
+            .propagate(&mut self.clauses, &mut self.constraints)
+    }
+
+    /// Backjumps to the given decision level.
+    fn backjump(&mut self, level: usize) {
+        self.assignment.pop_decision_level(
+            DecisionLevel::from_index(level),
+            &mut self.constraints,
+            &mut self.decider,
+        );
+    }
+
+    fn is_satisfied(&self, literal: i32) -> Option<bool> {
+        self.assignment
+            .variable_assignment()
+            .is_satisfied(Literal::from(literal))
+    }
+}
+
+#[test]
+fn db_works() {
+    let mut db = ConstraintDatabase::default();
+    db.register_variables(5);
+    assert!(db.is_empty());
+    let c1 = db.push_at_least(literals([1, 2, 3]), 2);
+    let c2 = db.push_at_least(literals([-1, 4, 5, -2]), 3);
+    assert_eq!(db.len(), 2);
+    assert_ne!(c1, c2);
+    let c1_literals = db.get(c1).terms.iter().map(|term| term.literal);
+    assert_eq!(c1_literals.collect::<Vec<_>>(), literals([1, 2, 3]));
+    assert_eq!(db.get(c2).max_slack, 1);
+    let c3 = db.push_pseudo_boolean(terms([(1, 1), (3, 2), (2, 3)]), 3);
+    let c3_terms = db.get(c3).terms.iter().map(|term| term.coefficient);
+    assert_eq!(c3_terms.collect::<Vec<_>>(), vec![3, 2, 1]);
+    assert_eq!(db.get(c3).max_slack, 3);
+}
+
+#[test]
+#[should_panic]
+fn push_clause_like_constraint_fails() {
+    let mut db = ConstraintDatabase::default();
+    db.register_variables(3);
+    db.push_at_least(literals([1, 2, 3]), 1);
+}
+
+#[test]
+#[should_panic]
+fn push_implying_constraint_fails() {
+    let mut db = ConstraintDatabase::default();
+    db.register_variables(3);
+    db.push_pseudo_boolean(terms([(3, 1), (1, 2), (1, 3)]), 3);
+}
+
+#[test]
+fn propagate_and_backtrack_works() {
+    let mut setup = Setup::new(4);
+    let cref = setup.constraints.push_at_least(literals([1, 2, 3, 4]), 3);
+    assert_eq!(setup.decide(-1), PropagationResult::Consistent);
+    for literal in [2, 3, 4] {
+        assert_eq!(setup.is_satisfied(literal), Some(true));
+        let variable = Literal::from(literal).variable();
+        assert_eq!(setup.assignment.reason(variable), Reason::Constraint(cref));
+    }
+    setup.backjump(0);
+    assert_eq!(setup.constraints.get(cref).slack, 1);
+    assert_eq!(setup.is_satisfied(2), None);
+    assert_eq!(setup.decide(2), PropagationResult::Consistent);
+    assert_eq!(setup.is_satisfied(1), None);
+    assert_eq!(setup.decide(-3), PropagationResult::Consistent);
+    assert_eq!(setup.is_satisfied(1), Some(true));
+    assert_eq!(setup.is_satisfied(4), Some(true));
+    setup.backjump(1);
+    assert_eq!(setup.constraints.get(cref).slack, 1);
+    assert_eq!(setup.is_satisfied(2), Some(true));
+    assert_eq!(setup.is_satisfied(1), None);
+}
+
+#[test]
+fn conflict_works() {
+    let mut setup = Setup::new(4);
+    let c1 = setup.constraints.push_at_least(literals([1, 2, 3]), 2);
+    let c2 = setup.constraints.push_at_least(literals([-2, -3, 4]), 2);
+    // Implies `2` and `3` via `c1` which falsifies two literals of `c2`.
+    assert_eq!(
+        setup.decide(-1),
+        PropagationResult::Conflict(Reason::Constraint(c2))
+    );
+    setup.backjump(0);
+    assert_eq!(setup.constraints.get(c1).slack, 1);
+    assert_eq!(setup.constraints.get(c2).slack, 1);
+    assert_eq!(setup.decide(1), PropagationResult::Consistent);
+}
+
+#[test]
+fn explain_works() {
+    let mut setup = Setup::new(5);
+    let cref = setup
+        .constraints
+        .push_at_least(literals([1, 2, 3, 4, 5]), 3);
+    assert_eq!(setup.decide(-1), PropagationResult::Consistent);
+    assert_eq!(setup.decide(-4), PropagationResult::Consistent);
+    let mut explanation = Vec::new();
+    setup.constraints.explain(
+        cref,
+        Some(Literal::from(3)),
+        &setup.assignment,
+        &mut explanation,
+    );
+    assert_eq!(explanation, literals([3, 1, 4]));
+}
+
+#[test]
+fn pseudo_boolean_propagation_works() {
+    let mut setup = Setup::new(4);
+    // 3*x1 + 2*x2 + 2*x3 + 1*x4 >= 4
+    let cref = setup
+        .constraints
+        .push_pseudo_boolean(terms([(3, 1), (2, 2), (2, 3), (1, 4)]), 4);
+    // Falsifying `x4` leaves a slack of 3 which implies nothing.
+    assert_eq!(setup.decide(-4), PropagationResult::Consistent);
+    assert_eq!(setup.is_satisfied(1), None);
+    // Falsifying `x3` leaves a slack of 1 which implies `x1` and `x2`.
+    assert_eq!(setup.decide(-3), PropagationResult::Consistent);
+    assert_eq!(setup.is_satisfied(1), Some(true));
+    assert_eq!(setup.is_satisfied(2), Some(true));
+    assert_eq!(setup.constraints.get(cref).slack, 1);
+    let mut explanation = Vec::new();
+    setup.constraints.explain(
+        cref,
+        Some(Literal::from(2)),
+        &setup.assignment,
+        &mut explanation,
+    );
+    assert_eq!(explanation, literals([2, 3, 4]));
+    setup.backjump(1);
+    assert_eq!(setup.constraints.get(cref).slack, 3);
+    assert_eq!(setup.is_satisfied(1), None);
+    // Falsifying `x1` leaves a slack of 1 which implies `x2` and `x3`.
+    assert_eq!(setup.decide(-1), PropagationResult::Consistent);
+    assert_eq!(setup.is_satisfied(2), Some(true));
+    assert_eq!(setup.is_satisfied(3), Some(true));
+    setup.constraints.explain(
+        cref,
+        Some(Literal::from(3)),
+        &setup.assignment,
+        &mut explanation,
+    );
+    assert_eq!(explanation, literals([3, 1]));
+}
+
+#[test]
+fn pseudo_boolean_conflict_works() {
+    let mut setup = Setup::new(3);
+    // 2*x1 + 2*x2 + 1*x3 >= 3
+    let c1 = setup
+        .constraints
+        .push_pseudo_boolean(terms([(2, 1), (2, 2), (1, 3)]), 3);
+    // 2*(-x1) + 2*(-x2) + 1*x3 >= 2
+    let c2 = setup
+        .constraints
+        .push_pseudo_boolean(terms([(2, -1), (2, -2), (1, 3)]), 2);
+    // Implies `x1` and `x2` via `c1` which violates `c2`.
+    assert_eq!(
+        setup.decide(-3),
+        PropagationResult::Conflict(Reason::Constraint(c2))
+    );
+    let mut explanation = Vec::new();
+    setup
+        .constraints
+        .explain(c2, None, &setup.assignment, &mut explanation);
+    assert_eq!(explanation, literals([-1, -2]));
+    setup.backjump(0);
+    assert_eq!(setup.constraints.get(c1).slack, 2);
+    assert_eq!(setup.constraints.get(c2).slack, 3);
+}
@@ -0,0 +1,513 @@
+//! Exact model counting (#SAT) with optional projection.
+
+mod number;
+#[cfg(test)]
+mod tests;
+
+pub use self::number::ModelCount;
+use crate::{
+    assignment::{
+        Assignment,
+        AssignmentError,
+        PropagationResult,
+    },
+    builder::{
+        CnfBuilder,
+        ConsumeCnf,
+    },
+    clause_db::ClauseDatabase,
+    constraint_db::ConstraintDatabase,
+    decider::RestoreVariable,
+    literal::RegisterVariables,
+    sanitizer::{
+        ClauseSanitizer,
+        SanitizedLiterals,
+    },
+    Error,
+    Literal,
+    LiteralChunk,
+    Sign,
+    Variable,
+};
+use ahash::AHashMap;
+use bounded::Index as _;
+use cnf_parser::{
+    Error as CnfError,
+    Input,
+};
+
+/// An exact model counter for CNF formulas.
+///
+/// # Note
+///
+/// The counter performs a DPLL-style search that splits the residual
+/// formula into connected components which are counted independently
+/// and cached by their canonical clause sets.
+#[derive(Debug, Default, Clone)]
+pub struct ModelCounter {
+    /// The number of registered variables.
+    len_variables: usize,
+    /// The sanitized clauses with at least two literals.
+    clauses: Vec<Vec<Literal>>,
+    /// The unit clauses.
+    units: Vec<Literal>,
+    /// Yields `true` if `consume_clause` encountered the empty clause.
+    encountered_empty_clause: bool,
+    /// Sanitizes clauses before being fed to the model counter.
+    sanitizer: ClauseSanitizer,
+}
+
+impl ModelCounter {
+    /// Consumes the `.cnf` input and feeds it to the returned model counter.
+    ///
+    /// # Note
+    ///
+    /// Use [`parse_show`] to read the projection of the input.
+    ///
+    /// # Errors
+    ///
+    /// - If the input is no valid `.cnf` format.
+    /// - If the input encodes an invalid CNF formula.
+    pub fn from_cnf<I>(input: &mut I) -> Result<Self, CnfError<Error>>
+    where
+        I: Input,
+    {
+        let mut builder = CnfBuilder::default();
+        cnf_parser::parse_cnf(input, &mut builder)?;
+        Ok(builder.finalize())
+    }
+
+    /// Registers a new literal for the model counter and returns it.
+    ///
+    /// # Note
+    ///
+    /// The returned literal has positive polarity.
+    pub fn new_literal(&mut self) -> Literal {
+        let variable = Variable::from_index(self.len_variables);
+        self.len_variables += 1;
+        self.sanitizer.register_variables(1);
+        Literal::new(variable, Sign::POS)
+    }
+
+    /// Allocates the given amount of new literals for the model counter and returns them.
+    ///
+    /// # Panics
+    ///
+    /// If more variables have been registered than supported.
+    pub fn new_literal_chunk(&mut self, amount: usize) -> LiteralChunk {
+        let first_index = self.len_variables;
+        let chunk = LiteralChunk::new(first_index, amount).unwrap_or_else(|_| {
+            panic!(
+                "created invalid literal chunk for range ({}..{})",
+                first_index,
+                first_index + amount
+            )
+        });
+        self.len_variables += amount;
+        self.sanitizer.register_variables(amount);
+        chunk
+    }
+
+    /// Consumes the given clause.
+    ///
+    /// # Note
+    ///
+    /// Duplicate literals are removed and tautological clauses are dropped.
+    ///
+    /// # Panics
+    ///
+    /// If any of the literals has not been registered.
+    pub fn consume_clause<I>(&mut self, literals: I)
+    where
+        I: IntoIterator<Item = Literal>,
+    {
+        let literals = literals.into_iter().collect::<Vec<_>>();
+        match self.sanitizer.sanitize(literals) {
+            SanitizedLiterals::Literals(literals) => {
+                self.clauses.push(literals.collect())
+            }
+            SanitizedLiterals::UnitClause(unit) => self.units.push(unit),
+            SanitizedLiterals::TautologicalClause => (),
+            SanitizedLiterals::EmptyClause => self.encountered_empty_clause = true,
+            SanitizedLiterals::InvalidLiteral(invalid_literal) => {
+                panic!("encountered invalid literal: {}", invalid_literal)
+            }
+        }
+    }
+
+    /// Returns the number of models projected onto the given variables.
+    ///
+    /// Models that only differ in variables outside of the projection are
+    /// counted once. All variables are projection variables if no `projection`
+    /// is given.
+    ///
+    /// # Panics
+    ///
+    /// If any of the projection variables has not been registered.
+    pub fn count(&self, projection: Option<&[Variable]>) -> ModelCount {
+        if self.encountered_empty_clause {
+            return ModelCount::zero()
+        }
+        let mut is_projected = vec![projection.is_none(); self.len_variables];
+        for variable in projection.unwrap_or(&[]) {
+            match is_projected.get_mut(variable.into_index()) {
+                Some(is_projected) => *is_projected = true,
+                None => panic!("encountered unregistered projection variable"),
+            }
+        }
+        Search::new(self, is_projected).count()
+    }
+}
+
+/// Returns the variables of the `c p show` lines of the `.cnf` input if any.
+///
+/// # Note
+///
+/// The variables of every `c p show` line are terminated by `0` and the
+/// variables of multiple lines are combined.
+pub fn parse_show(input: &[u8]) -> Option<Vec<Variable>> {
+    let mut projection = None;
+    for line in input.split(|&byte| byte == b'\n') {
+        let mut tokens = line
+            .split(|byte| byte.is_ascii_whitespace())
+            .filter(|token| !token.is_empty());
+        if tokens.next() != Some(b"c")
+            || tokens.next() != Some(b"p")
+            || tokens.next() != Some(b"show")
+        {
+            continue
+        }
+        let variables = projection.get_or_insert_with(Vec::new);
+        let indices = tokens
+            .map_while(|token| core::str::from_utf8(token).ok()?.parse::<u32>().ok())
+            .take_while(|&index| index != 0);
+        variables.extend(indices.map(|index| Variable::from(index - 1)));
+    }
+    projection
+}
+
+impl ConsumeCnf for ModelCounter {
+    fn new_variables(&mut self, amount: usize) {
+        self.new_literal_chunk(amount);
+    }
+
+    fn consume_cnf_clause(&mut self, literals: &[Literal]) {
+        self.consume_clause(literals.iter().copied());
+    }
+}
+
+/// The model counter has no decision heuristic to be informed about backtracking.
+struct NoHeuristic;
+
+impl RestoreVariable for NoHeuristic {
+    fn restore_variable(&mut self, _variable: Variable) {}
+}
+
+/// A component of the residual formula that shares no variables with other components.
+#[derive(Debug, Default)]
+struct Component {
+    /// The indices of the unsatisfied clauses of the component.
+    clauses: Vec<usize>,
+    /// The unassigned variables of the component.
+    variables: Vec<Variable>,
+}
+
+/// Marks an unused entry of the union-find scratch arrays.
+const UNUSED: usize = usize::MAX;
+
+/// The state of a single model counting search.
+struct Search<'a> {
+    /// The clauses of the model counter.
+    clauses: &'a [Vec<Literal>],
+    /// The unit clauses of the model counter.
+    units: &'a [Literal],
+    /// The clauses used for unit propagation via their watched literals.
+    clause_db: ClauseDatabase,
+    /// The empty constraint database required by the propagation.
+    constraints: ConstraintDatabase,
+    /// The partial assignment and its trail.
+    assignment: Assignment,
+    /// Yields `true` for every projection variable.
+    is_projected: Vec<bool>,
+    /// The parent of every variable in the union-find forest of the components.
+    parents: Vec<usize>,
+    /// The component index of every union-find root.
+    slots: Vec<usize>,
+    /// The number of occurrences of every variable in a residual component.
+    occurrences: Vec<usize>,
+    /// The counts of the already counted components keyed by their residual clauses.
+    cache: AHashMap<Vec<Vec<Literal>>, ModelCount>,
+}
+
+impl<'a> Search<'a> {
+    /// Creates the search for the model counter and its projection variables.
+    fn new(counter: &'a ModelCounter, is_projected: Vec<bool>) -> Self {
+        let len_variables = counter.len_variables;
+        let mut search = Self {
+            clauses: &counter.clauses,
+            units: &counter.units,
+            clause_db: ClauseDatabase::default(),
+            constraints: ConstraintDatabase::default(),
+            assignment: Assignment::default(),
+            is_projected,
+            parents: vec![UNUSED; len_variables],
+            slots: vec![UNUSED; len_variables],
+            occurrences: vec![0; len_variables],
+            cache: AHashMap::default(),
+        };
+        search.constraints.register_variables(len_variables);
+        search.assignment.register_variables(len_variables);
+        for clause in search.clauses {
+            let cref = search.clause_db.alloc(clause.iter().copied());
+            let resolved = search.clause_db.resolve(cref).unwrap_or_else(|| {
+                panic!("failed to resolve recently allocated clause: {:?}", cref)
+            });
+            search.assignment.initialize_watchers(cref, resolved);
+        }
+        search
+    }
+
+    /// Counts the models of the whole formula.
+    fn count(mut self) -> ModelCount {
+        for &unit in self.units {
+            match self.assignment.enqueue_assumption(unit) {
+                Ok(()) | Err(AssignmentError::AlreadyAssigned) => (),
+                Err(_) => return ModelCount::zero(),
+            }
+        }
+        let result = self
+            .assignment
+            .propagate(&mut self.clause_db, &mut self.constraints);
+        if result.is_conflict() {
+            return ModelCount::zero()
+        }
+        let variables = (0..self.parents.len())
+            .map(Variable::from_index)
+            .collect::<Vec<_>>();
+        self.count_residual(0..self.clauses.len(), &variables)
+    }
+
+    /// Returns `true` if the variable is a projection variable.
+    fn is_projected(&self, variable: Variable) -> bool {
+        self.is_projected[variable.into_index()]
+    }
+
+    /// Returns `true` if the variable is unassigned.
+    fn is_unassigned(&self, variable: Variable) -> bool {
+        self.assignment
+            .variable_assignment()
+            .get(variable)
+            .is_none()
+    }
+
+    /// Returns `true` if the clause is satisfied under the current assignment.
+    fn is_satisfied(&self, clause: usize) -> bool {
+        self.clauses[clause].iter().any(|&literal| {
+            self.assignment.variable_assignment().is_satisfied(literal) == Some(true)
+        })
+    }
+
+    /// Returns the unassigned literals of the clause.
+    fn unassigned_literals(&self, clause: usize) -> impl Iterator<Item = Literal> + '_ {
+        self.clauses[clause]
+            .iter()
+            .copied()
+            .filter(move |literal| self.is_unassigned(literal.variable()))
+    }
+
+    /// Returns the root of the variable index in the union-find forest.
+    fn find(&mut self, mut index: usize) -> usize {
+        while self.parents[index] != index {
+            let grand_parent = self.parents[self.parents[index]];
+            self.parents[index] = grand_parent;
+            index = grand_parent;
+        }
+        index
+    }
+
+    /// Splits the unsatisfied clauses into components of the residual formula.
+    fn components<I>(&mut self, clauses: I) -> Vec<Component>
+    where
+        I: IntoIterator<Item = usize>,
+    {
+        let residual = clauses
+            .into_iter()
+            .filter(|&clause| !self.is_satisfied(clause))
+            .collect::<Vec<_>>();
+        let mut touched = Vec::new();
+        for &clause in &residual {
+            let mut root = None;
+            for index in 0..self.clauses[clause].len() {
+                let variable = self.clauses[clause][index].variable();
+                if !self.is_unassigned(variable) {
+                    continue
+                }
+                let index = variable.into_index();
+                if self.parents[index] == UNUSED {
+                    self.parents[index] = index;
+                    touched.push(index);
+                }
+                let other = self.find(index);
+                match root {
+                    None => root = Some(other),
+                    Some(root) if root != other => self.parents[other] = root,
+                    Some(_) => (),
+                }
+            }
+        }
+        let mut components = Vec::<Component>::new();
+        for &clause in &residual {
+            let variable = self
+                .unassigned_literals(clause)
+                .next()
+                .expect("encountered unexpected falsified clause")
+                .variable();
+            let root = self.find(variable.into_index());
+            if self.slots[root] == UNUSED {
+                self.slots[root] = components.len();
+                components.push(Component::default());
+            }
+            components[self.slots[root]].clauses.push(clause);
+        }
+        for &index in &touched {
+            let root = self.find(index);
+            let slot = self.slots[root];
+            components[slot].variables.push(Variable::from_index(index));
+        }
+        for &index in &touched {
+            self.parents[index] = UNUSED;
+            self.slots[index] = UNUSED;
+        }
+        components
+    }
+
+    /// Counts the models of the residual formula of the clauses over the variables.
+    ///
+    /// # Note
+    ///
+    /// Every unassigned projection variable that no longer occurs in any
+    /// unsatisfied clause doubles the number of models.
+    fn count_residual<I>(&mut self, clauses: I, variables: &[Variable]) -> ModelCount
+    where
+        I: IntoIterator<Item = usize>,
+    {
+        let components = self.components(clauses);
+        let len_unassigned = variables
+            .iter()
+            .filter(|&&variable| {
+                self.is_projected(variable) && self.is_unassigned(variable)
+            })
+            .count();
+        let len_constrained = components
+            .iter()
+            .flat_map(|component| &component.variables)
+            .filter(|&&variable| self.is_projected(variable))
+            .count();
+        let mut count = ModelCount::one() << (len_unassigned - len_constrained);
+        for component in components {
+            let component_count = self.count_component(component);
+            count = &count * &component_count;
+            if count.is_zero() {
+                break
+            }
+        }
+        count
+    }
+
+    /// Returns the canonical residual clauses of the component.
+    fn cache_key(&self, component: &Component) -> Vec<Vec<Literal>> {
+        let mut key = component
+            .clauses
+            .iter()
+            .map(|&clause| self.unassigned_literals(clause).collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+        key.sort_unstable();
+        key
+    }
+
+    /// Returns the variable of the component to branch on.
+    ///
+    /// Prefers projection variables and then variables with the most
+    /// occurrences in the residual clauses of the component.
+    fn branching_variable(&mut self, component: &Component) -> Variable {
+        for &clause in &component.clauses {
+            for index in 0..self.clauses[clause].len() {
+                let variable = self.clauses[clause][index].variable();
+                self.occurrences[variable.into_index()] += 1;
+            }
+        }
+        let variable = component
+            .variables
+            .iter()
+            .copied()
+            .max_by_key(|&variable| {
+                (
+                    self.is_projected(variable),
+                    self.occurrences[variable.into_index()],
+                )
+            })
+            .expect("encountered unexpected component without variables");
+        for &clause in &component.clauses {
+            for literal in &self.clauses[clause] {
+                self.occurrences[literal.variable().into_index()] = 0;
+            }
+        }
+        variable
+    }
+
+    /// Counts the models of the component.
+    ///
+    /// # Note
+    ///
+    /// Components without projection variables have a count of either `0` or
+    /// `1` so that it suffices to find a single model for them.
+    fn count_component(&mut self, component: Component) -> ModelCount {
+        let key = self.cache_key(&component);
+        if let Some(count) = self.cache.get(&key) {
+            return count.clone()
+        }
+        let variable = self.branching_variable(&component);
+        let literal = Literal::new(variable, Sign::POS);
+        let count = if self.is_projected(variable) {
+            let pos = self.count_branch(&component, literal);
+            let neg = self.count_branch(&component, !literal);
+            &pos + &neg
+        } else {
+            let pos = self.count_branch(&component, literal);
+            if pos.is_zero() {
+                self.count_branch(&component, !literal)
+            } else {
+                pos
+            }
+        };
+        self.cache.insert(key, count.clone());
+        count
+    }
+
+    /// Counts the models of the component with the literal being `true`.
+    fn count_branch(&mut self, component: &Component, literal: Literal) -> ModelCount {
+        let level = self.assignment.current_decision_level();
+        self.assignment.bump_decision_level();
+        self.assignment
+            .enqueue_assumption(literal)
+            .expect("encountered unexpected assigned branching literal");
+        let count = match self
+            .assignment
+            .propagate(&mut self.clause_db, &mut self.constraints)
+        {
+            PropagationResult::Conflict(_) => ModelCount::zero(),
+            PropagationResult::Consistent => {
+                self.count_residual(
+                    component.clauses.iter().copied(),
+                    &component.variables,
+                )
+            }
+        };
+        self.assignment.pop_decision_level(
+            level,
+            &mut self.constraints,
+            &mut NoHeuristic,
+        );
+        count
+    }
+}
@@ -0,0 +1,197 @@
+use core::{
+    cmp::Ordering,
+    fmt,
+    fmt::Display,
+    ops::{
+        Add,
+        Mul,
+        Shl,
+    },
+};
+
+/// An arbitrary-precision number of models.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Hash)]
+pub struct ModelCount {
+    /// The 32-bit digits of the number in little-endian order.
+    ///
+    /// # Note
+    ///
+    /// The most significant digit is never zero so that every number
+    /// has a unique representation and zero has no digits.
+    digits: Vec<u32>,
+}
+
+impl ModelCount {
+    /// Returns the number zero.
+    pub fn zero() -> Self {
+        Self::default()
+    }
+
+    /// Returns the number one.
+    pub fn one() -> Self {
+        Self::from(1_u32)
+    }
+
+    /// Returns `true` if the number is zero.
+    pub fn is_zero(&self) -> bool {
+        self.digits.is_empty()
+    }
+
+    /// Removes the most significant zero digits.
+    fn normalize(&mut self) {
+        while self.digits.last() == Some(&0) {
+            self.digits.pop();
+        }
+    }
+
+    /// Divides the number in place by the divisor and returns the remainder.
+    fn div_rem_in_place(&mut self, divisor: u32) -> u32 {
+        let mut remainder = 0_u64;
+        for digit in self.digits.iter_mut().rev() {
+            let value = (remainder << 32) | u64::from(*digit);
+            *digit = (value / u64::from(divisor)) as u32;
+            remainder = value % u64::from(divisor);
+        }
+        self.normalize();
+        remainder as u32
+    }
+}
+
+impl From<u32> for ModelCount {
+    fn from(value: u32) -> Self {
+        Self::from(u64::from(value))
+    }
+}
+
+impl From<u64> for ModelCount {
+    fn from(value: u64) -> Self {
+        let mut count = Self {
+            digits: vec![value as u32, (value >> 32) as u32],
+        };
+        count.normalize();
+        count
+    }
+}
+
+impl TryFrom<&ModelCount> for u64 {
+    type Error = core::num::TryFromIntError;
+
+    fn try_from(count: &ModelCount) -> Result<Self, Self::Error> {
+        match count.digits[..] {
+            [] => Ok(0),
+            [low] => Ok(u64::from(low)),
+            [low, high] => Ok(u64::from(high) << 32 | u64::from(low)),
+            // Provokes the error type of the standard library.
+            _ => u64::try_from(u128::MAX),
+        }
+    }
+}
+
+impl<'a> Add<&'a ModelCount> for &'a ModelCount {
+    type Output = ModelCount;
+
+    fn add(self, rhs: &'a ModelCount) -> Self::Output {
+        let (long, short) = if self.digits.len() >= rhs.digits.len() {
+            (self, rhs)
+        } else {
+            (rhs, self)
+        };
+        let mut digits = Vec::with_capacity(long.digits.len() + 1);
+        let mut carry = 0_u64;
+        for (index, &digit) in long.digits.iter().enumerate() {
+            let other = short.digits.get(index).copied().unwrap_or(0);
+            let sum = u64::from(digit) + u64::from(other) + carry;
+            digits.push(sum as u32);
+            carry = sum >> 32;
+        }
+        if carry != 0 {
+            digits.push(carry as u32);
+        }
+        ModelCount { digits }
+    }
+}
+
+impl<'a> Mul<&'a ModelCount> for &'a ModelCount {
+    type Output = ModelCount;
+
+    fn mul(self, rhs: &'a ModelCount) -> Self::Output {
+        if self.is_zero() || rhs.is_zero() {
+            return ModelCount::zero()
+        }
+        let mut digits = vec![0_u32; self.digits.len() + rhs.digits.len()];
+        for (i, &lhs_digit) in self.digits.iter().enumerate() {
+            let mut carry = 0_u64;
+            for (j, &rhs_digit) in rhs.digits.iter().enumerate() {
+                let product = u64::from(lhs_digit) * u64::from(rhs_digit)
+                    + u64::from(digits[i + j])
+                    + carry;
+                digits[i + j] = product as u32;
+                carry = product >> 32;
+            }
+            digits[i + rhs.digits.len()] = carry as u32;
+        }
+        let mut count = ModelCount { digits };
+        count.normalize();
+        count
+    }
+}
+
+impl Shl<usize> for ModelCount {
+    type Output = Self;
+
+    /// Multiplies the number by `2^shift`.
+    fn shl(self, shift: usize) -> Self::Output {
+        if self.is_zero() {
+            return self
+        }
+        let (words, bits) = (shift / 32, shift % 32);
+        let mut digits = vec![0_u32; words];
+        digits.reserve(self.digits.len() + 1);
+        let mut carry = 0_u32;
+        for digit in self.digits {
+            digits.push((digit << bits) | carry);
+            carry = if bits == 0 { 0 } else { digit >> (32 - bits) };
+        }
+        digits.push(carry);
+        let mut count = Self { digits };
+        count.normalize();
+        count
+    }
+}
+
+impl PartialOrd for ModelCount {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for ModelCount {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.digits
+            .len()
+            .cmp(&other.digits.len())
+            .then_with(|| self.digits.iter().rev().cmp(other.digits.iter().rev()))
+    }
+}
+
+impl Display for ModelCount {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        /// The largest power of ten that fits into a digit.
+        const CHUNK: u32 = 1_000_000_000;
+        let mut rest = self.clone();
+        let mut chunks = Vec::new();
+        while !rest.is_zero() {
+            chunks.push(rest.div_rem_in_place(CHUNK));
+        }
+        match chunks.split_last() {
+            None => write!(f, "0"),
+            Some((most_significant, rest)) => {
+                write!(f, "{}", most_significant)?;
+                for chunk in rest.iter().rev() {
+                    write!(f, "{:09}", chunk)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
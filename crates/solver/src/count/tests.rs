@@ -0,0 +1,150 @@
+use super::*;
+use crate::tests::Lcg;
+
+#[test]
+fn model_count_arithmetic_works() {
+    let mut rng = Lcg(42);
+    for _ in 0..1000 {
+        let a = rng.next(1 << 30) as u64 * rng.next(1 << 30) as u64;
+        let b = rng.next(1 << 30) as u64 * rng.next(4) as u64;
+        let shift = rng.next(40);
+        let (count_a, count_b) = (ModelCount::from(a), ModelCount::from(b));
+        let sum = &count_a + &count_b;
+        assert_eq!(sum.to_string(), (u128::from(a) + u128::from(b)).to_string());
+        let product = &count_a * &count_b;
+        assert_eq!(
+            product.to_string(),
+            (u128::from(a) * u128::from(b)).to_string()
+        );
+        let shifted = count_a.clone() << shift;
+        assert_eq!(shifted.to_string(), (u128::from(a) << shift).to_string());
+        assert_eq!(count_a.cmp(&count_b), a.cmp(&b));
+        assert_eq!(u64::try_from(&count_a), Ok(a));
+    }
+    assert_eq!(ModelCount::zero().to_string(), "0");
+    assert!((ModelCount::zero() << 100).is_zero());
+    assert!(u64::try_from(&(ModelCount::one() << 64)).is_err());
+    assert_eq!(
+        (ModelCount::one() << 100).to_string(),
+        "1267650600228229401496703205376"
+    );
+}
+
+/// Returns `true` if the clause is satisfied by the assignment.
+///
+/// The `n`-th bit of the assignment is the value of the `n`-th variable.
+fn is_satisfied(clause: &[Literal], assignment: u32) -> bool {
+    clause.iter().any(|literal| {
+        let value = assignment & (1 << literal.variable().into_index()) != 0;
+        value == (literal.sign() == Sign::POS)
+    })
+}
+
+/// Returns the number of models of the clauses projected onto the variables of the mask.
+fn brute_force_count(clauses: &[Vec<Literal>], len_variables: usize, mask: u32) -> u64 {
+    let mut projections = (0..1_u32 << len_variables)
+        .filter(|&assignment| {
+            clauses
+                .iter()
+                .all(|clause| is_satisfied(clause, assignment))
+        })
+        .map(|assignment| assignment & mask)
+        .collect::<Vec<_>>();
+    projections.sort_unstable();
+    projections.dedup();
+    projections.len() as u64
+}
+
+#[test]
+fn random_counts_agree_with_brute_force() {
+    let mut rng = Lcg(42);
+    for _ in 0..300 {
+        let len_variables = 1 + rng.next(12);
+        let mut counter = ModelCounter::default();
+        let vars = counter
+            .new_literal_chunk(len_variables)
+            .into_iter()
+            .collect::<Vec<_>>();
+        let clauses = (0..rng.next(3 * len_variables))
+            .map(|_| {
+                (0..1 + rng.next(3))
+                    .map(|_| {
+                        let literal = vars[rng.next(len_variables)];
+                        if rng.next(2) == 0 {
+                            !literal
+                        } else {
+                            literal
+                        }
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+        for clause in &clauses {
+            counter.consume_clause(clause.iter().copied());
+        }
+        let expected = brute_force_count(&clauses, len_variables, u32::MAX);
+        assert_eq!(counter.count(None), ModelCount::from(expected));
+        let projection = vars
+            .iter()
+            .filter(|_| rng.next(2) == 0)
+            .map(|literal| literal.variable())
+            .collect::<Vec<_>>();
+        let mask = projection
+            .iter()
+            .fold(0, |mask, variable| mask | 1 << variable.into_index());
+        let expected = brute_force_count(&clauses, len_variables, mask);
+        assert_eq!(counter.count(Some(&projection)), ModelCount::from(expected));
+    }
+}
+
+#[test]
+fn trivial_counts_work() {
+    let mut counter = ModelCounter::default();
+    assert_eq!(counter.count(None), ModelCount::one());
+    let vars = counter
+        .new_literal_chunk(100)
+        .into_iter()
+        .collect::<Vec<_>>();
+    assert_eq!(counter.count(None), ModelCount::one() << 100);
+    assert_eq!(counter.count(Some(&[])), ModelCount::one());
+    // Tautological clauses do not constrain the models.
+    counter.consume_clause([vars[0], !vars[0], vars[1]]);
+    assert_eq!(counter.count(None), ModelCount::one() << 100);
+    counter.consume_clause([vars[0], vars[0]]);
+    assert_eq!(counter.count(None), ModelCount::one() << 99);
+    counter.consume_clause([!vars[0]]);
+    assert!(counter.count(None).is_zero());
+    assert!(counter.count(Some(&[])).is_zero());
+}
+
+#[test]
+fn independent_components_work() {
+    // 50 independent copies of `(a | b) & (c | d)` with 9 models each.
+    let mut counter = ModelCounter::default();
+    for _ in 0..50 {
+        let vars = counter.new_literal_chunk(4).into_iter().collect::<Vec<_>>();
+        counter.consume_clause([vars[0], vars[1]]);
+        counter.consume_clause([vars[2], vars[3]]);
+    }
+    let expected = (0..100).fold(ModelCount::one(), |count, _| {
+        &count * &ModelCount::from(3_u32)
+    });
+    assert_eq!(counter.count(None), expected);
+}
+
+#[test]
+fn from_cnf_and_parse_show_work() {
+    let input = b"\
+        c p show 1 3 0\n\
+        c p show 4 0\n\
+        p cnf 4 2\n\
+        1 2 0\n\
+        -1 3 4 0\n\
+    ";
+    let counter = ModelCounter::from_cnf(&mut &input[..]).unwrap();
+    assert_eq!(counter.count(None), ModelCount::from(10_u32));
+    let projection = parse_show(input).unwrap();
+    assert_eq!(projection, [0, 2, 3].map(Variable::from_index).to_vec());
+    assert_eq!(counter.count(Some(&projection)), ModelCount::from(7_u32));
+    assert_eq!(parse_show(b"p cnf 1 0\nc p other 1 0\n"), None);
+}
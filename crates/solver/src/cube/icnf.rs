@@ -0,0 +1,172 @@
+use crate::{
+    Literal,
+    Sign,
+    Variable,
+};
+use bounded::Index as _;
+use core::{
+    fmt,
+    fmt::Display,
+};
+
+/// Errors that can be encountered when parsing `.icnf` input.
+///
+/// # Note
+///
+/// All lines are counted starting at `1`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum IcnfError {
+    /// Encountered a token that is not a valid literal.
+    UnexpectedToken { line: usize },
+    /// An integer does not fit into its type.
+    OutOfRangeInteger { line: usize },
+    /// A variable index exceeds the supported number of variables.
+    InvalidVariable { line: usize },
+    /// A clause or assumption line is not terminated by `0` at the end of its line.
+    UnterminatedLine { line: usize },
+    /// The `p inccnf` header is malformed or does not precede all other lines.
+    InvalidHeader { line: usize },
+}
+
+impl Display for IcnfError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnexpectedToken { line } => {
+                write!(f, "encountered unexpected token in line {}", line)
+            }
+            Self::OutOfRangeInteger { line } => {
+                write!(f, "encountered out of range integer in line {}", line)
+            }
+            Self::InvalidVariable { line } => {
+                write!(f, "encountered invalid variable in line {}", line)
+            }
+            Self::UnterminatedLine { line } => {
+                write!(f, "encountered unterminated line {}", line)
+            }
+            Self::InvalidHeader { line } => {
+                write!(f, "encountered invalid header in line {}", line)
+            }
+        }
+    }
+}
+
+/// A line of an incremental `.icnf` instance.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum IcnfLine {
+    /// A clause that is added to the clauses so far.
+    Clause(Vec<Literal>),
+    /// Assumptions under which the clauses so far are solved.
+    Assumptions(Vec<Literal>),
+}
+
+/// An incremental instance that interleaves clauses with queries.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Icnf {
+    /// The number of variables used by the instance.
+    len_variables: usize,
+    /// The clause and assumption lines in the order of the input.
+    lines: Vec<IcnfLine>,
+}
+
+impl Icnf {
+    /// Returns the number of variables used by the instance.
+    pub fn len_variables(&self) -> usize {
+        self.len_variables
+    }
+
+    /// Returns the clause and assumption lines in the order of the input.
+    pub fn lines(&self) -> &[IcnfLine] {
+        &self.lines
+    }
+
+    /// Parses `.icnf` input of the incremental DIMACS format.
+    ///
+    /// # Note
+    ///
+    /// - Comments start with `c` and span until the end of the line.
+    /// - The optional `p inccnf` header precedes all other lines.
+    /// - Clauses are DIMACS literals and assumption lines start with `a`.
+    /// - Every clause and assumption line is terminated by `0` at the end of its line.
+    ///
+    /// # Errors
+    ///
+    /// If the input is no valid `.icnf` format.
+    pub fn parse(input: &[u8]) -> Result<Self, IcnfError> {
+        let mut icnf = Self::default();
+        let mut is_header_allowed = true;
+        for (index, line) in input.split(|&byte| byte == b'\n').enumerate() {
+            let line_number = index + 1;
+            let mut tokens = line
+                .split(|byte| byte.is_ascii_whitespace())
+                .filter(|token| !token.is_empty())
+                .peekable();
+            match tokens.peek() {
+                None => continue,
+                Some(token) if token.starts_with(b"c") => continue,
+                Some(&b"p") => {
+                    let is_valid = is_header_allowed
+                        && tokens.by_ref().eq([&b"p"[..], &b"inccnf"[..]]);
+                    if !is_valid {
+                        return Err(IcnfError::InvalidHeader { line: line_number })
+                    }
+                }
+                Some(&b"a") => {
+                    tokens.next();
+                    let assumptions = icnf.parse_literals(tokens, line_number)?;
+                    icnf.lines.push(IcnfLine::Assumptions(assumptions));
+                }
+                Some(_) => {
+                    let clause = icnf.parse_literals(tokens, line_number)?;
+                    icnf.lines.push(IcnfLine::Clause(clause));
+                }
+            }
+            is_header_allowed = false;
+        }
+        Ok(icnf)
+    }
+
+    /// Parses the literals of a line until the terminating `0`.
+    fn parse_literals<'a, I>(
+        &mut self,
+        mut tokens: I,
+        line: usize,
+    ) -> Result<Vec<Literal>, IcnfError>
+    where
+        I: Iterator<Item = &'a [u8]>,
+    {
+        let mut literals = Vec::new();
+        loop {
+            let token = tokens.next().ok_or(IcnfError::UnterminatedLine { line })?;
+            let value = parse_integer(token, line)?;
+            if value == 0 {
+                break
+            }
+            let index = usize::try_from(value.unsigned_abs() - 1)
+                .ok()
+                .filter(|&index| index <= Variable::MAX_INDEX)
+                .ok_or(IcnfError::InvalidVariable { line })?;
+            let sign = if value > 0 { Sign::POS } else { Sign::NEG };
+            literals.push(Literal::new(Variable::from_index(index), sign));
+            self.len_variables = self.len_variables.max(index + 1);
+        }
+        if tokens.next().is_some() {
+            return Err(IcnfError::UnexpectedToken { line })
+        }
+        Ok(literals)
+    }
+}
+
+/// Parses the token as a decimal integer.
+fn parse_integer(token: &[u8], line: usize) -> Result<i64, IcnfError> {
+    let token =
+        core::str::from_utf8(token).map_err(|_| IcnfError::UnexpectedToken { line })?;
+    token.parse::<i64>().map_err(|error| {
+        match error.kind() {
+            core::num::IntErrorKind::PosOverflow
+            | core::num::IntErrorKind::NegOverflow => {
+                IcnfError::OutOfRangeInteger { line }
+            }
+            _ => IcnfError::UnexpectedToken { line },
+        }
+    })
+}
@@ -0,0 +1,516 @@
+//! Cube-and-conquer solving with a lookahead cuber.
+//!
+//! The cuber splits an instance into cubes, i.e. conjunctions of literals,
+//! that together cover all of its models. Every split chooses the variable
+//! whose lookahead on both polarities reduces the most clauses which is
+//! measured by the difference heuristic of the march solvers. The cubes are
+//! then solved as assumptions by the same or by multiple solvers.
+//!
+//! Cubes are exchanged in the incremental `.icnf` format whose assumption
+//! lines each denote a query against the clauses before them.
+
+mod icnf;
+#[cfg(test)]
+mod tests;
+
+pub use self::icnf::{
+    Icnf,
+    IcnfError,
+    IcnfLine,
+};
+use crate::{
+    assignment::PropagationResult,
+    builder::{
+        CnfBuilder,
+        ConsumeCnf,
+    },
+    Error,
+    Literal,
+    LiteralChunk,
+    Model,
+    Sign,
+    SolveResult,
+    Solver,
+    Variable,
+};
+use bounded::Index as _;
+use cnf_parser::{
+    Error as CnfError,
+    Input,
+};
+use std::{
+    collections::VecDeque,
+    io,
+    io::Write,
+    sync::{
+        atomic::{
+            AtomicUsize,
+            Ordering,
+        },
+        OnceLock,
+    },
+    thread,
+};
+
+/// The maximum number of variables that are looked ahead upon every split.
+///
+/// # Note
+///
+/// The candidates are the unassigned variables with the most occurrences.
+const MAX_CANDIDATES: usize = 128;
+
+/// The weight of a reduced clause relative to a reduced clause with one more literal.
+const REDUCTION_WEIGHT: f64 = 5.0;
+
+/// The result of solving the cubes of an instance.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CubeResult {
+    /// The instance is satisfiable with the model under the cube with the index.
+    Sat { cube: usize, model: Model },
+    /// All cubes and therefore the instance are unsatisfiable.
+    Unsat,
+    /// No cube was satisfiable but some were interrupted by a limit of the solver.
+    Unknown,
+}
+
+/// The result of the lookahead on a cube.
+#[derive(Debug)]
+enum Lookahead {
+    /// The cube is unsatisfiable.
+    Refuted,
+    /// The cube assigns all variables without a conflict.
+    Satisfied,
+    /// The cube extended by the implied failed literals is split on the variable.
+    Split {
+        implied: Vec<Literal>,
+        variable: Variable,
+    },
+}
+
+/// Splits instances into cubes and solves them.
+#[derive(Debug, Default, Clone)]
+pub struct Cuber {
+    /// The solver used for lookahead and for solving the cubes.
+    solver: Solver,
+    /// The clauses in the order in which they were added.
+    clauses: Vec<Vec<Literal>>,
+    /// The indices of the clauses in which every literal occurs.
+    occurrences: Vec<Vec<usize>>,
+    /// The lookahead during which every clause was counted last.
+    counted: Vec<u64>,
+    /// The number of lookaheads so far.
+    lookaheads: u64,
+}
+
+impl ConsumeCnf for Cuber {
+    fn new_variables(&mut self, amount: usize) {
+        self.new_literal_chunk(amount);
+    }
+
+    fn consume_cnf_clause(&mut self, literals: &[Literal]) {
+        self.add_clause(literals);
+    }
+}
+
+impl Cuber {
+    /// Consumes the `.cnf` input and feeds it to the returned cuber.
+    ///
+    /// # Errors
+    ///
+    /// - If the input is no valid `.cnf` format.
+    /// - If the input encodes an invalid CNF formula.
+    pub fn from_cnf<I>(input: &mut I) -> Result<Self, CnfError<Error>>
+    where
+        I: Input,
+    {
+        let mut builder = CnfBuilder::default();
+        cnf_parser::parse_cnf(input, &mut builder)?;
+        Ok(builder.finalize())
+    }
+
+    /// Allocates the given amount of new literals and returns them.
+    pub fn new_literal_chunk(&mut self, amount: usize) -> LiteralChunk {
+        let chunk = self.solver.new_literal_chunk(amount);
+        self.occurrences
+            .resize_with(2 * self.len_variables(), Vec::new);
+        chunk
+    }
+
+    /// Returns the number of variables of the clauses.
+    pub fn len_variables(&self) -> usize {
+        self.solver.len_variables()
+    }
+
+    /// Adds the clause.
+    ///
+    /// # Panics
+    ///
+    /// If any of the literals has not been registered.
+    pub fn add_clause(&mut self, literals: &[Literal]) {
+        for literal in literals {
+            if literal.variable().into_index() >= self.len_variables() {
+                panic!("encountered invalid literal: {}", literal)
+            }
+        }
+        let index = self.clauses.len();
+        self.clauses.push(literals.to_vec());
+        self.counted.push(0);
+        let is_tautological =
+            literals.iter().any(|&literal| literals.contains(&!literal));
+        if is_tautological {
+            // Tautological clauses are never reduced by the lookahead.
+            return
+        }
+        for literal in literals {
+            self.occurrences[literal.into_u32() as usize].push(index);
+        }
+        self.solver.consume_clause(literals.iter().copied());
+    }
+
+    /// Returns the clauses in the order in which they were added.
+    pub fn clauses(&self) -> &[Vec<Literal>] {
+        &self.clauses
+    }
+
+    /// Returns the solver of the cubes to configure its limits.
+    pub fn solver_mut(&mut self) -> &mut Solver {
+        &mut self.solver
+    }
+
+    /// Splits the instance into cubes of at most `max_depth` decisions until
+    /// there are `max_cubes` cubes.
+    ///
+    /// # Note
+    ///
+    /// - The instance is satisfiable if and only if any of the cubes is.
+    /// - Cubes are split in breadth-first order so that they are balanced.
+    /// - Cubes contain the literals implied by failed literal probing on the
+    ///   way as well as their decisions.
+    /// - Cubes that are refuted by the lookahead are dropped so that an
+    ///   unsatisfiable instance might yield no cubes at all.
+    pub fn cubes(&mut self, max_depth: usize, max_cubes: usize) -> Vec<Vec<Literal>> {
+        let mut cubes = Vec::new();
+        let mut open = VecDeque::from([(Vec::new(), 0)]);
+        while let Some((cube, depth)) = open.pop_front() {
+            if depth >= max_depth || cubes.len() + open.len() + 2 > max_cubes {
+                cubes.push(cube);
+                continue
+            }
+            match self.lookahead(&cube) {
+                Lookahead::Refuted => (),
+                Lookahead::Satisfied => cubes.push(cube),
+                Lookahead::Split { implied, variable } => {
+                    let mut cube = cube;
+                    cube.extend(implied);
+                    for sign in [Sign::POS, Sign::NEG] {
+                        let mut split = cube.clone();
+                        split.push(Literal::new(variable, sign));
+                        open.push_back((split, depth + 1));
+                    }
+                }
+            }
+        }
+        self.reset();
+        cubes
+    }
+
+    /// Solves the cubes on the given number of threads.
+    ///
+    /// Returns the first satisfiable cube with its model.
+    ///
+    /// # Note
+    ///
+    /// Every thread solves its cubes incrementally with its own copy of the
+    /// solver. With a single thread the cubes are solved in order by the
+    /// solver of the cuber on the calling thread.
+    ///
+    /// # Errors
+    ///
+    /// If the solver encountered an error before finding a satisfiable cube.
+    ///
+    /// # Panics
+    ///
+    /// If the number of threads is zero.
+    pub fn solve_cubes(
+        &mut self,
+        cubes: &[Vec<Literal>],
+        len_threads: usize,
+    ) -> Result<CubeResult, Error> {
+        assert!(len_threads > 0, "encountered unexpected zero threads");
+        if len_threads == 1 {
+            let mut is_unknown = false;
+            for (index, cube) in cubes.iter().enumerate() {
+                match self.solver.solve(cube.iter().copied())? {
+                    SolveResult::Sat(sat) => {
+                        return Ok(CubeResult::Sat {
+                            cube: index,
+                            model: sat.model().clone(),
+                        })
+                    }
+                    SolveResult::Unsat => (),
+                    SolveResult::Unknown => is_unknown = true,
+                }
+            }
+            return Ok(match is_unknown {
+                true => CubeResult::Unknown,
+                false => CubeResult::Unsat,
+            })
+        }
+        let next_cube = AtomicUsize::new(0);
+        let answer = OnceLock::new();
+        let error = OnceLock::new();
+        let unknown = OnceLock::new();
+        let interrupter = self.solver.interrupter();
+        thread::scope(|scope| {
+            for _ in 0..len_threads {
+                let mut solver = self.solver.clone();
+                let (next_cube, answer, error, unknown, interrupter) =
+                    (&next_cube, &answer, &error, &unknown, &interrupter);
+                scope.spawn(move || {
+                    loop {
+                        let index = next_cube.fetch_add(1, Ordering::Relaxed);
+                        if index >= cubes.len() || answer.get().is_some() {
+                            return
+                        }
+                        match solver.solve(cubes[index].iter().copied()) {
+                            Ok(SolveResult::Sat(sat)) => {
+                                let found = CubeResult::Sat {
+                                    cube: index,
+                                    model: sat.model().clone(),
+                                };
+                                if answer.set(found).is_ok() {
+                                    interrupter.interrupt();
+                                }
+                                return
+                            }
+                            Ok(SolveResult::Unsat) => (),
+                            Ok(SolveResult::Unknown) => {
+                                let _ = unknown.set(());
+                            }
+                            Err(found_error) => {
+                                let _ = error.set(found_error);
+                                return
+                            }
+                        }
+                    }
+                });
+            }
+        });
+        if let Some(answer) = answer.into_inner() {
+            interrupter.reset();
+            return Ok(answer)
+        }
+        if let Some(error) = error.into_inner() {
+            return Err(error)
+        }
+        match unknown.into_inner() {
+            Some(()) => Ok(CubeResult::Unknown),
+            None => Ok(CubeResult::Unsat),
+        }
+    }
+
+    /// Writes the clauses and the cubes in the iCNF format.
+    ///
+    /// # Note
+    ///
+    /// Every cube is written as an `a ... 0` line of assumptions.
+    pub fn write_icnf<W>(&self, out: &mut W, cubes: &[Vec<Literal>]) -> io::Result<()>
+    where
+        W: Write,
+    {
+        writeln!(out, "p inccnf")?;
+        for clause in &self.clauses {
+            for literal in clause {
+                write!(out, "{} ", literal)?;
+            }
+            writeln!(out, "0")?;
+        }
+        for cube in cubes {
+            write!(out, "a ")?;
+            for literal in cube {
+                write!(out, "{} ", literal)?;
+            }
+            writeln!(out, "0")?;
+        }
+        Ok(())
+    }
+
+    /// Undoes all assignments of the solver.
+    fn reset(&mut self) {
+        let solver = &mut self.solver;
+        solver
+            .assignment
+            .reset(&mut solver.constraints, &mut solver.decider);
+    }
+
+    /// Propagates the enqueued assignments.
+    ///
+    /// Returns `false` upon a conflict.
+    fn propagate(&mut self) -> bool {
+        let solver = &mut self.solver;
+        let result = solver
+            .assignment
+            .propagate(&mut solver.clauses, &mut solver.constraints);
+        matches!(result, PropagationResult::Consistent)
+    }
+
+    /// Returns the value of the literal under the current assignment if any.
+    fn value(&self, literal: Literal) -> Option<bool> {
+        self.solver
+            .assignment
+            .variable_assignment()
+            .is_satisfied(literal)
+    }
+
+    /// Assigns the literal at a new decision level and propagates it.
+    ///
+    /// Returns `false` upon a conflict.
+    fn assume(&mut self, literal: Literal) -> bool {
+        match self.value(literal) {
+            Some(value) => value,
+            None => {
+                self.solver.assignment.bump_decision_level();
+                self.solver
+                    .assignment
+                    .enqueue_assumption(literal)
+                    .expect("encountered unexpected assigned assumption");
+                self.propagate()
+            }
+        }
+    }
+
+    /// Assigns the literal at a new decision level and returns the weighted
+    /// number of clauses that are reduced but not satisfied by its propagation.
+    ///
+    /// Returns `None` upon a conflict. Undoes all assignments of the lookahead.
+    fn look_ahead(&mut self, literal: Literal) -> Option<f64> {
+        let level = self.solver.assignment.current_decision_level();
+        let start = self.solver.assignment.trail_len();
+        self.solver.assignment.bump_decision_level();
+        self.solver
+            .assignment
+            .enqueue_assumption(literal)
+            .expect("encountered unexpected assigned lookahead literal");
+        let difference = match self.propagate() {
+            true => Some(self.difference(start)),
+            false => None,
+        };
+        let solver = &mut self.solver;
+        solver.assignment.pop_decision_level(
+            level,
+            &mut solver.constraints,
+            &mut solver.decider,
+        );
+        difference
+    }
+
+    /// Returns the weighted number of clauses reduced but not satisfied by the
+    /// assignments on the trail from the start position on.
+    ///
+    /// # Note
+    ///
+    /// Reduced clauses with fewer unassigned literals weigh more since they
+    /// are closer to propagating.
+    fn difference(&mut self, start: usize) -> f64 {
+        self.lookaheads += 1;
+        let mut difference = 0.0;
+        for position in start..self.solver.assignment.trail_len() {
+            let falsified = !self.solver.assignment.trail_literal(position);
+            for &index in &self.occurrences[falsified.into_u32() as usize] {
+                if self.counted[index] == self.lookaheads {
+                    continue
+                }
+                self.counted[index] = self.lookaheads;
+                let mut unassigned = 0;
+                let mut is_satisfied = false;
+                for &literal in &self.clauses[index] {
+                    match self
+                        .solver
+                        .assignment
+                        .variable_assignment()
+                        .is_satisfied(literal)
+                    {
+                        Some(true) => is_satisfied = true,
+                        Some(false) => (),
+                        None => unassigned += 1,
+                    }
+                }
+                if !is_satisfied && unassigned >= 2 {
+                    difference += REDUCTION_WEIGHT.powi(2 - unassigned);
+                }
+            }
+        }
+        difference
+    }
+
+    /// Returns up to [`MAX_CANDIDATES`] unassigned variables with the most occurrences.
+    fn candidates(&self) -> Vec<Variable> {
+        let mut candidates = (0..self.len_variables())
+            .map(Variable::from_index)
+            .filter(|&variable| self.value(Literal::new(variable, Sign::POS)).is_none())
+            .collect::<Vec<_>>();
+        let occurrences = |variable: Variable| {
+            let positive = Literal::new(variable, Sign::POS).into_u32() as usize;
+            self.occurrences[positive].len() + self.occurrences[positive ^ 1].len()
+        };
+        candidates.sort_by_key(|&variable| core::cmp::Reverse(occurrences(variable)));
+        candidates.truncate(MAX_CANDIDATES);
+        candidates
+    }
+
+    /// Looks ahead on both polarities of the candidate variables under the cube
+    /// and returns the variable to split on.
+    ///
+    /// # Note
+    ///
+    /// Failed literals whose lookahead yields a conflict are implied under the
+    /// cube and assigned immediately. The score of a variable is the product of
+    /// the differences of both of its polarities as in the march solvers.
+    fn lookahead(&mut self, cube: &[Literal]) -> Lookahead {
+        self.reset();
+        if self.solver.enqueue_hard_facts().is_err() || !self.propagate() {
+            return Lookahead::Refuted
+        }
+        for &literal in cube {
+            if !self.assume(literal) {
+                return Lookahead::Refuted
+            }
+        }
+        let mut implied = Vec::new();
+        let mut best: Option<(f64, Variable)> = None;
+        for variable in self.candidates() {
+            let mut differences = [0.0; 2];
+            for (difference, sign) in differences.iter_mut().zip([Sign::POS, Sign::NEG]) {
+                let literal = Literal::new(variable, sign);
+                if self.value(literal).is_some() {
+                    break
+                }
+                match self.look_ahead(literal) {
+                    Some(found) => *difference = found,
+                    None => {
+                        if !self.assume(!literal) {
+                            return Lookahead::Refuted
+                        }
+                        implied.push(!literal);
+                    }
+                }
+            }
+            if self.value(Literal::new(variable, Sign::POS)).is_some() {
+                continue
+            }
+            let [positive, negative] = differences;
+            let score = 1024.0 * positive * negative + positive + negative;
+            if best.is_none_or(|(best_score, _)| score > best_score) {
+                best = Some((score, variable));
+            }
+        }
+        let variable = match best {
+            Some((_, variable)) => Some(variable),
+            None => self.candidates().first().copied(),
+        };
+        match variable {
+            Some(variable) => Lookahead::Split { implied, variable },
+            None => Lookahead::Satisfied,
+        }
+    }
+}
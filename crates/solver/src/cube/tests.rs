@@ -0,0 +1,221 @@
+use super::*;
+use crate::tests::Lcg;
+
+/// Returns `true` if the clause is satisfied by the assignment.
+///
+/// The `n`-th bit of the assignment is the value of the `n`-th variable.
+fn is_satisfied(clause: &[Literal], assignment: u32) -> bool {
+    clause.iter().any(|literal| {
+        let value = assignment & (1 << literal.variable().into_index()) != 0;
+        value == (literal.sign() == Sign::POS)
+    })
+}
+
+/// Returns `true` if all literals of the cube are satisfied by the assignment.
+fn is_covered(cube: &[Literal], assignment: u32) -> bool {
+    cube.iter()
+        .all(|&literal| is_satisfied(&[literal], assignment))
+}
+
+/// Returns a cuber of random clauses over the given number of variables.
+fn random_cuber(rng: &mut Lcg, len_variables: usize) -> Cuber {
+    let mut cuber = Cuber::default();
+    let vars = cuber
+        .new_literal_chunk(len_variables)
+        .into_iter()
+        .collect::<Vec<_>>();
+    for _ in 0..3 * len_variables + rng.next(2 * len_variables) {
+        let clause = (0..1 + rng.next(3))
+            .map(|_| {
+                let literal = vars[rng.next(len_variables)];
+                if rng.next(2) == 0 {
+                    !literal
+                } else {
+                    literal
+                }
+            })
+            .collect::<Vec<_>>();
+        cuber.add_clause(&clause);
+    }
+    cuber
+}
+
+#[test]
+fn random_cubes_agree_with_brute_force() {
+    let mut rng = Lcg(42);
+    for _ in 0..200 {
+        let len_variables = 1 + rng.next(12);
+        let mut cuber = random_cuber(&mut rng, len_variables);
+        let max_cubes = 1 + rng.next(16);
+        let cubes = cuber.cubes(1 + rng.next(5), max_cubes);
+        assert!(cubes.len() <= max_cubes);
+        let clauses = cuber.clauses().to_vec();
+        let is_model = |assignment: u32| {
+            clauses
+                .iter()
+                .all(|clause| is_satisfied(clause, assignment))
+        };
+        // Every model satisfies some cube.
+        let models = (0..1_u32 << len_variables)
+            .filter(|&assignment| is_model(assignment))
+            .collect::<Vec<_>>();
+        for &model in &models {
+            assert!(cubes.iter().any(|cube| is_covered(cube, model)));
+        }
+        for len_threads in [1, 3] {
+            match cuber.solve_cubes(&cubes, len_threads).unwrap() {
+                CubeResult::Sat { cube, model } => {
+                    let assignment = (0..len_variables)
+                        .filter(|&index| {
+                            let literal =
+                                Literal::new(Variable::from_index(index), Sign::POS);
+                            model.is_satisfied(literal) == Ok(true)
+                        })
+                        .fold(0, |assignment, index| assignment | 1 << index);
+                    assert!(is_model(assignment));
+                    assert!(is_covered(&cubes[cube], assignment));
+                }
+                CubeResult::Unsat => assert!(models.is_empty()),
+                CubeResult::Unknown => panic!("encountered unexpected unknown result"),
+            }
+        }
+    }
+}
+
+#[test]
+fn pigeon_hole_cubes_work() {
+    let (len_holes, len_pigeons) = (5, 6);
+    let mut cuber = Cuber::default();
+    let vars = cuber
+        .new_literal_chunk(len_pigeons * len_holes)
+        .into_iter()
+        .collect::<Vec<_>>();
+    let placed = |pigeon: usize, hole: usize| vars[pigeon * len_holes + hole];
+    for pigeon in 0..len_pigeons {
+        let clause = (0..len_holes)
+            .map(|hole| placed(pigeon, hole))
+            .collect::<Vec<_>>();
+        cuber.add_clause(&clause);
+    }
+    for hole in 0..len_holes {
+        for a in 0..len_pigeons {
+            for b in a + 1..len_pigeons {
+                cuber.add_clause(&[!placed(a, hole), !placed(b, hole)]);
+            }
+        }
+    }
+    let cubes = cuber.cubes(3, 8);
+    assert!(!cubes.is_empty() && cubes.len() <= 8);
+    assert_eq!(cuber.solve_cubes(&cubes, 1), Ok(CubeResult::Unsat));
+    assert_eq!(cuber.solve_cubes(&cubes, 2), Ok(CubeResult::Unsat));
+    // The solver is not left interrupted by the threads.
+    assert!(!cuber.solver_mut().interrupter().is_interrupted());
+}
+
+#[test]
+fn write_icnf_works() {
+    let input = b"p cnf 3 2\n1 -2 0\n2 3 0\n";
+    let mut cuber = Cuber::from_cnf(&mut &input[..]).unwrap();
+    assert_eq!(cuber.len_variables(), 3);
+    let cubes = cuber.cubes(1, 2);
+    assert_eq!(cubes.len(), 2);
+    let mut output = Vec::new();
+    cuber.write_icnf(&mut output, &cubes).unwrap();
+    let output = String::from_utf8(output).unwrap();
+    let mut lines = output.lines();
+    assert_eq!(lines.next(), Some("p inccnf"));
+    assert_eq!(lines.next(), Some("1 -2 0"));
+    assert_eq!(lines.next(), Some("2 3 0"));
+    let assumptions = lines.collect::<Vec<_>>();
+    assert_eq!(assumptions.len(), 2);
+    assert!(assumptions
+        .iter()
+        .all(|line| line.starts_with("a ") && line.ends_with(" 0")));
+    // An unsatisfiable instance is refuted by the lookahead.
+    cuber.add_clause(&[Literal::from(-1)]);
+    cuber.add_clause(&[Literal::from(2)]);
+    assert!(cuber.cubes(2, 4).is_empty());
+    assert_eq!(cuber.solve_cubes(&[], 1), Ok(CubeResult::Unsat));
+}
+
+#[test]
+fn parse_icnf_works() {
+    let icnf = Icnf::parse(
+        b"\
+        c comment\n\
+        p inccnf\n\
+        1 -2 0\n\
+        a 2 0\n\
+        \n\
+        -1 3 0\n\
+        a 0\n\
+        a -3 -5 0\n\
+        ",
+    )
+    .unwrap();
+    let literals = |values: &[i32]| {
+        values
+            .iter()
+            .map(|&value| Literal::from(value))
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(icnf.len_variables(), 5);
+    assert_eq!(
+        icnf.lines(),
+        [
+            IcnfLine::Clause(literals(&[1, -2])),
+            IcnfLine::Assumptions(literals(&[2])),
+            IcnfLine::Clause(literals(&[-1, 3])),
+            IcnfLine::Assumptions(literals(&[])),
+            IcnfLine::Assumptions(literals(&[-3, -5])),
+        ]
+    );
+    // The iCNF output of the cuber is valid input.
+    let mut cuber = Cuber::default();
+    cuber.new_literal_chunk(3);
+    cuber.add_clause(&literals(&[1, 2, 3]));
+    cuber.add_clause(&literals(&[-1, -2]));
+    let cubes = cuber.cubes(2, 4);
+    let mut output = Vec::new();
+    cuber.write_icnf(&mut output, &cubes).unwrap();
+    let icnf = Icnf::parse(&output).unwrap();
+    let queries = icnf
+        .lines()
+        .iter()
+        .filter_map(|line| {
+            match line {
+                IcnfLine::Assumptions(assumptions) => Some(assumptions.clone()),
+                IcnfLine::Clause(_) => None,
+            }
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(queries, cubes);
+}
+
+#[test]
+fn parse_icnf_errors_work() {
+    assert_eq!(
+        Icnf::parse(b"1 2"),
+        Err(IcnfError::UnterminatedLine { line: 1 })
+    );
+    assert_eq!(
+        Icnf::parse(b"1 0\na x 0"),
+        Err(IcnfError::UnexpectedToken { line: 2 })
+    );
+    assert_eq!(
+        Icnf::parse(b"a 1 0 2"),
+        Err(IcnfError::UnexpectedToken { line: 1 })
+    );
+    assert_eq!(
+        Icnf::parse(b"99999999999999999999 0"),
+        Err(IcnfError::OutOfRangeInteger { line: 1 })
+    );
+    assert_eq!(
+        Icnf::parse(b"1 0\np inccnf"),
+        Err(IcnfError::InvalidHeader { line: 2 })
+    );
+    assert_eq!(
+        Icnf::parse(b"p cnf 1 1"),
+        Err(IcnfError::InvalidHeader { line: 1 })
+    );
+}
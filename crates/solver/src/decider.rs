@@ -1,6 +1,7 @@
 use crate::{
     assignment::PartialAssignment,
     literal::RegisterVariables,
+    Sign,
     Variable,
 };
 use bounded::{
@@ -51,14 +52,77 @@ impl RestoreVariable for Decider {
     }
 }
 
+impl Priority {
+    /// Returns the priority after bumping it with the ACIDS scheme.
+    ///
+    /// # Note
+    ///
+    /// The ACIDS scheme averages the old priority with the current
+    /// activity delta which grows by one with every conflict.
+    fn acids(self, activity_delta: u64) -> Self {
+        Self(self.0 / 2 + activity_delta / 2 + (self.0 & activity_delta & 1))
+    }
+}
+
+/// The scheme by which the priorities of variables involved in conflicts are bumped.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub enum DecisionHeuristic {
+    /// Averages the old priority with the number of conflicts so far.
+    #[default]
+    Acids,
+    /// Replaces the priority with the number of conflicts so far so that the
+    /// most recently bumped variables are decided first.
+    ///
+    /// # Note
+    ///
+    /// Only the latest bump of a variable counts in contrast to ACIDS which
+    /// also keeps half of the old priority.
+    RecentBump,
+}
+
+/// The polarity with which variables are decided.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub enum Phase {
+    /// Variables are decided to be `true`.
+    #[default]
+    Positive,
+    /// Variables are decided to be `false`.
+    Negative,
+    /// Variables are decided pseudo randomly depending on the seed.
+    Random,
+}
+
 /// Heuristic that chooses the next literal to propagate.
 #[derive(Debug, Default, Clone)]
 pub struct Decider {
     len_variables: usize,
     priorities: BoundedHeap<Variable, Priority>,
-    _activity_delta: u64,
+    activity_delta: u64,
+    /// The state of the random number generator or `0` if randomization is disabled.
+    random_state: u64,
+    heuristic: DecisionHeuristic,
+    phase: Phase,
+    /// The state of the random number generator for random phases.
+    phase_state: u64,
 }
 
+/// Advances the xorshift64* generator with the non-zero state and returns
+/// its next pseudo random number.
+fn xorshift(state: &mut u64) -> u64 {
+    *state ^= *state >> 12;
+    *state ^= *state << 25;
+    *state ^= *state >> 27;
+    state.wrapping_mul(0x2545_F491_4F6C_DD1D) >> 32
+}
+
+/// The exclusive upper bound of the random initial priorities of variables.
+///
+/// # Note
+///
+/// Kept small so that the priorities of variables involved in conflicts
+/// quickly dominate the random ones.
+const MAX_RANDOM_PRIORITY: u64 = 64;
+
 impl RegisterVariables for Decider {
     fn register_variables(&mut self, additional: usize) {
         let total_variables = self.len_variables() + additional;
@@ -68,9 +132,17 @@ impl RegisterVariables for Decider {
             self.priorities
                 .push_or_update(variable, identity)
                 .expect("unexpected variable index out of bounds");
+            self.randomize_priority(variable);
         }
         self.len_variables += additional;
     }
+
+    fn recycle_variable(&mut self, variable: Variable) {
+        self.priorities
+            .push_or_update(variable, |_| Priority::default())
+            .expect("encountered unexpected out of bounds variable");
+        self.randomize_priority(variable);
+    }
 }
 
 impl Decider {
@@ -79,6 +151,59 @@ impl Decider {
         self.len_variables
     }
 
+    /// Seeds the random number generator and randomizes the priorities of
+    /// all registered variables.
+    ///
+    /// # Note
+    ///
+    /// The priorities of variables registered later on are randomized as well.
+    pub fn set_seed(&mut self, seed: u64) {
+        // The xorshift generator must never have a zero state.
+        self.random_state = seed | 1;
+        self.phase_state = seed | 1;
+        for i in 0..self.len_variables() {
+            self.randomize_priority(Variable::from_index(i));
+        }
+    }
+
+    /// Bumps the priority of the variable by a random amount if randomization is enabled.
+    fn randomize_priority(&mut self, variable: Variable) {
+        if self.random_state == 0 {
+            return
+        }
+        let random = xorshift(&mut self.random_state);
+        self.bump_priority_by(variable, random % MAX_RANDOM_PRIORITY);
+    }
+
+    /// Sets the scheme by which the priorities of conflicting variables are bumped.
+    pub fn set_heuristic(&mut self, heuristic: DecisionHeuristic) {
+        self.heuristic = heuristic;
+    }
+
+    /// Sets the polarity with which variables are decided.
+    pub fn set_phase(&mut self, phase: Phase) {
+        self.phase = phase;
+    }
+
+    /// Returns the polarity of the next decided variable.
+    pub fn next_phase(&mut self) -> Sign {
+        match self.phase {
+            Phase::Positive => Sign::POS,
+            Phase::Negative => Sign::NEG,
+            Phase::Random => {
+                // Without a seed the random phases are still deterministic.
+                if self.phase_state == 0 {
+                    self.phase_state = 1;
+                }
+                if xorshift(&mut self.phase_state) & 1 == 0 {
+                    Sign::POS
+                } else {
+                    Sign::NEG
+                }
+            }
+        }
+    }
+
     /// Bumps the priority of the given variable by a given amount.
     pub fn bump_priority_by(&mut self, variable: Variable, amount: u64) {
         self.priorities
@@ -86,6 +211,27 @@ impl Decider {
             .expect("encountered unexpected out of bounds variable");
     }
 
+    /// Bumps the activity of a variable that took part in a conflict.
+    pub fn bump_activity(&mut self, variable: Variable) {
+        let activity_delta = self.activity_delta;
+        let heuristic = self.heuristic;
+        self.priorities
+            .update_priority(variable, |priority| {
+                match heuristic {
+                    DecisionHeuristic::Acids => priority.acids(activity_delta),
+                    DecisionHeuristic::RecentBump => Priority(activity_delta),
+                }
+            })
+            .expect("encountered unexpected out of bounds variable");
+    }
+
+    /// Informs the decision heuristic about a conflict.
+    ///
+    /// This increases the weight of future activity bumps.
+    pub fn on_conflict(&mut self) {
+        self.activity_delta += 1;
+    }
+
     /// Returns the next variable to propgate if any unassigned variable is left.
     ///
     /// This removes the variable from the priority queue.
@@ -0,0 +1,123 @@
+use crate::{
+    assignment::PropagationResult,
+    retract,
+    Literal,
+    Solver,
+};
+use std::{
+    io,
+    io::Write,
+};
+
+/// Selects the optional parts of the formula written by [`Solver::write_dimacs`].
+///
+/// By default only the clauses and the unit clauses given to the solver are written.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub struct DimacsOptions {
+    learnt_clauses: bool,
+    root_assignment: bool,
+}
+
+impl DimacsOptions {
+    /// Additionally writes the learned clauses that have not been deleted, yet,
+    /// as well as the learned and imported unit clauses.
+    pub fn learnt_clauses(mut self, learnt_clauses: bool) -> Self {
+        self.learnt_clauses = learnt_clauses;
+        self
+    }
+
+    /// Additionally writes the literals implied at the root level as unit clauses.
+    pub fn root_assignment(mut self, root_assignment: bool) -> Self {
+        self.root_assignment = root_assignment;
+        self
+    }
+}
+
+/// Writes the formula of the solver in the DIMACS format.
+///
+/// # Note
+///
+/// - Unit clauses are written before all other clauses.
+/// - An unsatisfiable formula that contains the empty clause or whose root
+///   level propagation yields a conflict is written with the empty clause.
+/// - Native constraints are not written but their number is stated in a
+///   comment line.
+pub(crate) fn write_dimacs<W>(
+    solver: &mut Solver,
+    out: &mut W,
+    options: DimacsOptions,
+) -> io::Result<()>
+where
+    W: Write,
+{
+    retract::forget_learnt_if_removed(solver);
+    let (mut is_unsat, mut units) = if options.learnt_clauses {
+        (solver.encountered_empty_clause, solver.hard_facts.clone())
+    } else {
+        // The hard facts also contain the learned and imported unit clauses.
+        (
+            solver.consumed.contains_empty(),
+            solver.consumed.units().collect::<Vec<_>>(),
+        )
+    };
+    if options.root_assignment {
+        match root_assignment(solver) {
+            Some(assignment) => units.extend(assignment),
+            None => is_unsat = true,
+        }
+    }
+    // Unit clauses may repeat and are part of the root assignment.
+    let mut is_written = vec![false; 2 * solver.len_variables()];
+    units.retain(|unit| {
+        !core::mem::replace(&mut is_written[unit.into_u32() as usize], true)
+    });
+    let clauses = (&solver.clauses)
+        .into_iter()
+        .filter(|clause| {
+            let header = clause.header();
+            !header.is_deleted() && (options.learnt_clauses || !header.is_learnt())
+        })
+        .collect::<Vec<_>>();
+    let len_clauses = usize::from(is_unsat) + units.len() + clauses.len();
+    if !solver.constraints.is_empty() {
+        writeln!(
+            out,
+            "c {} native constraints are not written",
+            solver.constraints.len()
+        )?;
+    }
+    writeln!(out, "p cnf {} {}", solver.len_variables(), len_clauses)?;
+    if is_unsat {
+        writeln!(out, "0")?;
+    }
+    for unit in units {
+        writeln!(out, "{} 0", unit)?;
+    }
+    for clause in clauses {
+        for literal in clause.literals() {
+            write!(out, "{} ", literal)?;
+        }
+        writeln!(out, "0")?;
+    }
+    Ok(())
+}
+
+/// Returns the literals implied by the hard facts at the root level.
+///
+/// Returns `None` if the root level propagation yields a conflict.
+fn root_assignment(solver: &mut Solver) -> Option<Vec<Literal>> {
+    let is_consistent = solver.enqueue_hard_facts().is_ok()
+        && matches!(
+            solver
+                .assignment
+                .propagate(&mut solver.clauses, &mut solver.constraints),
+            PropagationResult::Consistent
+        );
+    let assignment = (0..solver.assignment.trail_len())
+        .map(|position| solver.assignment.trail_literal(position))
+        .collect::<Vec<_>>();
+    solver
+        .assignment
+        .reset(&mut solver.constraints, &mut solver.decider);
+    is_consistent.then_some(assignment)
+}
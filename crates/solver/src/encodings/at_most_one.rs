@@ -0,0 +1,90 @@
+use super::{
+    add_clause,
+    new_literals,
+};
+use crate::{
+    Literal,
+    Solver,
+};
+
+/// The number of literals up to which the recursive encodings fall back
+/// to the pairwise encoding.
+const PAIRWISE_THRESHOLD: usize = 4;
+
+/// The size of the groups of the commander encoding.
+const COMMANDER_GROUP_SIZE: usize = 3;
+
+/// Encodes at-most-one by forbidding every pair of literals.
+pub fn pairwise(solver: &mut Solver, literals: &[Literal]) {
+    for (n, &first) in literals.iter().enumerate() {
+        for &second in &literals[n + 1..] {
+            add_clause(solver, &[!first, !second]);
+        }
+    }
+}
+
+/// Encodes at-most-one using the ladder encoding.
+///
+/// The auxiliary variable `s_i` is `true` if any of the first `i+1` literals is `true`.
+pub fn ladder(solver: &mut Solver, literals: &[Literal]) {
+    if literals.len() <= 2 {
+        return pairwise(solver, literals)
+    }
+    let ladder = new_literals(solver, literals.len() - 1);
+    for (n, &literal) in literals.iter().enumerate() {
+        if let Some(&current) = ladder.get(n) {
+            add_clause(solver, &[!literal, current]);
+        }
+        if let Some(&previous) = n.checked_sub(1).and_then(|n| ladder.get(n)) {
+            add_clause(solver, &[!literal, !previous]);
+            if let Some(&current) = ladder.get(n) {
+                add_clause(solver, &[!previous, current]);
+            }
+        }
+    }
+}
+
+/// Encodes at-most-one using the commander encoding.
+///
+/// Every group of literals has a commander that is implied by all of the
+/// literals in its group. The commanders are constrained recursively.
+pub fn commander(solver: &mut Solver, literals: &[Literal]) {
+    if literals.len() <= PAIRWISE_THRESHOLD {
+        return pairwise(solver, literals)
+    }
+    let mut commanders = Vec::new();
+    for group in literals.chunks(COMMANDER_GROUP_SIZE) {
+        if let [single] = group {
+            commanders.push(*single);
+            continue
+        }
+        pairwise(solver, group);
+        let commander = solver.new_literal();
+        for &literal in group {
+            add_clause(solver, &[!literal, commander]);
+        }
+        commanders.push(commander);
+    }
+    self::commander(solver, &commanders)
+}
+
+/// Encodes at-most-one using the product encoding.
+///
+/// The literals are arranged in a grid with a row and a column variable
+/// implied by every literal. At most one row and at most one column
+/// variable are allowed to be `true` which is encoded recursively.
+pub fn product(solver: &mut Solver, literals: &[Literal]) {
+    if literals.len() <= PAIRWISE_THRESHOLD {
+        return pairwise(solver, literals)
+    }
+    let len_columns = (literals.len() as f64).sqrt().ceil() as usize;
+    let len_rows = literals.len().div_ceil(len_columns);
+    let rows = new_literals(solver, len_rows);
+    let columns = new_literals(solver, len_columns);
+    for (n, &literal) in literals.iter().enumerate() {
+        add_clause(solver, &[!literal, rows[n / len_columns]]);
+        add_clause(solver, &[!literal, columns[n % len_columns]]);
+    }
+    product(solver, &rows);
+    product(solver, &columns);
+}
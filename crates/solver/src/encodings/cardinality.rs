@@ -0,0 +1,328 @@
+//! Encodings of `sum(l_i) <= k` for `0 < k < n`.
+//!
+//! # Note
+//!
+//! The encodings only contain the clauses required for upper bounds:
+//! the auxiliary variables are implied by the inputs but not vice versa.
+
+use super::{
+    add_clause,
+    new_literals,
+};
+use crate::{
+    Literal,
+    Solver,
+};
+
+/// Encodes at-most-k using the sequential counter encoding.
+///
+/// The auxiliary variable `s[i][j]` is `true` if at least `j+1` of the first
+/// `i+1` literals are `true`.
+pub fn sequential_counter(solver: &mut Solver, literals: &[Literal], k: usize) {
+    let len_counters = literals.len() - 1;
+    let counters = new_literals(solver, len_counters * k);
+    let counter = |i: usize, j: usize| counters[i * k + j];
+    add_clause(solver, &[!literals[0], counter(0, 0)]);
+    for j in 1..k {
+        add_clause(solver, &[!counter(0, j)]);
+    }
+    for (i, &literal) in literals.iter().enumerate().skip(1) {
+        if i < len_counters {
+            add_clause(solver, &[!literal, counter(i, 0)]);
+            add_clause(solver, &[!counter(i - 1, 0), counter(i, 0)]);
+            for j in 1..k {
+                add_clause(solver, &[!literal, !counter(i - 1, j - 1), counter(i, j)]);
+                add_clause(solver, &[!counter(i - 1, j), counter(i, j)]);
+            }
+        }
+        add_clause(solver, &[!literal, !counter(i - 1, k - 1)]);
+    }
+}
+
+/// Encodes at-most-k using the totalizer encoding.
+///
+/// Every node of a binary tree over the literals counts the `true` literals
+/// of its subtree in unary up to `k+1`.
+pub fn totalizer(solver: &mut Solver, literals: &[Literal], k: usize) {
+    let outputs = totalizer_node(solver, literals, k + 1);
+    if let Some(&exceeded) = outputs.get(k) {
+        add_clause(solver, &[!exceeded]);
+    }
+}
+
+/// Returns the unary outputs of the totalizer node over the literals.
+///
+/// The `i`-th output is `true` if at least `i+1` of the literals are `true`.
+/// Counts greater than `limit` are represented by the `limit`-th output.
+pub fn totalizer_node(
+    solver: &mut Solver,
+    literals: &[Literal],
+    limit: usize,
+) -> Vec<Literal> {
+    if literals.len() == 1 {
+        return literals.to_vec()
+    }
+    let (left, right) = literals.split_at(literals.len() / 2);
+    let left = totalizer_node(solver, left, limit);
+    let right = totalizer_node(solver, right, limit);
+    let outputs = new_literals(solver, limit.min(left.len() + right.len()));
+    let mut clause = Vec::with_capacity(3);
+    for a in 0..=left.len() {
+        for b in 0..=right.len() {
+            if a + b == 0 {
+                continue
+            }
+            clause.clear();
+            clause.extend(a.checked_sub(1).map(|a| !left[a]));
+            clause.extend(b.checked_sub(1).map(|b| !right[b]));
+            clause.push(outputs[(a + b).min(outputs.len()) - 1]);
+            add_clause(solver, &clause);
+        }
+    }
+    outputs
+}
+
+/// A node of the modulo totalizer.
+///
+/// Represents the number of `true` literals in its subtree as
+/// `quotient * modulus + remainder` where both parts are counted in unary.
+struct ModuloNode {
+    /// The number of literals in the subtree.
+    len_literals: usize,
+    /// The `i`-th literal is `true` if the quotient is at least `i+1`.
+    quotient: Vec<Literal>,
+    /// The `i`-th literal is `true` if the remainder is at least `i+1`.
+    remainder: Vec<Literal>,
+}
+
+/// The clause literal for a unary counter being at least some value.
+enum Bound {
+    /// The bound is trivially satisfied.
+    True,
+    /// The bound can never be satisfied.
+    False,
+    /// The bound is represented by the literal.
+    Literal(Literal),
+}
+
+/// Encodes at-most-k using the modulo totalizer encoding.
+///
+/// In comparison to the totalizer this requires fewer clauses for large `k`
+/// since every node only counts up to `k / modulus` and `modulus` in unary.
+pub fn modulo_totalizer(solver: &mut Solver, literals: &[Literal], k: usize) {
+    let modulus = ((k + 1) as f64).sqrt().ceil().max(2.0) as usize;
+    let max_quotient = (k + 1).div_ceil(modulus);
+    let root = modulo_node(solver, literals, modulus, max_quotient);
+    // Forbid all combinations of quotient and remainder that exceed `k`.
+    for quotient in 0..=root.quotient.len() {
+        for remainder in 0..=root.remainder.len() {
+            if quotient + remainder == 0 || quotient * modulus + remainder <= k {
+                continue
+            }
+            let mut clause = Vec::with_capacity(2);
+            clause.extend(quotient.checked_sub(1).map(|q| !root.quotient[q]));
+            clause.extend(remainder.checked_sub(1).map(|r| !root.remainder[r]));
+            add_clause(solver, &clause);
+        }
+    }
+}
+
+/// Returns the modulo totalizer node over the literals.
+///
+/// Quotients greater than `max_quotient` are represented by the
+/// `max_quotient`-th quotient output.
+fn modulo_node(
+    solver: &mut Solver,
+    literals: &[Literal],
+    modulus: usize,
+    max_quotient: usize,
+) -> ModuloNode {
+    if literals.len() == 1 {
+        return ModuloNode {
+            len_literals: 1,
+            quotient: Vec::new(),
+            remainder: literals.to_vec(),
+        }
+    }
+    let (left, right) = literals.split_at(literals.len() / 2);
+    let left = modulo_node(solver, left, modulus, max_quotient);
+    let right = modulo_node(solver, right, modulus, max_quotient);
+    let len_literals = left.len_literals + right.len_literals;
+    let len_quotient = (len_literals / modulus).min(max_quotient);
+    let node = ModuloNode {
+        len_literals,
+        quotient: new_literals(solver, len_quotient),
+        remainder: new_literals(solver, (modulus - 1).min(len_literals)),
+    };
+    let quotient_bound = |quotient: usize| {
+        if quotient == 0 {
+            Bound::True
+        } else if quotient <= len_quotient {
+            Bound::Literal(node.quotient[quotient - 1])
+        } else if len_quotient == max_quotient {
+            Bound::Literal(node.quotient[len_quotient - 1])
+        } else {
+            Bound::False
+        }
+    };
+    let mut premise = Vec::with_capacity(4);
+    for i in 0..=left.quotient.len() {
+        for j in 0..=right.quotient.len() {
+            for a in 0..=left.remainder.len() {
+                for b in 0..=right.remainder.len() {
+                    if i + j + a + b == 0 {
+                        continue
+                    }
+                    premise.clear();
+                    premise.extend(i.checked_sub(1).map(|i| !left.quotient[i]));
+                    premise.extend(j.checked_sub(1).map(|j| !right.quotient[j]));
+                    premise.extend(a.checked_sub(1).map(|a| !left.remainder[a]));
+                    premise.extend(b.checked_sub(1).map(|b| !right.remainder[b]));
+                    let mut implies = |conclusion: &[Bound]| {
+                        let mut clause = premise.clone();
+                        for bound in conclusion {
+                            match *bound {
+                                Bound::True => return,
+                                Bound::False => (),
+                                Bound::Literal(literal) => clause.push(literal),
+                            }
+                        }
+                        add_clause(solver, &clause);
+                    };
+                    let remainder = a + b;
+                    if remainder < modulus {
+                        // Without a carry the quotients add up. With a carry
+                        // the remainder might be smaller but the quotient greater.
+                        implies(&[quotient_bound(i + j)]);
+                        if remainder > 0 {
+                            implies(&[
+                                Bound::Literal(node.remainder[remainder - 1]),
+                                quotient_bound(i + j + 1),
+                            ]);
+                        }
+                    } else {
+                        implies(&[quotient_bound(i + j + 1)]);
+                        if remainder > modulus {
+                            implies(&[Bound::Literal(
+                                node.remainder[remainder - modulus - 1],
+                            )]);
+                        }
+                    }
+                }
+            }
+        }
+    }
+    node
+}
+
+/// A wire of a sorting network which might be constantly `false`.
+type Wire = Option<Literal>;
+
+/// Sorts the two wires in descending order.
+fn comparator(solver: &mut Solver, a: Wire, b: Wire) -> (Wire, Wire) {
+    match (a, b) {
+        (None, wire) | (wire, None) => (wire, None),
+        (Some(a), Some(b)) => {
+            let max = solver.new_literal();
+            let min = solver.new_literal();
+            add_clause(solver, &[!a, max]);
+            add_clause(solver, &[!b, max]);
+            add_clause(solver, &[!a, !b, min]);
+            (Some(max), Some(min))
+        }
+    }
+}
+
+/// Returns the wires at odd and even positions, starting with the first.
+fn split_odd_even(wires: &[Wire]) -> (Vec<Wire>, Vec<Wire>) {
+    let odd = wires.iter().step_by(2).copied().collect();
+    let even = wires.iter().skip(1).step_by(2).copied().collect();
+    (odd, even)
+}
+
+/// Merges the two sorted sequences of the same power of two length.
+fn half_merge(solver: &mut Solver, a: &[Wire], b: &[Wire]) -> Vec<Wire> {
+    debug_assert_eq!(a.len(), b.len());
+    let len = a.len();
+    if len == 1 {
+        let (max, min) = comparator(solver, a[0], b[0]);
+        return vec![max, min]
+    }
+    let (a_odd, a_even) = split_odd_even(a);
+    let (b_odd, b_even) = split_odd_even(b);
+    let odd = half_merge(solver, &a_odd, &b_odd);
+    let even = half_merge(solver, &a_even, &b_even);
+    let mut outputs = Vec::with_capacity(2 * len);
+    outputs.push(odd[0]);
+    for i in 1..len {
+        let (max, min) = comparator(solver, odd[i], even[i - 1]);
+        outputs.push(max);
+        outputs.push(min);
+    }
+    outputs.push(even[len - 1]);
+    outputs
+}
+
+/// Sorts the wires of a power of two length.
+fn half_sort(solver: &mut Solver, wires: &[Wire]) -> Vec<Wire> {
+    if wires.len() == 1 {
+        return wires.to_vec()
+    }
+    let (left, right) = wires.split_at(wires.len() / 2);
+    let left = half_sort(solver, left);
+    let right = half_sort(solver, right);
+    half_merge(solver, &left, &right)
+}
+
+/// Merges the two sorted sequences of the same power of two length `n`
+/// and returns only the first `n+1` outputs.
+fn simplified_merge(solver: &mut Solver, a: &[Wire], b: &[Wire]) -> Vec<Wire> {
+    debug_assert_eq!(a.len(), b.len());
+    let len = a.len();
+    if len == 1 {
+        let (max, min) = comparator(solver, a[0], b[0]);
+        return vec![max, min]
+    }
+    let (a_odd, a_even) = split_odd_even(a);
+    let (b_odd, b_even) = split_odd_even(b);
+    let odd = simplified_merge(solver, &a_odd, &b_odd);
+    let even = simplified_merge(solver, &a_even, &b_even);
+    let mut outputs = Vec::with_capacity(len + 1);
+    outputs.push(odd[0]);
+    for i in 1..=len / 2 {
+        let (max, min) = comparator(solver, odd[i], even[i - 1]);
+        outputs.push(max);
+        outputs.push(min);
+    }
+    outputs
+}
+
+/// Returns the first `width` sorted outputs of the wires.
+///
+/// The number of wires must be a multiple of `width` which must be a power of two.
+fn card(solver: &mut Solver, wires: &[Wire], width: usize) -> Vec<Wire> {
+    if wires.len() == width {
+        return half_sort(solver, wires)
+    }
+    let (left, right) = wires.split_at(width);
+    let left = card(solver, left, width);
+    let right = card(solver, right, width);
+    let mut outputs = simplified_merge(solver, &left, &right);
+    outputs.truncate(width);
+    outputs
+}
+
+/// Encodes at-most-k using cardinality networks.
+///
+/// The inputs are padded with constant `false` wires to a multiple of the
+/// network width which is the smallest power of two greater than `k`.
+pub fn cardinality_network(solver: &mut Solver, literals: &[Literal], k: usize) {
+    let width = (k + 1).next_power_of_two();
+    let len_wires = literals.len().div_ceil(width) * width;
+    let mut wires = literals.iter().copied().map(Some).collect::<Vec<_>>();
+    wires.resize(len_wires, None);
+    let outputs = card(solver, &wires, width);
+    if let Some(exceeded) = outputs[k] {
+        add_clause(solver, &[!exceeded]);
+    }
+}
@@ -0,0 +1,240 @@
+//! Encodings of cardinality and pseudo-Boolean constraints into clauses.
+//!
+//! # Note
+//!
+//! - All encoders feed their clauses to the solver via [`Solver::consume_clause`]
+//!   and introduce auxiliary variables via [`Solver::new_literal_chunk`].
+//! - In contrast to the native constraints added via [`Solver::add_at_most`] or
+//!   [`Solver::add_pseudo_boolean`] the encoded constraints are propagated by
+//!   the clause database and profit from clause learning over auxiliary variables.
+
+mod at_most_one;
+mod cardinality;
+mod pseudo_boolean;
+
+#[cfg(test)]
+mod tests;
+
+use crate::{
+    Literal,
+    Solver,
+};
+
+/// The encoding used by [`at_most_one`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum AtMostOneEncoding {
+    /// Forbids every pair of literals without auxiliary variables.
+    ///
+    /// Requires a quadratic number of binary clauses.
+    Pairwise,
+    /// The ladder or sequential encoding using a chain of auxiliary variables.
+    Ladder,
+    /// The commander encoding that recursively forbids pairs within small
+    /// groups and groups with more than one commander being `true`.
+    Commander,
+    /// The product encoding that arranges the literals in a two-dimensional grid
+    /// and recursively constrains its rows and columns.
+    Product,
+}
+
+/// The encoding used by [`at_most_k`] and [`at_least_k`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum CardinalityEncoding {
+    /// The sequential counter encoding by Sinz.
+    SequentialCounter,
+    /// The totalizer encoding by Bailleux and Boufkhad.
+    Totalizer,
+    /// The modulo totalizer encoding by Ogawa et al. that counts in
+    /// a quotient and remainder representation.
+    ModuloTotalizer,
+    /// The cardinality networks by Asín et al. built from odd-even merges.
+    CardinalityNetwork,
+}
+
+/// The encoding used by [`pseudo_boolean_at_most`] and [`pseudo_boolean_at_least`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum PseudoBooleanEncoding {
+    /// Sums up the weighted literals in binary using adder networks
+    /// and compares the sum with the bound.
+    Adder,
+    /// Encodes the reduced ordered binary decision diagram of the constraint.
+    Bdd,
+    /// The generalized totalizer encoding by Joshi et al.
+    GeneralizedTotalizer,
+}
+
+/// Feeds the clause to the solver.
+pub(crate) fn add_clause(solver: &mut Solver, literals: &[Literal]) {
+    solver.consume_clause(literals.iter().copied());
+}
+
+/// Registers `amount` new literals at the solver and returns them.
+fn new_literals(solver: &mut Solver, amount: usize) -> Vec<Literal> {
+    solver.new_literal_chunk(amount).into_iter().collect()
+}
+
+/// Returns the unary outputs of a totalizer over the literals.
+///
+/// The `i`-th output is implied by at least `i+1` of the literals being `true`.
+///
+/// # Panics
+///
+/// If there are no literals.
+pub(crate) fn totalizer_outputs(
+    solver: &mut Solver,
+    literals: &[Literal],
+) -> Vec<Literal> {
+    cardinality::totalizer_node(solver, literals, literals.len())
+}
+
+/// Encodes that at most one of the literals is `true`.
+///
+/// # Panics
+///
+/// If any of the literals has not been registered.
+pub fn at_most_one(
+    solver: &mut Solver,
+    literals: &[Literal],
+    encoding: AtMostOneEncoding,
+) {
+    match encoding {
+        AtMostOneEncoding::Pairwise => at_most_one::pairwise(solver, literals),
+        AtMostOneEncoding::Ladder => at_most_one::ladder(solver, literals),
+        AtMostOneEncoding::Commander => at_most_one::commander(solver, literals),
+        AtMostOneEncoding::Product => at_most_one::product(solver, literals),
+    }
+}
+
+/// Encodes that at most `k` of the literals are `true`.
+///
+/// # Note
+///
+/// Duplicate literals are counted as many times as they occur.
+///
+/// # Panics
+///
+/// If any of the literals has not been registered.
+pub fn at_most_k(
+    solver: &mut Solver,
+    literals: &[Literal],
+    k: usize,
+    encoding: CardinalityEncoding,
+) {
+    if k >= literals.len() {
+        return
+    }
+    if k == 0 {
+        for &literal in literals {
+            solver.consume_clause([!literal]);
+        }
+        return
+    }
+    match encoding {
+        CardinalityEncoding::SequentialCounter => {
+            cardinality::sequential_counter(solver, literals, k)
+        }
+        CardinalityEncoding::Totalizer => cardinality::totalizer(solver, literals, k),
+        CardinalityEncoding::ModuloTotalizer => {
+            cardinality::modulo_totalizer(solver, literals, k)
+        }
+        CardinalityEncoding::CardinalityNetwork => {
+            cardinality::cardinality_network(solver, literals, k)
+        }
+    }
+}
+
+/// Encodes that at least `k` of the literals are `true`.
+///
+/// # Note
+///
+/// Duplicate literals are counted as many times as they occur.
+///
+/// # Panics
+///
+/// If any of the literals has not been registered.
+pub fn at_least_k(
+    solver: &mut Solver,
+    literals: &[Literal],
+    k: usize,
+    encoding: CardinalityEncoding,
+) {
+    if k > literals.len() {
+        solver.consume_clause([]);
+        return
+    }
+    let negated = literals.iter().map(|&literal| !literal).collect::<Vec<_>>();
+    at_most_k(solver, &negated, literals.len() - k, encoding)
+}
+
+/// Encodes `sum(a_i * l_i) <= bound` for the given terms `(a_i, l_i)`.
+///
+/// # Note
+///
+/// Duplicate literals are counted as many times as they occur.
+///
+/// # Panics
+///
+/// - If any of the literals has not been registered.
+/// - If the sum of all coefficients does not fit into an `u64`.
+pub fn pseudo_boolean_at_most(
+    solver: &mut Solver,
+    terms: &[(u64, Literal)],
+    bound: u64,
+    encoding: PseudoBooleanEncoding,
+) {
+    // Literals with coefficients greater than the bound must be `false`.
+    let mut normalized = Vec::with_capacity(terms.len());
+    for &(coefficient, literal) in terms {
+        if coefficient > bound {
+            solver.consume_clause([!literal]);
+        } else if coefficient > 0 {
+            normalized.push((coefficient, literal));
+        }
+    }
+    let sum = normalized
+        .iter()
+        .try_fold(0_u64, |sum, &(coefficient, _)| sum.checked_add(coefficient))
+        .expect("encountered overflowing pseudo-Boolean constraint coefficients");
+    if sum <= bound {
+        return
+    }
+    match encoding {
+        PseudoBooleanEncoding::Adder => pseudo_boolean::adder(solver, &normalized, bound),
+        PseudoBooleanEncoding::Bdd => pseudo_boolean::bdd(solver, &normalized, bound),
+        PseudoBooleanEncoding::GeneralizedTotalizer => {
+            pseudo_boolean::generalized_totalizer(solver, &normalized, bound)
+        }
+    }
+}
+
+/// Encodes `sum(a_i * l_i) >= degree` for the given terms `(a_i, l_i)`.
+///
+/// # Note
+///
+/// Duplicate literals are counted as many times as they occur.
+///
+/// # Panics
+///
+/// - If any of the literals has not been registered.
+/// - If the sum of all coefficients does not fit into an `u64`.
+pub fn pseudo_boolean_at_least(
+    solver: &mut Solver,
+    terms: &[(u64, Literal)],
+    degree: u64,
+    encoding: PseudoBooleanEncoding,
+) {
+    let sum = terms
+        .iter()
+        .try_fold(0_u64, |sum, &(coefficient, _)| sum.checked_add(coefficient))
+        .expect("encountered overflowing pseudo-Boolean constraint coefficients");
+    if degree > sum {
+        solver.consume_clause([]);
+        return
+    }
+    // `sum(a_i * l_i) >= degree` is equivalent to `sum(a_i * !l_i) <= sum - degree`.
+    let negated = terms
+        .iter()
+        .map(|&(coefficient, literal)| (coefficient, !literal))
+        .collect::<Vec<_>>();
+    pseudo_boolean_at_most(solver, &negated, sum - degree, encoding)
+}
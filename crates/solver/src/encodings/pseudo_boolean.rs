@@ -0,0 +1,267 @@
+//! Encodings of `sum(a_i * l_i) <= bound` for `0 < a_i <= bound < sum(a_i)`.
+
+use super::{
+    add_clause,
+    new_literals,
+};
+use crate::{
+    Literal,
+    Solver,
+};
+use ahash::AHashMap;
+use core::cmp::Reverse;
+use std::collections::BTreeMap;
+
+/// Returns the sum and carry outputs of a full adder over the three literals.
+///
+/// # Note
+///
+/// Both directions are encoded since the binary sum is not monotone in its bits.
+fn full_adder(
+    solver: &mut Solver,
+    a: Literal,
+    b: Literal,
+    c: Literal,
+) -> (Literal, Literal) {
+    let sum = solver.new_literal();
+    let carry = solver.new_literal();
+    for (x, y, z) in [(a, b, c), (a, !b, !c), (!a, b, !c), (!a, !b, c)] {
+        add_clause(solver, &[!x, !y, !z, sum]);
+        add_clause(solver, &[x, y, z, !sum]);
+    }
+    for (x, y) in [(a, b), (a, c), (b, c)] {
+        add_clause(solver, &[!x, !y, carry]);
+        add_clause(solver, &[x, y, !carry]);
+    }
+    (sum, carry)
+}
+
+/// Returns the sum and carry outputs of a half adder over the two literals.
+fn half_adder(solver: &mut Solver, a: Literal, b: Literal) -> (Literal, Literal) {
+    let sum = solver.new_literal();
+    let carry = solver.new_literal();
+    add_clause(solver, &[!a, b, sum]);
+    add_clause(solver, &[a, !b, sum]);
+    add_clause(solver, &[a, b, !sum]);
+    add_clause(solver, &[!a, !b, !sum]);
+    add_clause(solver, &[!a, !b, carry]);
+    add_clause(solver, &[a, !carry]);
+    add_clause(solver, &[b, !carry]);
+    (sum, carry)
+}
+
+/// Encodes the pseudo-Boolean constraint using adder networks.
+///
+/// Every literal is put into the buckets of the set bits of its coefficient.
+/// The buckets are then reduced to a single bit of the sum with full and half
+/// adders from the least significant bit upwards.
+pub fn adder(solver: &mut Solver, terms: &[(u64, Literal)], bound: u64) {
+    let mut buckets = vec![Vec::new(); u64::BITS as usize];
+    for &(coefficient, literal) in terms {
+        for (bit, bucket) in buckets.iter_mut().enumerate() {
+            if coefficient & (1 << bit) != 0 {
+                bucket.push(literal);
+            }
+        }
+    }
+    let mut sum = Vec::with_capacity(buckets.len());
+    for bit in 0..buckets.len() {
+        loop {
+            let bucket = &mut buckets[bit];
+            let (bit_sum, carry) = match bucket.len() {
+                0 | 1 => break,
+                2 => {
+                    let (a, b) = (bucket[0], bucket[1]);
+                    bucket.clear();
+                    half_adder(solver, a, b)
+                }
+                _ => {
+                    let c = bucket.pop().expect("encountered unexpected empty bucket");
+                    let b = bucket.pop().expect("encountered unexpected empty bucket");
+                    let a = bucket.pop().expect("encountered unexpected empty bucket");
+                    full_adder(solver, a, b, c)
+                }
+            };
+            buckets[bit].push(bit_sum);
+            buckets
+                .get_mut(bit + 1)
+                .expect("encountered overflowing adder network")
+                .push(carry);
+        }
+        sum.push(buckets[bit].first().copied());
+    }
+    // The sum exceeds the bound if for some bit the sum has a `1` while the bound
+    // has a `0` and all more significant `1` bits of the bound are set in the sum.
+    'bits: for (bit, &sum_bit) in sum.iter().enumerate() {
+        let sum_bit = match sum_bit {
+            Some(sum_bit) if bound & (1 << bit) == 0 => sum_bit,
+            _ => continue,
+        };
+        let mut clause = vec![!sum_bit];
+        for (higher, &higher_bit) in sum.iter().enumerate().skip(bit + 1) {
+            if bound & (1 << higher) != 0 {
+                match higher_bit {
+                    Some(higher_bit) => clause.push(!higher_bit),
+                    None => continue 'bits,
+                }
+            }
+        }
+        add_clause(solver, &clause);
+    }
+}
+
+/// A node of a binary decision diagram.
+#[derive(Debug, Copy, Clone)]
+enum Node {
+    True,
+    False,
+    Literal(Literal),
+}
+
+/// Encodes the pseudo-Boolean constraint using a binary decision diagram.
+///
+/// The node for the `i`-th term and remaining bound `r` represents
+/// `sum(a_j * l_j for j >= i) <= r`. Nodes are shared between all remaining
+/// bounds that lead to the same sub-diagram.
+pub fn bdd(solver: &mut Solver, terms: &[(u64, Literal)], bound: u64) {
+    let mut terms = terms.to_vec();
+    terms.sort_by_key(|&(coefficient, _)| Reverse(coefficient));
+    let mut suffix_sums = vec![0; terms.len() + 1];
+    for (i, &(coefficient, _)) in terms.iter().enumerate().rev() {
+        suffix_sums[i] = suffix_sums[i + 1] + i128::from(coefficient);
+    }
+    let mut builder = BddBuilder {
+        terms: &terms,
+        suffix_sums: &suffix_sums,
+        nodes: AHashMap::default(),
+    };
+    match builder.node(solver, 0, i128::from(bound)).0 {
+        Node::True => (),
+        Node::False => {
+            solver.consume_clause([]);
+        }
+        Node::Literal(root) => add_clause(solver, &[root]),
+    }
+}
+
+/// Builds the binary decision diagram of a pseudo-Boolean constraint.
+struct BddBuilder<'a> {
+    /// The terms sorted by decreasing coefficients.
+    terms: &'a [(u64, Literal)],
+    /// The sums of the coefficients of all terms starting at the index.
+    suffix_sums: &'a [i128],
+    /// The already built nodes per term index with the interval of remaining
+    /// bounds they represent.
+    nodes: AHashMap<usize, Vec<(i128, i128, Node)>>,
+}
+
+impl BddBuilder<'_> {
+    /// Returns the node for the `index`-th term and the remaining bound.
+    ///
+    /// Also returns the interval of remaining bounds for which the node is the same.
+    fn node(
+        &mut self,
+        solver: &mut Solver,
+        index: usize,
+        remaining: i128,
+    ) -> (Node, i128, i128) {
+        if remaining < 0 {
+            return (Node::False, i128::MIN, -1)
+        }
+        if self.suffix_sums[index] <= remaining {
+            return (Node::True, self.suffix_sums[index], i128::MAX)
+        }
+        if let Some(nodes) = self.nodes.get(&index) {
+            for &(min, max, node) in nodes {
+                if min <= remaining && remaining <= max {
+                    return (node, min, max)
+                }
+            }
+        }
+        let (coefficient, literal) = self.terms[index];
+        let coefficient = i128::from(coefficient);
+        let (high, high_min, high_max) =
+            self.node(solver, index + 1, remaining - coefficient);
+        let (low, low_min, low_max) = self.node(solver, index + 1, remaining);
+        // The node is the same for all bounds for which both children are the same.
+        let min = low_min.max(high_min.saturating_add(coefficient));
+        let max = low_max.min(high_max.saturating_add(coefficient));
+        let node = solver.new_literal();
+        match high {
+            Node::True => (),
+            Node::False => add_clause(solver, &[!node, !literal]),
+            Node::Literal(high) => add_clause(solver, &[!node, !literal, high]),
+        }
+        match low {
+            Node::True => (),
+            Node::False => add_clause(solver, &[!node]),
+            Node::Literal(low) => add_clause(solver, &[!node, low]),
+        }
+        let node = Node::Literal(node);
+        self.nodes.entry(index).or_default().push((min, max, node));
+        (node, min, max)
+    }
+}
+
+/// Encodes the pseudo-Boolean constraint using the generalized totalizer.
+///
+/// Every node of a binary tree over the terms has an output for every sum of
+/// coefficients its subtree can attain. Sums greater than the bound are
+/// represented by a single output for `bound + 1`.
+pub fn generalized_totalizer(solver: &mut Solver, terms: &[(u64, Literal)], bound: u64) {
+    let outputs = generalized_totalizer_node(solver, terms, bound + 1);
+    if let Some(&exceeded) = outputs.get(&(bound + 1)) {
+        add_clause(solver, &[!exceeded]);
+    }
+}
+
+/// Returns the outputs of the generalized totalizer node over the terms.
+///
+/// The output for a value is `true` if the weighted sum is at least that value.
+fn generalized_totalizer_node(
+    solver: &mut Solver,
+    terms: &[(u64, Literal)],
+    limit: u64,
+) -> BTreeMap<u64, Literal> {
+    if let &[(coefficient, literal)] = terms {
+        return [(coefficient.min(limit), literal)].into_iter().collect()
+    }
+    let (left, right) = terms.split_at(terms.len() / 2);
+    let left = generalized_totalizer_node(solver, left, limit);
+    let right = generalized_totalizer_node(solver, right, limit);
+    let with_zero = |outputs: &BTreeMap<u64, Literal>| {
+        core::iter::once((0, None))
+            .chain(
+                outputs
+                    .iter()
+                    .map(|(&value, &literal)| (value, Some(literal))),
+            )
+            .collect::<Vec<_>>()
+    };
+    let left = with_zero(&left);
+    let right = with_zero(&right);
+    let mut values = left
+        .iter()
+        .flat_map(|&(a, _)| right.iter().map(move |&(b, _)| (a + b).min(limit)))
+        .filter(|&value| value > 0)
+        .collect::<Vec<_>>();
+    values.sort_unstable();
+    values.dedup();
+    let literals = new_literals(solver, values.len());
+    let outputs = values.into_iter().zip(literals).collect::<BTreeMap<_, _>>();
+    let mut clause = Vec::with_capacity(3);
+    for &(a, left) in &left {
+        for &(b, right) in &right {
+            let value = (a + b).min(limit);
+            if value == 0 {
+                continue
+            }
+            clause.clear();
+            clause.extend(left.map(|left| !left));
+            clause.extend(right.map(|right| !right));
+            clause.push(outputs[&value]);
+            add_clause(solver, &clause);
+        }
+    }
+    outputs
+}
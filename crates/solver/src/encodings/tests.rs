@@ -0,0 +1,216 @@
+use super::*;
+use crate::tests::Lcg;
+
+const AT_MOST_ONE_ENCODINGS: [AtMostOneEncoding; 4] = [
+    AtMostOneEncoding::Pairwise,
+    AtMostOneEncoding::Ladder,
+    AtMostOneEncoding::Commander,
+    AtMostOneEncoding::Product,
+];
+
+const CARDINALITY_ENCODINGS: [CardinalityEncoding; 4] = [
+    CardinalityEncoding::SequentialCounter,
+    CardinalityEncoding::Totalizer,
+    CardinalityEncoding::ModuloTotalizer,
+    CardinalityEncoding::CardinalityNetwork,
+];
+
+const PSEUDO_BOOLEAN_ENCODINGS: [PseudoBooleanEncoding; 3] = [
+    PseudoBooleanEncoding::Adder,
+    PseudoBooleanEncoding::Bdd,
+    PseudoBooleanEncoding::GeneralizedTotalizer,
+];
+
+/// Returns the input literals for `len` fresh variables.
+///
+/// Every third input is negated to also cover negative polarities.
+fn inputs(solver: &mut Solver, len: usize) -> Vec<Literal> {
+    new_literals(solver, len)
+        .into_iter()
+        .enumerate()
+        .map(|(n, literal)| if n % 3 == 2 { !literal } else { literal })
+        .collect()
+}
+
+/// Returns the truth values of the inputs under the assignment of the bits.
+fn input_values(len: usize, assignment: u32) -> Vec<bool> {
+    (0..len).map(|n| assignment & (1 << n) != 0).collect()
+}
+
+/// Asserts that the encoded constraint over `len` inputs is satisfiable under
+/// every assignment of the inputs exactly if `expected` holds for it.
+fn assert_encodes<E, F>(len: usize, encode: E, expected: F)
+where
+    E: FnOnce(&mut Solver, &[Literal]),
+    F: Fn(&[bool]) -> bool,
+{
+    let mut solver = Solver::default();
+    let inputs = inputs(&mut solver, len);
+    encode(&mut solver, &inputs);
+    for assignment in 0..1_u32 << len {
+        let values = input_values(len, assignment);
+        let assumptions = inputs
+            .iter()
+            .zip(&values)
+            .map(|(&literal, &value)| if value { literal } else { !literal })
+            .collect::<Vec<_>>();
+        let result = solver.solve(assumptions).map(|result| result.is_sat());
+        assert_eq!(
+            result,
+            Ok(expected(&values)),
+            "unexpected result for assignment {:?}",
+            values,
+        );
+    }
+}
+
+/// Returns the number of `true` values.
+fn count_true(values: &[bool]) -> usize {
+    values.iter().filter(|&&value| value).count()
+}
+
+#[test]
+fn at_most_one_works() {
+    for encoding in AT_MOST_ONE_ENCODINGS {
+        for len in 0..=9 {
+            assert_encodes(
+                len,
+                |solver, inputs| at_most_one(solver, inputs, encoding),
+                |values| count_true(values) <= 1,
+            );
+        }
+    }
+}
+
+#[test]
+fn at_most_k_works() {
+    for encoding in CARDINALITY_ENCODINGS {
+        for len in 0..=7 {
+            for k in 0..=len + 1 {
+                assert_encodes(
+                    len,
+                    |solver, inputs| at_most_k(solver, inputs, k, encoding),
+                    |values| count_true(values) <= k,
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn at_least_k_works() {
+    for encoding in CARDINALITY_ENCODINGS {
+        for len in 0..=6 {
+            for k in 0..=len + 1 {
+                assert_encodes(
+                    len,
+                    |solver, inputs| at_least_k(solver, inputs, k, encoding),
+                    |values| count_true(values) >= k,
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn cardinality_with_complementary_inputs_works() {
+    for encoding in CARDINALITY_ENCODINGS {
+        for k in 0..=4 {
+            assert_encodes(
+                3,
+                |solver, inputs| {
+                    let inputs = [inputs[0], !inputs[0], inputs[1], inputs[2]];
+                    at_most_k(solver, &inputs, k, encoding)
+                },
+                // Exactly one of the complementary inputs is always `true`.
+                |values| count_true(&values[1..]) < k,
+            );
+        }
+    }
+}
+
+/// Returns the weighted sum of the `true` values.
+fn weighted_sum(coefficients: &[u64], values: &[bool]) -> u64 {
+    coefficients
+        .iter()
+        .zip(values)
+        .filter(|(_, &value)| value)
+        .map(|(&coefficient, _)| coefficient)
+        .sum()
+}
+
+/// Returns random coefficient vectors of up to 6 terms with small coefficients.
+fn random_coefficients() -> Vec<Vec<u64>> {
+    let mut rng = Lcg(42);
+    (1..=6)
+        .flat_map(|len| (0..8).map(move |_| len))
+        .map(|len| (0..len).map(|_| 1 + rng.next(7) as u64).collect())
+        .collect()
+}
+
+#[test]
+fn pseudo_boolean_at_most_works() {
+    for encoding in PSEUDO_BOOLEAN_ENCODINGS {
+        for coefficients in random_coefficients() {
+            let sum = coefficients.iter().sum::<u64>();
+            for bound in 0..=sum {
+                assert_encodes(
+                    coefficients.len(),
+                    |solver, inputs| {
+                        let terms = coefficients
+                            .iter()
+                            .copied()
+                            .zip(inputs.iter().copied())
+                            .collect::<Vec<_>>();
+                        pseudo_boolean_at_most(solver, &terms, bound, encoding)
+                    },
+                    |values| weighted_sum(&coefficients, values) <= bound,
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn pseudo_boolean_at_least_works() {
+    for encoding in PSEUDO_BOOLEAN_ENCODINGS {
+        for coefficients in random_coefficients().into_iter().step_by(3) {
+            let sum = coefficients.iter().sum::<u64>();
+            for degree in 0..=sum + 1 {
+                assert_encodes(
+                    coefficients.len(),
+                    |solver, inputs| {
+                        let terms = coefficients
+                            .iter()
+                            .copied()
+                            .zip(inputs.iter().copied())
+                            .collect::<Vec<_>>();
+                        pseudo_boolean_at_least(solver, &terms, degree, encoding)
+                    },
+                    |values| weighted_sum(&coefficients, values) >= degree,
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn pseudo_boolean_with_large_coefficients_works() {
+    let coefficients = [1 << 40, 3 << 39, 1 << 38, 5, 1];
+    for encoding in PSEUDO_BOOLEAN_ENCODINGS {
+        for bound in [0, 5, 6, 1 << 40, (1 << 40) + 6, (3 << 39) + (1 << 38)] {
+            assert_encodes(
+                coefficients.len(),
+                |solver, inputs| {
+                    let terms = coefficients
+                        .iter()
+                        .copied()
+                        .zip(inputs.iter().copied())
+                        .collect::<Vec<_>>();
+                    pseudo_boolean_at_most(solver, &terms, bound, encoding)
+                },
+                |values| weighted_sum(&coefficients, values) <= bound,
+            );
+        }
+    }
+}
@@ -0,0 +1,149 @@
+use crate::{
+    retract,
+    ClauseHandle,
+    Literal,
+    Model,
+    Sign,
+    SolveResult,
+    Solver,
+    Variable,
+};
+use bounded::Index as _;
+
+/// Iterator over the models of a solver created by [`Solver::enumerate_models`].
+///
+/// # Note
+///
+/// Every found model is blocked by a clause over the projection variables that
+/// is guarded by an activation literal. The activation literal is assumed during
+/// enumeration. The blocking clauses are removed and the activation variable is
+/// released once the iterator is dropped.
+///
+/// The yielded models assign neither released variables nor the activation
+/// variables of open scopes or of the enumeration itself.
+#[derive(Debug)]
+pub struct ModelEnumerator<'a> {
+    /// The solver whose models are enumerated.
+    solver: &'a mut Solver,
+    /// The variables onto which the models are projected.
+    projection: Vec<Variable>,
+    /// The literal that activates the blocking clauses.
+    activation: Literal,
+    /// The released variables and activation variables that are removed from the models.
+    internal: Vec<Variable>,
+    /// The handles of the blocking clauses.
+    blocking: Vec<ClauseHandle>,
+    /// The number of variables before the activation literal was allocated.
+    len_variables: usize,
+    /// The number of models that may still be yielded if limited.
+    remaining: Option<usize>,
+    /// Yields `true` if all models have been enumerated.
+    is_exhausted: bool,
+}
+
+impl<'a> ModelEnumerator<'a> {
+    /// Creates a new model enumerator for the solver.
+    ///
+    /// # Panics
+    ///
+    /// If any of the projection variables has not been registered.
+    pub(crate) fn new(
+        solver: &'a mut Solver,
+        projection: Option<&[Variable]>,
+        limit: Option<usize>,
+    ) -> Self {
+        let len_variables = solver.len_variables();
+        let projection = match projection {
+            Some(projection) => {
+                assert!(
+                    projection
+                        .iter()
+                        .all(|variable| variable.into_index() < len_variables),
+                    "encountered unregistered projection variable"
+                );
+                projection.to_vec()
+            }
+            None => {
+                (0..len_variables)
+                    .map(Variable::from_index)
+                    .filter(|&variable| !solver.is_internal(variable))
+                    .collect()
+            }
+        };
+        let activation = solver.new_literal();
+        let internal = (0..len_variables)
+            .map(Variable::from_index)
+            .filter(|&variable| {
+                variable == activation.variable() || solver.is_internal(variable)
+            })
+            .collect();
+        Self {
+            solver,
+            projection,
+            activation,
+            internal,
+            blocking: Vec::new(),
+            len_variables,
+            remaining: limit,
+            is_exhausted: false,
+        }
+    }
+
+    /// Adds the clause that blocks the projection of the model.
+    fn block(&mut self, model: &Model) {
+        let blocking = self.projection.iter().map(|&variable| {
+            let literal = Literal::new(variable, Sign::POS);
+            match model.is_satisfied(literal) {
+                Ok(true) => !literal,
+                Ok(false) => literal,
+                Err(_) => panic!("encountered unassigned projection variable"),
+            }
+        });
+        let clause = core::iter::once(!self.activation)
+            .chain(blocking)
+            .collect::<Vec<_>>();
+        // The blocking clauses are not scoped by the open scopes of the solver.
+        let handle = self.solver.consume_unscoped_clause(clause);
+        self.blocking.push(handle);
+    }
+}
+
+impl<'a> Iterator for ModelEnumerator<'a> {
+    type Item = Model;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.is_exhausted || self.remaining == Some(0) {
+            return None
+        }
+        let result = self
+            .solver
+            .solve([self.activation])
+            .expect("encountered unexpected error during solving");
+        let mut model = match result {
+            SolveResult::Sat(sat) => sat.model().clone(),
+            SolveResult::Unsat => {
+                self.is_exhausted = true;
+                return None
+            }
+            SolveResult::Unknown => panic!("encountered unexpected interrupted search"),
+        };
+        model.truncate(self.len_variables);
+        if let Some(remaining) = &mut self.remaining {
+            *remaining -= 1;
+        }
+        if self.remaining != Some(0) {
+            self.block(&model);
+        }
+        for &variable in &self.internal {
+            model.remove(variable);
+        }
+        Some(model)
+    }
+}
+
+impl<'a> Drop for ModelEnumerator<'a> {
+    fn drop(&mut self) {
+        let blocking = core::mem::take(&mut self.blocking);
+        retract::retract_guarded(self.solver, blocking, self.activation);
+    }
+}
@@ -0,0 +1,71 @@
+use crate::{
+    ClauseDatabase,
+    Solver,
+};
+
+/// Decides when the garbage of the clause database is collected.
+///
+/// # Note
+///
+/// By default the garbage is collected once the deleted clauses occupy
+/// more than a fifth of all clause words.
+#[derive(Debug, Copy, Clone)]
+pub struct GcPolicy {
+    /// The fraction of freed clause words that triggers a garbage collection.
+    fraction: f64,
+}
+
+impl Default for GcPolicy {
+    fn default() -> Self {
+        Self::new(0.2)
+    }
+}
+
+impl GcPolicy {
+    /// Creates a new garbage collection policy with the given fraction.
+    ///
+    /// # Panics
+    ///
+    /// If the fraction is not within `0.0..=1.0`.
+    pub fn new(fraction: f64) -> Self {
+        assert!(
+            (0.0..=1.0).contains(&fraction),
+            "encountered invalid garbage collection fraction: {}",
+            fraction
+        );
+        Self { fraction }
+    }
+
+    /// Returns `true` if the freed clause words exceed the fraction of all clause words.
+    pub fn is_due(&self, clause_db: &ClauseDatabase) -> bool {
+        clause_db.len_freed_words() as f64 > self.fraction * clause_db.len_words() as f64
+    }
+}
+
+/// Sweeps the deleted clauses from the clause database.
+///
+/// # Note
+///
+/// The watchers, the reasons of current assignments, the learned clauses
+/// and the consumed clauses of the solver refer to the moved clauses afterwards.
+/// Deleted clauses must not be the reason for a current assignment.
+pub(crate) fn collect_garbage(solver: &mut Solver) {
+    // The references of deleted clauses are reused by moved clauses.
+    solver.assignment.remove_deleted_watchers(&solver.clauses);
+    let mut moved = Vec::new();
+    solver.clauses.gc(|from, into| moved.push((from, into)));
+    // Moved clauses are reported in the order of their old references.
+    let relocate = |cref| {
+        match moved.binary_search_by_key(&cref, |&(from, _)| from) {
+            Ok(index) => moved[index].1,
+            Err(_) => cref,
+        }
+    };
+    solver.assignment.relocate_clauses(relocate);
+    solver.consumed.relocate(relocate);
+    solver.learnt_clauses.clear();
+    solver
+        .learnt_clauses
+        .extend(solver.clauses.live_clauses().learnt().map(|(cref, _)| cref));
+    solver.stats.gc_runs += 1;
+}
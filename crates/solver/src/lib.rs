@@ -2,35 +2,81 @@
 #![warn(unsafe_op_in_unsafe_fn)]
 #![allow(clippy::len_without_is_empty)]
 
+mod analyzer;
 mod assignment;
+mod backbone;
 mod builder;
+pub mod circuit;
 pub mod clause_db;
+mod constraint_db;
+pub mod count;
+pub mod cube;
 mod decider;
+mod dimacs;
+pub mod encodings;
+mod enumerate;
+mod gc;
+mod lifecycle;
 mod literal;
 mod literal_chunk;
+pub mod maxsat;
+pub mod mcs;
+pub mod mus;
+mod observer;
+mod opb;
+pub mod parallel;
+mod restart;
+mod retract;
 mod sanitizer;
+mod scope;
+mod sharing;
+mod stats;
 
 #[cfg(test)]
 mod tests;
 
 use crate::{
+    analyzer::ConflictAnalyzer,
     assignment::{
         Assignment,
         AssignmentError,
+        DecisionLevel,
         LastModel,
-        Model,
         PropagationResult,
+        Reason,
     },
     builder::SolverBuilder,
-    clause_db::ClauseDatabase,
+    clause_db::{
+        ClauseDatabase,
+        ClauseHeader,
+        ClauseRef,
+        ClauseRemoval,
+    },
+    constraint_db::ConstraintDatabase,
     decider::Decider,
+    gc::GcPolicy,
+    lifecycle::VariableLifecycle,
     literal::RegisterVariables,
+    observer::Observer,
+    opb::OpbParser,
+    restart::RestartPolicy,
+    retract::ConsumedClauses,
     sanitizer::{
         ClauseSanitizer,
         SanitizedLiterals,
     },
+    scope::Scopes,
+    sharing::ClauseSharing,
 };
 pub use crate::{
+    assignment::Model,
+    decider::{
+        DecisionHeuristic,
+        Phase,
+    },
+    dimacs::DimacsOptions,
+    enumerate::ModelEnumerator,
+    lifecycle::ReleaseError,
     literal::{
         Literal,
         Sign,
@@ -40,6 +86,17 @@ pub use crate::{
         LiteralChunk,
         LiteralChunkIter,
     },
+    observer::{
+        JsonTrace,
+        SolverObserver,
+    },
+    opb::{
+        Objective,
+        OpbError,
+    },
+    restart::RestartStrategy,
+    retract::ClauseHandle,
+    stats::Statistics,
 };
 use bounded::{
     Bool,
@@ -50,9 +107,22 @@ use cnf_parser::{
     Input,
 };
 use core::{
+    cmp::Reverse,
     fmt,
     fmt::Display,
 };
+use std::{
+    io,
+    io::Write,
+    sync::{
+        atomic::{
+            AtomicBool,
+            Ordering,
+        },
+        Arc,
+    },
+    time::Instant,
+};
 
 #[derive(Debug, PartialEq, Eq)]
 pub enum Error {
@@ -86,20 +156,36 @@ impl From<&'static str> for Error {
     }
 }
 
-/// The result of a decision followed by propagation.
-#[derive(Debug, PartialEq, Eq, Clone, Copy)]
-enum DecisionResult {
-    /// The decision caused a conflict.
-    Conflict,
-    /// The decision resulted in a satisfying assignment.
-    Sat,
+impl Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Other(message) => write!(f, "{}", message),
+            Self::Assignment(error) => write!(f, "{}", error),
+            Self::Bounded(_) => write!(f, "encountered out of bounds access"),
+            Self::Conflict => write!(f, "encountered unexpected conflict"),
+            Self::InvalidLiteralChunk => write!(f, "encountered invalid literal chunk"),
+            Self::TooManyVariablesInUse => write!(f, "too many variables in use"),
+            Self::InvalidDecisionId => write!(f, "encountered invalid decision level"),
+            Self::InvalidDecisionStart => {
+                write!(f, "encountered invalid start of decision level")
+            }
+            Self::InvalidDecisionEnd => {
+                write!(f, "encountered invalid end of decision level")
+            }
+            Self::InvalidSizeIncrement => write!(f, "encountered invalid size increment"),
+        }
+    }
 }
 
-impl DecisionResult {
-    /// Returns `true` if the decision result yielded a satisfying assignment.
-    pub fn is_sat(&self) -> bool {
-        matches!(self, Self::Sat)
-    }
+/// The result of the search for a satisfying assignment.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+enum SearchResult {
+    /// The search found a satisfying assignment.
+    Sat,
+    /// The search proved that no satisfying assignment exists.
+    Unsat,
+    /// The search was interrupted by a resource limit.
+    Unknown,
 }
 
 /// The satisfiable or unsatisfiable solution to a SAT instance.
@@ -113,6 +199,9 @@ pub enum SolveResult<'a> {
     Unsat,
     /// The SAT instance is satisfiable with the given satisfying assignment.
     Sat(SatResult<'a>),
+    /// The search was interrupted by a resource limit before the satisfiability
+    /// of the SAT instance was determined.
+    Unknown,
 }
 
 impl<'a> SolveResult<'a> {
@@ -127,7 +216,12 @@ impl<'a> SolveResult<'a> {
 
     /// Returns `true` if the SAT instance was determined to be unsatisfiable.
     pub fn is_unsat(&self) -> bool {
-        !self.is_sat()
+        matches!(self, SolveResult::Unsat)
+    }
+
+    /// Returns `true` if the search was interrupted by a resource limit.
+    pub fn is_unknown(&self) -> bool {
+        matches!(self, SolveResult::Unknown)
     }
 }
 
@@ -151,6 +245,45 @@ impl<'a> SatResult<'a> {
     }
 }
 
+/// Returns the greatest common divisor of `a` and `b`.
+fn gcd(mut a: i128, mut b: i128) -> i128 {
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+/// A handle to interrupt the search of a solver from another thread.
+///
+/// # Note
+///
+/// Clones of a solver share the same handle.
+#[derive(Debug, Default, Clone)]
+pub struct Interrupter {
+    /// Yields `true` if the search shall be interrupted.
+    is_interrupted: Arc<AtomicBool>,
+}
+
+impl Interrupter {
+    /// Interrupts the current and all following calls to `solve` upon their next
+    /// conflict until the handle is reset.
+    ///
+    /// Interrupted calls to `solve` return [`SolveResult::Unknown`].
+    pub fn interrupt(&self) {
+        self.is_interrupted.store(true, Ordering::Relaxed);
+    }
+
+    /// Allows calls to `solve` to run to completion again.
+    pub fn reset(&self) {
+        self.is_interrupted.store(false, Ordering::Relaxed);
+    }
+
+    /// Returns `true` if calls to `solve` are interrupted.
+    pub fn is_interrupted(&self) -> bool {
+        self.is_interrupted.load(Ordering::Relaxed)
+    }
+}
+
 /// The solver instance.
 #[derive(Debug, Default, Clone)]
 pub struct Solver {
@@ -158,10 +291,22 @@ pub struct Solver {
     len_variables: usize,
     /// The clause database that stores all information about clauses.
     clauses: ClauseDatabase,
+    /// The constraint database that stores native cardinality and pseudo-Boolean constraints.
+    constraints: ConstraintDatabase,
     /// The partial assignment of variables.
     assignment: Assignment,
     /// The decision heuristic.
     decider: Decider,
+    /// Analyzes conflicts and derives learned clauses.
+    analyzer: ConflictAnalyzer,
+    /// Decides when to restart the search.
+    restarts: RestartPolicy,
+    /// Decides when to collect the garbage of the clause database.
+    gc: GcPolicy,
+    /// The learned clauses stored in the clause database.
+    learnt_clauses: Vec<ClauseRef>,
+    /// The number of learned clauses that triggers their reduction upon the next restart.
+    max_learnt_clauses: usize,
     /// The last full assignment found by the solver upon SAT.
     last_model: LastModel,
     /// Sanitizes clauses before being fed to the solver.
@@ -172,20 +317,61 @@ pub struct Solver {
     ///
     /// They are immediately propagated when calling `solve`.
     hard_facts: Vec<Literal>,
+    /// The clauses that have been fed to `consume_clause` indexed by their handles.
+    consumed: ConsumedClauses,
+    /// Yields `true` if `remove_clause` removed clauses since the learned clauses
+    /// have last been forgotten.
+    ///
+    /// The learned clauses are forgotten upon the next call to `solve`.
+    has_removed_clauses: bool,
+    /// The scopes opened by `push` whose activation literals are assumed by `solve`.
+    scopes: Scopes,
+    /// The frozen and released variables and the occurrences of all variables.
+    lifecycle: VariableLifecycle,
+    /// The assumptions of the current `solve` invocation.
+    assumptions: Vec<Literal>,
+    /// The assumptions responsible for the last `solve` invocation being unsatisfiable.
+    failed_assumptions: Vec<Literal>,
+    /// The maximum number of conflicts of a single `solve` invocation if any.
+    conflict_limit: Option<u64>,
+    /// The number of conflicts of the current `solve` invocation.
+    conflicts: u64,
+    /// The instant after which `solve` invocations are interrupted if any.
+    deadline: Option<Instant>,
+    /// The handle to interrupt `solve` invocations from other threads.
+    interrupter: Interrupter,
+    /// The observer of the search if any.
+    observer: Observer,
+    /// The callbacks to export learned clauses and import external clauses.
+    sharing: ClauseSharing,
+    /// The statistics about the work of the solver.
+    ///
+    /// # Note
+    ///
+    /// The number of propagations is kept by the assignment.
+    stats: Statistics,
 }
 
 impl RegisterVariables for Solver {
     fn register_variables(&mut self, additional: usize) {
+        self.constraints.register_variables(additional);
         self.assignment.register_variables(additional);
         self.decider.register_variables(additional);
+        self.analyzer.register_variables(additional);
         self.sanitizer.register_variables(additional);
+        self.lifecycle.register_variables(additional);
         self.len_variables += additional;
     }
+
+    fn recycle_variable(&mut self, variable: Variable) {
+        self.assignment.recycle_variable(variable);
+        self.decider.recycle_variable(variable);
+    }
 }
 
 impl Solver {
     /// Returns the number of currently registered variables.
-    fn len_variables(&self) -> usize {
+    pub fn len_variables(&self) -> usize {
         self.len_variables
     }
 
@@ -204,12 +390,51 @@ impl Solver {
         Ok(builder.finalize())
     }
 
+    /// Consumes the `.opb` input and feeds it to the returned solver.
+    ///
+    /// Also returns the objective function to be minimized if the input has one.
+    ///
+    /// # Errors
+    ///
+    /// If the input is no valid `.opb` format.
+    pub fn from_opb(input: &[u8]) -> Result<(Self, Option<Objective>), OpbError> {
+        let mut solver = Self::default();
+        let objective = OpbParser::new(input).parse_into(&mut solver)?;
+        Ok((solver, objective))
+    }
+
     /// Consumes the given clause.
     ///
+    /// Returns a handle that allows to remove the clause via [`Solver::remove_clause`].
+    ///
+    /// # Note
+    ///
+    /// Within a scope opened by [`Solver::push`] the clause is guarded by the
+    /// activation literal of the scope and removed by the matching [`Solver::pop`].
+    ///
     /// # Panics
     ///
     /// If any of the literals has not been registered.
-    pub fn consume_clause<I>(&mut self, literals: I)
+    pub fn consume_clause<I>(&mut self, literals: I) -> ClauseHandle
+    where
+        I: IntoIterator,
+        I::IntoIter: ExactSizeIterator<Item = Literal>,
+    {
+        let activation = match self.scopes.activation() {
+            Some(activation) => activation,
+            None => return self.consume_unscoped_clause(literals),
+        };
+        let guarded = literals
+            .into_iter()
+            .chain([!activation])
+            .collect::<Vec<_>>();
+        let handle = self.consume_unscoped_clause(guarded);
+        self.scopes.record(handle);
+        handle
+    }
+
+    /// Consumes the given clause regardless of the open scopes.
+    fn consume_unscoped_clause<I>(&mut self, literals: I) -> ClauseHandle
     where
         I: IntoIterator,
         I::IntoIter: ExactSizeIterator<Item = Literal>,
@@ -225,13 +450,18 @@ impl Solver {
                     let variable = literal.variable();
                     self.decider.bump_priority_by(variable, 1);
                 }
+                self.lifecycle.add_occurrences(resolved.literals());
+                self.consumed.push_stored(cref)
             }
             SanitizedLiterals::UnitClause(unit) => {
+                self.lifecycle.add_occurrences([&unit]);
                 self.hard_facts.push(unit);
+                self.consumed.push_unit(unit)
             }
-            SanitizedLiterals::TautologicalClause => (),
+            SanitizedLiterals::TautologicalClause => self.consumed.push_tautological(),
             SanitizedLiterals::EmptyClause => {
                 self.encountered_empty_clause = true;
+                self.consumed.push_empty()
             }
             SanitizedLiterals::InvalidLiteral(invalid_literal) => {
                 panic!("encountered invalid literal: {}", invalid_literal)
@@ -239,8 +469,195 @@ impl Solver {
         }
     }
 
+    /// Returns `true` if the variable is released or the activation variable of an open scope.
+    fn is_internal(&self, variable: Variable) -> bool {
+        self.lifecycle.is_released(variable)
+            || self
+                .scopes
+                .activations()
+                .any(|activation| activation.variable() == variable)
+    }
+
+    /// Adds a pseudo-Boolean constraint requiring `sum(a_i * l_i) >= degree`.
+    ///
+    /// The terms are pairs of integer coefficients `a_i` and literals `l_i`.
+    ///
+    /// # Note
+    ///
+    /// - The constraint is normalized and stored natively without being
+    ///   encoded into clauses.
+    /// - Negative coefficients as well as duplicate and complementary literals
+    ///   are allowed.
+    /// - Constraints that are trivially satisfied, unsatisfiable or equivalent
+    ///   to a single clause are handled without the constraint database.
+    ///   Literals that are implied by the constraint alone become unit clauses.
+    /// - The constraint is not scoped by [`Solver::push`].
+    ///
+    /// # Panics
+    ///
+    /// - If any of the literals has not been registered.
+    /// - If the normalized coefficients do not fit into an `i64`.
+    pub fn add_pseudo_boolean<I>(&mut self, terms: I, degree: i64)
+    where
+        I: IntoIterator<Item = (i64, Literal)>,
+    {
+        let mut degree = i128::from(degree);
+        // Make all coefficients positive using `-a*l = a*(!l) - a`.
+        let mut terms = terms
+            .into_iter()
+            .filter(|&(coefficient, _)| coefficient != 0)
+            .map(|(coefficient, literal)| {
+                if literal.variable().into_index() >= self.len_variables() {
+                    panic!("encountered invalid literal: {}", literal)
+                }
+                let coefficient = i128::from(coefficient);
+                if coefficient < 0 {
+                    degree -= coefficient;
+                    (-coefficient, !literal)
+                } else {
+                    (coefficient, literal)
+                }
+            })
+            .collect::<Vec<_>>();
+        // Merge duplicate literals and cancel out complementary literals
+        // using `a*l + b*(!l) = (a-b)*l + b`.
+        terms.sort_unstable_by_key(|&(_, literal)| literal);
+        let mut normalized = Vec::<(i128, Literal)>::with_capacity(terms.len());
+        for (coefficient, literal) in terms {
+            match normalized.last_mut() {
+                Some((last_coefficient, last_literal)) if *last_literal == literal => {
+                    *last_coefficient += coefficient;
+                }
+                Some((last_coefficient, last_literal))
+                    if last_literal.variable() == literal.variable() =>
+                {
+                    let common = (*last_coefficient).min(coefficient);
+                    degree -= common;
+                    *last_coefficient -= common;
+                    if *last_coefficient == 0 {
+                        *last_coefficient = coefficient - common;
+                        *last_literal = literal;
+                    }
+                }
+                _ => normalized.push((coefficient, literal)),
+            }
+        }
+        normalized.retain(|&(coefficient, _)| coefficient != 0);
+        loop {
+            if degree <= 0 {
+                // The constraint is trivially satisfied.
+                return
+            }
+            for (coefficient, _) in &mut normalized {
+                *coefficient = (*coefficient).min(degree);
+            }
+            let divisor = normalized
+                .iter()
+                .fold(0, |divisor, &(coefficient, _)| gcd(divisor, coefficient));
+            if divisor > 1 {
+                for (coefficient, _) in &mut normalized {
+                    *coefficient /= divisor;
+                }
+                degree = (degree + divisor - 1) / divisor;
+            }
+            let sum = normalized
+                .iter()
+                .map(|&(coefficient, _)| coefficient)
+                .sum::<i128>();
+            let slack = sum - degree;
+            if slack < 0 {
+                self.consume_unscoped_clause([]);
+                return
+            }
+            // Literals with a coefficient greater than the slack are implied.
+            let len_terms = normalized.len();
+            normalized.retain(|&(coefficient, literal)| {
+                if coefficient <= slack {
+                    return true
+                }
+                degree -= coefficient;
+                self.consume_unscoped_clause([literal]);
+                false
+            });
+            if normalized.len() == len_terms {
+                break
+            }
+        }
+        if normalized
+            .iter()
+            .all(|&(coefficient, _)| coefficient >= degree)
+        {
+            // Every single literal satisfies the constraint on its own.
+            self.consume_unscoped_clause(
+                normalized.into_iter().map(|(_, literal)| literal),
+            );
+            return
+        }
+        let into_u64 = |value: i128| {
+            u64::try_from(value)
+                .expect("encountered overflowing pseudo-Boolean constraint coefficient")
+        };
+        for (_, literal) in &normalized {
+            self.decider.bump_priority_by(literal.variable(), 1);
+        }
+        self.lifecycle
+            .add_occurrences(normalized.iter().map(|(_, literal)| literal));
+        self.constraints.push_pseudo_boolean(
+            normalized
+                .into_iter()
+                .map(|(coefficient, literal)| (into_u64(coefficient), literal)),
+            into_u64(degree),
+        );
+    }
+
+    /// Adds a constraint requiring at least `min_true` of the literals to be `true`.
+    ///
+    /// # Note
+    ///
+    /// - The constraint is stored natively and not encoded into clauses.
+    /// - Duplicate literals are counted as many times as they occur.
+    ///
+    /// # Panics
+    ///
+    /// If any of the literals has not been registered.
+    pub fn add_at_least<I>(&mut self, literals: I, min_true: usize)
+    where
+        I: IntoIterator<Item = Literal>,
+    {
+        self.add_pseudo_boolean(
+            literals.into_iter().map(|literal| (1, literal)),
+            i64::try_from(min_true).unwrap_or(i64::MAX),
+        )
+    }
+
+    /// Adds a constraint requiring at most `max_true` of the literals to be `true`.
+    ///
+    /// # Note
+    ///
+    /// - The constraint is stored natively and not encoded into clauses.
+    /// - Duplicate literals are counted as many times as they occur.
+    ///
+    /// # Panics
+    ///
+    /// If any of the literals has not been registered.
+    pub fn add_at_most<I>(&mut self, literals: I, max_true: usize)
+    where
+        I: IntoIterator<Item = Literal>,
+    {
+        self.add_pseudo_boolean(
+            literals.into_iter().map(|literal| (-1, literal)),
+            i64::try_from(max_true).map_or(i64::MIN, |max_true| -max_true),
+        )
+    }
+
     /// Returns the next variable.
+    ///
+    /// Reuses the slot of a released variable if any.
     fn new_variable(&mut self) -> Variable {
+        if let Some(variable) = self.lifecycle.reuse() {
+            self.recycle_variable(variable);
+            return variable
+        }
         let next_id = self.len_variables();
         self.register_variables(1);
         Variable::from_index(next_id)
@@ -250,7 +667,9 @@ impl Solver {
     ///
     /// # Note
     ///
-    /// The returned literal has positive polarity.
+    /// - The returned literal has positive polarity.
+    /// - The returned literal may reuse the variable of a literal released via
+    ///   [`Solver::release_variable`].
     ///
     /// # Panics
     ///
@@ -265,6 +684,8 @@ impl Solver {
     ///
     /// - The returned literals have positive polarity.
     /// - The returned literal chunk acts as an efficient iterator over the new literals.
+    /// - The returned literals are always newly registered since the variables of a
+    ///   literal chunk are contiguous. Released variables are not reused.
     ///
     /// # Panics
     ///
@@ -283,101 +704,766 @@ impl Solver {
     }
 
     /// Starts solving the given SAT instance.
-    pub fn solve<L>(&mut self, assumptions: L) -> Result<SolveResult, Error>
+    ///
+    /// # Note
+    ///
+    /// The solver can be used incrementally: clauses and constraints may be
+    /// added in between calls to `solve` and each call may use different
+    /// assumptions. Clauses learned in previous calls are kept.
+    pub fn solve<L>(&mut self, assumptions: L) -> Result<SolveResult<'_>, Error>
     where
         L: IntoIterator<Item = Literal>,
     {
+        self.failed_assumptions.clear();
+        retract::forget_learnt_if_removed(self);
         // If the set of clauses contain the empty clause: UNSAT
         if self.encountered_empty_clause {
             return Ok(SolveResult::Unsat)
         }
 
-        // If the set of clauses contain the empty clause: UNSAT
+        // If there are no variables the instance is trivially satisfiable: SAT
         if self.len_variables() == 0 {
             return Ok(SolveResult::sat(self.last_model.get()))
         }
 
-        // Raise decision level before propagating the hard problem facts.
-        let _root_level = self.assignment.bump_decision_level();
+        self.assumptions.clear();
+        self.assumptions.extend(self.scopes.activations());
+        self.assumptions.extend(assumptions);
+        self.conflicts = 0;
+        let started = Instant::now();
+        let result = self.search();
+        self.stats.solve_time += started.elapsed();
+        let len_scopes = self.scopes.len();
+        let scopes = &self.assumptions[..len_scopes];
+        self.failed_assumptions
+            .retain(|assumption| !scopes.contains(assumption));
+        // Undo all assignments so that the solver is ready to accept new
+        // clauses or constraints and another call to `solve`.
+        self.assignment
+            .reset(&mut self.constraints, &mut self.decider);
+        let result = match result {
+            SearchResult::Sat => SolveResult::sat(self.last_model.get()),
+            SearchResult::Unsat => SolveResult::Unsat,
+            SearchResult::Unknown => SolveResult::Unknown,
+        };
+        Ok(result)
+    }
 
-        // Propagate known hard facts (unit clauses).
-        if self.propagate_hard_facts().is_conflict() {
-            return Ok(SolveResult::Unsat)
+    /// Returns the statistics about the work of the solver so far.
+    pub fn stats(&self) -> Statistics {
+        Statistics {
+            propagations: self.assignment.propagations(),
+            ..self.stats
         }
+    }
 
-        // Raise decision level before propagating the given assumptions.
-        let _assumptions_level = self.assignment.bump_decision_level();
+    /// Sets the observer that receives the events of all following searches.
+    ///
+    /// # Note
+    ///
+    /// The observer is not inherited by clones of the solver.
+    pub fn set_observer<O>(&mut self, observer: O)
+    where
+        O: SolverObserver + Send + 'static,
+    {
+        self.observer.replace(Some(Box::new(observer)));
+    }
 
-        // Enqueue and propagate given assumptions.
-        //
-        // Bail out if the provided assumptions are in conflict with the instance.
-        if self.propagate_assumptions(assumptions).is_conflict() {
-            return Ok(SolveResult::Unsat)
-        }
+    /// Removes the observer from the solver and returns it if any.
+    pub fn take_observer(&mut self) -> Option<Box<dyn SolverObserver + Send>> {
+        self.observer.replace(None)
+    }
+
+    /// Sets the callback that receives every learned clause of up to `max_len`
+    /// literals.
+    ///
+    /// # Note
+    ///
+    /// - Learned unit clauses are exported as well.
+    /// - Learned clauses are implied by the clauses and constraints of the solver
+    ///   so that they can be added to other solvers of the same instance.
+    /// - The callback is not inherited by clones of the solver.
+    pub fn set_learn_callback<F>(&mut self, max_len: usize, callback: F)
+    where
+        F: FnMut(&[Literal]) + Send + 'static,
+    {
+        self.sharing.set_export(max_len, 0, Box::new(callback));
+    }
+
+    /// Sets the callback that receives every learned clause of up to `max_len`
+    /// literals or with a literal block distance of up to `max_lbd`.
+    pub(crate) fn set_share_callback<F>(
+        &mut self,
+        max_len: usize,
+        max_lbd: u32,
+        callback: F,
+    ) where
+        F: FnMut(&[Literal]) + Send + 'static,
+    {
+        self.sharing
+            .set_export(max_len, max_lbd, Box::new(callback));
+    }
+
+    /// Sets the callback that yields external clauses to import.
+    ///
+    /// The callback is polled until it yields `None` whenever the search is at
+    /// the root level, i.e. at the start of every call to `solve` and upon restarts.
+    /// Imported clauses are treated like learned clauses.
+    ///
+    /// # Note
+    ///
+    /// - Imported clauses must be implied by the clauses and constraints of the
+    ///   solver, e.g. learned by another solver of the same instance.
+    /// - Imported clauses with unregistered variables are ignored.
+    /// - The callback is not inherited by clones of the solver.
+    pub fn set_import_callback<F>(&mut self, callback: F)
+    where
+        F: FnMut() -> Option<Vec<Literal>> + Send + 'static,
+    {
+        self.sharing.set_import(Box::new(callback));
+    }
+
+    /// Resets the statistics about the work of the solver.
+    pub(crate) fn reset_stats(&mut self) {
+        self.stats = Statistics::default();
+        self.assignment.reset_propagations();
+    }
+
+    /// Returns a handle to interrupt calls to `solve` from other threads.
+    pub fn interrupter(&self) -> Interrupter {
+        self.interrupter.clone()
+    }
+
+    /// Limits the number of conflicts of every following call to `solve`.
+    ///
+    /// Calls to `solve` that reach the limit return [`SolveResult::Unknown`].
+    /// No limit applies if `limit` is `None` which is the default.
+    pub fn set_conflict_limit(&mut self, limit: Option<u64>) {
+        self.conflict_limit = limit;
+    }
+
+    /// Interrupts every following call to `solve` once the deadline has passed.
+    ///
+    /// Calls to `solve` that are interrupted return [`SolveResult::Unknown`].
+    /// No deadline applies if `deadline` is `None` which is the default.
+    ///
+    /// # Note
+    ///
+    /// The deadline is checked upon every conflict.
+    pub fn set_deadline(&mut self, deadline: Option<Instant>) {
+        self.deadline = deadline;
+    }
+
+    /// Diversifies the search by randomizing the order in which the decision
+    /// heuristic chooses variables that have not yet been involved in conflicts.
+    ///
+    /// Different seeds usually lead to different search paths and models.
+    /// The order of existing and all following variables is randomized.
+    pub fn set_seed(&mut self, seed: u64) {
+        self.decider.set_seed(seed);
+    }
+
+    /// Sets the scheme by which the decision heuristic bumps the priorities of
+    /// variables involved in conflicts.
+    pub fn set_decision_heuristic(&mut self, heuristic: DecisionHeuristic) {
+        self.decider.set_heuristic(heuristic);
+    }
+
+    /// Sets the polarity with which variables are decided.
+    pub fn set_phase(&mut self, phase: Phase) {
+        self.decider.set_phase(phase);
+    }
+
+    /// Sets the sequence of the numbers of conflicts between two restarts.
+    ///
+    /// # Note
+    ///
+    /// This starts the new sequence from its beginning.
+    pub fn set_restart_strategy(&mut self, strategy: RestartStrategy) {
+        self.restarts = RestartPolicy::new(strategy);
+    }
+
+    /// Sets the fraction of clause words freed by deleted clauses above which
+    /// the garbage of the clause database is collected upon the next restart.
+    ///
+    /// The default fraction is `0.2`. A fraction of `1.0` disables the garbage
+    /// collection.
+    ///
+    /// # Panics
+    ///
+    /// If the fraction is not within `0.0..=1.0`.
+    pub fn set_gc_fraction(&mut self, fraction: f64) {
+        self.gc = GcPolicy::new(fraction);
+    }
+
+    /// Returns the assumptions of the last call to `solve` that are responsible
+    /// for its unsatisfiability.
+    ///
+    /// # Note
+    ///
+    /// - The returned assumptions are a subset of the assumptions of the last call
+    ///   to `solve` and the instance is unsatisfiable under them as well.
+    /// - Returns an empty slice if the last call to `solve` was satisfiable or if
+    ///   the instance is unsatisfiable regardless of the assumptions.
+    pub fn failed_assumptions(&self) -> &[Literal] {
+        &self.failed_assumptions
+    }
+
+    /// Returns the backbone of the instance under the assumptions.
+    ///
+    /// The backbone consists of all literals that are satisfied by every model
+    /// that satisfies the assumptions. The returned literals are sorted by
+    /// their variables. Released variables and the activation variables of the
+    /// scopes opened by [`Solver::push`] are never part of the backbone.
+    ///
+    /// Returns `None` if the instance is unsatisfiable under the assumptions.
+    ///
+    /// # Note
+    ///
+    /// The backbone is computed by incremental calls to `solve` so that
+    /// clauses learned in the process are kept. After the computation
+    /// [`Solver::failed_assumptions`] is unspecified.
+    ///
+    /// # Panics
+    ///
+    /// If any call to `solve` is interrupted by a limit of the solver.
+    pub fn backbone<L>(&mut self, assumptions: L) -> Option<Vec<Literal>>
+    where
+        L: IntoIterator<Item = Literal>,
+    {
+        backbone::backbone(self, assumptions.into_iter().collect())
+    }
+
+    /// Writes the formula in the DIMACS format.
+    ///
+    /// Writes the clauses and unit clauses given to the solver as well as
+    /// the learned clauses and the literals implied at the root level if
+    /// selected by the options.
+    ///
+    /// # Note
+    ///
+    /// - Clauses are written as stored by the solver, i.e. without duplicate
+    ///   literals and without tautological clauses.
+    /// - Native pseudo-Boolean and cardinality constraints are not written.
+    ///
+    /// # Errors
+    ///
+    /// If writing to the output fails.
+    pub fn write_dimacs<W>(
+        &mut self,
+        out: &mut W,
+        options: DimacsOptions,
+    ) -> io::Result<()>
+    where
+        W: Write,
+    {
+        dimacs::write_dimacs(self, out, options)
+    }
+
+    /// Opens a new scope for the clauses consumed until the matching [`Solver::pop`].
+    ///
+    /// # Note
+    ///
+    /// - Every scope allocates a new activation literal that guards the clauses
+    ///   of the scope and that is assumed by `solve` while the scope is open.
+    /// - Native constraints are not scoped.
+    pub fn push(&mut self) {
+        scope::push(self)
+    }
+
+    /// Closes the innermost scope and removes the clauses consumed within it.
+    ///
+    /// Returns `false` if there is no open scope.
+    ///
+    /// # Note
+    ///
+    /// The learned clauses derived from the removed clauses are removed as well.
+    /// The activation literal of the scope is never assumed again and its
+    /// variable is released unless it has been frozen.
+    pub fn pop(&mut self) -> bool {
+        scope::pop(self)
+    }
+
+    /// Removes the clause of the handle returned by [`Solver::consume_clause`].
+    ///
+    /// Returns `false` if the clause has already been removed.
+    ///
+    /// # Note
+    ///
+    /// - All learned clauses are forgotten since they may have been derived
+    ///   from the removed clause. This includes the learned unit clauses
+    ///   and the clauses imported via [`Solver::set_import_callback`].
+    /// - The learned clauses are forgotten upon the next call to `solve` so that
+    ///   removing many clauses at once forgets them only once. Use the scopes of
+    ///   [`Solver::push`] to keep the learned clauses that do not depend on the
+    ///   removed clauses.
+    /// - Native constraints as well as unit clauses implied by them are kept.
+    ///
+    /// # Panics
+    ///
+    /// If the handle has not been returned by this solver.
+    pub fn remove_clause(&mut self, handle: ClauseHandle) -> bool {
+        retract::remove_clause(self, handle)
+    }
 
-        // Raise decision level before propagating the decisions.
-        let _constraints_level = self.assignment.bump_decision_level();
+    /// Freezes the variable of the literal so that it cannot be released.
+    ///
+    /// # Note
+    ///
+    /// - Frozen variables are protected from simplifications that eliminate or
+    ///   substitute variables. Freeze the variables that are queried or assumed later.
+    /// - A variable stays frozen until it has been melted as many times as it
+    ///   has been frozen.
+    ///
+    /// # Panics
+    ///
+    /// If the literal has not been registered.
+    pub fn freeze(&mut self, literal: Literal) {
+        self.lifecycle.freeze(literal.variable())
+    }
+
+    /// Melts the variable of the literal frozen via [`Solver::freeze`].
+    ///
+    /// # Panics
+    ///
+    /// - If the literal has not been registered.
+    /// - If the variable of the literal is not frozen.
+    pub fn melt(&mut self, literal: Literal) {
+        self.lifecycle.melt(literal.variable())
+    }
+
+    /// Returns `true` if the variable of the literal is frozen.
+    ///
+    /// # Panics
+    ///
+    /// If the literal has not been registered.
+    pub fn is_frozen(&self, literal: Literal) -> bool {
+        self.lifecycle.is_frozen(literal.variable())
+    }
+
+    /// Releases the variable of the literal so that it is reused by [`Solver::new_literal`].
+    ///
+    /// # Note
+    ///
+    /// - The variable must no longer occur in any clause or constraint, e.g. after
+    ///   its clauses have been removed via [`Solver::remove_clause`].
+    /// - Learned clauses containing the variable are forgotten.
+    /// - The variable must not be used after its release since it may be reused
+    ///   with a different meaning. Models still contain it until then.
+    ///
+    /// # Errors
+    ///
+    /// - If the variable is frozen.
+    /// - If the variable occurs in a clause, a unit clause or a native constraint.
+    /// - If the variable has already been released.
+    ///
+    /// # Panics
+    ///
+    /// If the literal has not been registered.
+    pub fn release_variable(&mut self, literal: Literal) -> Result<(), ReleaseError> {
+        lifecycle::release_variable(self, literal.variable())
+    }
+
+    /// Returns the clause database of the solver.
+    ///
+    /// # Note
+    ///
+    /// Unit clauses are kept as facts of the solver and are not stored
+    /// in the clause database. Use [`ClauseDatabase::live_clauses`] to
+    /// iterate over the clauses that have not been deleted.
+    /// Learned clauses are still stored after [`Solver::remove_clause`]
+    /// until the next call to `solve`.
+    pub fn clause_database(&self) -> &ClauseDatabase {
+        &self.clauses
+    }
+
+    /// Returns an iterator over the models of the instance.
+    ///
+    /// Yields models that differ in the assignment of at least one projection
+    /// variable and at most `limit` models if given.
+    /// All variables that have not been released and that are not the activation
+    /// variables of the scopes opened by [`Solver::push`] are projection variables
+    /// if no `projection` is given.
+    ///
+    /// # Note
+    ///
+    /// - Each call allocates a variable to guard the clauses that block the
+    ///   enumerated models.
+    /// - The yielded models assign neither released variables nor the activation
+    ///   variables of open scopes nor the guard variable.
+    /// - The blocking clauses are removed and the guard variable is released
+    ///   when the iterator is dropped so that the solver is left with the same
+    ///   models as before.
+    ///
+    /// # Panics
+    ///
+    /// - If any of the projection variables has not been registered.
+    /// - If any call to `solve` is interrupted by a limit of the solver.
+    pub fn enumerate_models(
+        &mut self,
+        projection: Option<&[Variable]>,
+        limit: Option<usize>,
+    ) -> ModelEnumerator<'_> {
+        ModelEnumerator::new(self, projection, limit)
+    }
 
-        // Start solving using recursive DPLL style.
-        let result = match self.decide_and_propagate() {
-            DecisionResult::Conflict => SolveResult::Unsat,
-            DecisionResult::Sat => {
-                let result = SolveResult::sat(self.last_model.get());
-                result
+    /// Searches for a satisfying assignment using conflict driven clause learning.
+    fn search(&mut self) -> SearchResult {
+        if self.max_learnt_clauses == 0 {
+            self.max_learnt_clauses = (self.clauses.len() / 3).max(2000);
+        }
+        // Enqueue known hard facts (unit clauses) at the root level.
+        if self.enqueue_hard_facts().is_err() {
+            return SearchResult::Unsat
+        }
+        if let Some(result) = self.import_clauses() {
+            return result
+        }
+        loop {
+            let started = Instant::now();
+            let propagated_from = self.assignment.trail_len();
+            let result = self
+                .assignment
+                .propagate(&mut self.clauses, &mut self.constraints);
+            self.stats.propagation_time += started.elapsed();
+            if self.observer.is_some() {
+                self.observe_propagation(propagated_from, result);
+            }
+            match result {
+                PropagationResult::Conflict(conflict) => {
+                    self.stats.conflicts += 1;
+                    if self.assignment.current_decision_level().is_root() {
+                        return SearchResult::Unsat
+                    }
+                    self.resolve_conflict(conflict);
+                    if self.is_interrupted() {
+                        return SearchResult::Unknown
+                    }
+                    if self.assignment.current_decision_level().is_root() {
+                        if let Some(result) = self.import_clauses() {
+                            return result
+                        }
+                    }
+                }
+                PropagationResult::Consistent => {
+                    if let Some(result) = self.decide() {
+                        return result
+                    }
+                }
             }
+        }
+    }
+
+    /// Reports the literals propagated since the trail position and the conflict
+    /// of the propagation if any to the observer.
+    #[cold]
+    fn observe_propagation(&mut self, propagated_from: usize, result: PropagationResult) {
+        let observer = match self.observer.get() {
+            Some(observer) => observer,
+            None => return,
         };
-        Ok(result)
+        for position in propagated_from..self.assignment.trail_len() {
+            observer.on_propagation(self.assignment.trail_literal(position));
+        }
+        if let PropagationResult::Conflict(conflict) = result {
+            let mut clause = Vec::new();
+            ConflictAnalyzer::explain(
+                &mut clause,
+                conflict,
+                None,
+                &self.assignment,
+                &self.clauses,
+                &self.constraints,
+            );
+            observer.on_conflict(&clause);
+        }
     }
 
-    /// Propagates the hard facts (unit clauses) of the SAT instance.
-    fn propagate_hard_facts(&mut self) -> PropagationResult {
+    /// Imports the external clauses of the import callback.
+    ///
+    /// Returns `Some(SearchResult::Unsat)` if an imported clause is falsified
+    /// at the root level.
+    ///
+    /// # Note
+    ///
+    /// Must only be called at the root level. Literals that are assigned at the
+    /// root level are removed from the imported clauses so that the remaining
+    /// literals can be watched without propagating the clause.
+    fn import_clauses(&mut self) -> Option<SearchResult> {
+        let len_variables = self.len_variables();
+        'import: while let Some(mut clause) = self.sharing.import() {
+            let mut len = 0;
+            for index in 0..clause.len() {
+                let literal = clause[index];
+                if literal.variable().into_index() >= len_variables {
+                    continue 'import
+                }
+                match self.assignment.variable_assignment().is_satisfied(literal) {
+                    Some(true) => continue 'import,
+                    Some(false) => continue,
+                    None => (),
+                }
+                if clause[..len].contains(&!literal) {
+                    continue 'import
+                }
+                if !clause[..len].contains(&literal) {
+                    clause[len] = literal;
+                    len += 1;
+                }
+            }
+            match clause[..len] {
+                [] => {
+                    self.encountered_empty_clause = true;
+                    return Some(SearchResult::Unsat)
+                }
+                [unit] => {
+                    self.hard_facts.push(unit);
+                    self.assignment
+                        .enqueue_assumption(unit)
+                        .expect("encountered unexpected assigned imported unit clause");
+                }
+                ref literals => {
+                    let header = ClauseHeader::build()
+                        .learnt(true)
+                        .lbd(literals.len() as u32)
+                        .finish();
+                    let cref = self
+                        .clauses
+                        .alloc_with_header(header, literals.iter().copied());
+                    let resolved = self.clauses.resolve(cref).unwrap_or_else(|| {
+                        panic!("failed to resolve recently imported clause: {:?}", cref)
+                    });
+                    self.assignment.initialize_watchers(cref, resolved);
+                    self.learnt_clauses.push(cref);
+                }
+            }
+        }
+        None
+    }
+
+    /// Returns `true` if the current `solve` invocation exceeded any of its limits.
+    ///
+    /// Must be called exactly once per conflict.
+    fn is_interrupted(&mut self) -> bool {
+        self.conflicts += 1;
+        if let Some(limit) = self.conflict_limit {
+            if self.conflicts >= limit {
+                return true
+            }
+        }
+        if self.interrupter.is_interrupted() {
+            return true
+        }
+        match self.deadline {
+            Some(deadline) => Instant::now() >= deadline,
+            None => false,
+        }
+    }
+
+    /// Enqueues the hard facts (unit clauses) of the SAT instance.
+    ///
+    /// # Errors
+    ///
+    /// If the hard facts are in conflict with each other.
+    fn enqueue_hard_facts(&mut self) -> Result<(), AssignmentError> {
         for &hard_fact in &self.hard_facts {
             match self.assignment.enqueue_assumption(hard_fact) {
                 Ok(()) | Err(AssignmentError::AlreadyAssigned) => (),
                 Err(AssignmentError::ConflictingAssignment) => {
-                    return PropagationResult::Conflict
+                    return Err(AssignmentError::ConflictingAssignment)
                 }
                 _unexpected_error => {
                     panic!("encountered unexpected error while propagating hard facts")
                 }
             }
         }
-        PropagationResult::Consistent
+        Ok(())
     }
 
-    /// Propagates the given assumptions.
-    fn propagate_assumptions<L>(&mut self, assumptions: L) -> PropagationResult
-    where
-        L: IntoIterator<Item = Literal>,
-    {
-        for assumption in assumptions {
-            if let Err(AssignmentError::ConflictingAssignment) =
-                self.assignment.enqueue_assumption(assumption)
-            {
-                return PropagationResult::Conflict
+    /// Analyzes the conflict, learns a clause from it and backjumps.
+    ///
+    /// Restarts the search if the restart policy demands it.
+    fn resolve_conflict(&mut self, conflict: Reason) {
+        let started = Instant::now();
+        self.decider.on_conflict();
+        let learned = self.analyzer.analyze(
+            conflict,
+            &self.assignment,
+            &self.clauses,
+            &self.constraints,
+            &mut self.decider,
+        );
+        self.assignment.pop_decision_level(
+            learned.backjump_level,
+            &mut self.constraints,
+            &mut self.decider,
+        );
+        for &cref in learned.resolved {
+            if let Some(mut clause) = self.clauses.resolve_mut(cref) {
+                if clause.header().is_learnt() {
+                    clause.header_mut().bump_activity();
+                }
             }
         }
-        if self
-            .assignment
-            .propagate(&mut self.clauses, &mut self.decider)
-            .is_conflict()
-        {
-            return PropagationResult::Conflict
+        self.sharing.export(learned.literals, learned.lbd);
+        if let Some(observer) = self.observer.get() {
+            observer.on_learn(learned.literals);
+            observer.on_backjump(learned.backjump_level.into_index());
+            observer.on_propagation(learned.literals[0]);
+        }
+        match *learned.literals {
+            [unit] => {
+                // Learned unit clauses are facts of the SAT instance.
+                self.hard_facts.push(unit);
+                self.assignment
+                    .enqueue_assumption(unit)
+                    .expect("encountered unexpected assigned learned unit clause");
+            }
+            ref literals => {
+                let header = ClauseHeader::build().learnt(true).lbd(learned.lbd).finish();
+                let cref = self
+                    .clauses
+                    .alloc_with_header(header, literals.iter().copied());
+                let resolved = self.clauses.resolve(cref).unwrap_or_else(|| {
+                    panic!("failed to resolve recently learned clause: {:?}", cref)
+                });
+                self.assignment.initialize_watchers(cref, resolved);
+                self.assignment
+                    .enqueue(literals[0], Reason::Clause(cref))
+                    .expect("encountered unexpected non-asserting learned clause");
+                self.learnt_clauses.push(cref);
+            }
+        }
+        self.stats.learned_clauses += 1;
+        self.stats.analysis_time += started.elapsed();
+        if self.restarts.on_conflict() {
+            self.restart();
         }
-        PropagationResult::Consistent
     }
 
-    /// Decides the next literal and propagates it.
+    /// Restarts the search by backjumping to the root level.
     ///
-    /// This recursively checks for a valid assignment for both
-    /// positive and negative assignments of the decided literal.
-    /// Returns a conflict if both assignments has led to a conflict.
+    /// Reduces the learned clauses if there are too many of them and collects
+    /// the garbage of the clause database if enough clause words have been freed.
+    fn restart(&mut self) {
+        self.stats.restarts += 1;
+        if let Some(observer) = self.observer.get() {
+            observer.on_restart();
+        }
+        self.assignment.pop_decision_level(
+            DecisionLevel::ROOT,
+            &mut self.constraints,
+            &mut self.decider,
+        );
+        if self.learnt_clauses.len() >= self.max_learnt_clauses {
+            let started = Instant::now();
+            self.reduce_learnt_clauses();
+            if self.gc.is_due(&self.clauses) {
+                gc::collect_garbage(self);
+            }
+            self.stats.reduction_time += started.elapsed();
+            self.max_learnt_clauses += self.max_learnt_clauses / 10;
+        }
+    }
+
+    /// Removes about half of the learned clauses with the highest literal block distance.
     ///
     /// # Note
     ///
-    /// Returns SAT if all literals already are assigned OR
-    /// if a valid assignment has been found.
-    fn decide_and_propagate(&mut self) -> DecisionResult {
+    /// - Among learned clauses with the same literal block distance the least
+    ///   active clauses are removed first.
+    /// - Learned clauses with a literal block distance of at most 2 as well as
+    ///   clauses that are the reason for a current assignment are kept.
+    /// - The activity of the kept learned clauses decays.
+    fn reduce_learnt_clauses(&mut self) {
+        let Self {
+            clauses,
+            learnt_clauses,
+            assignment,
+            stats,
+            observer,
+            ..
+        } = self;
+        learnt_clauses.sort_by_cached_key(|&cref| {
+            let clause = clauses
+                .resolve(cref)
+                .expect("encountered unexpected invalid learned clause");
+            (Reverse(clause.header().lbd()), clause.header().activity())
+        });
+        let limit = learnt_clauses.len() / 2;
+        let mut index = 0;
+        learnt_clauses.retain(|&cref| {
+            index += 1;
+            let clause = clauses
+                .resolve(cref)
+                .expect("encountered unexpected invalid learned clause");
+            let keep = index > limit
+                || clause.header().lbd() <= 2
+                || assignment.is_reason(cref, clause);
+            if !keep {
+                if let Some(observer) = observer.get() {
+                    observer.on_clause_deleted(clause.literals().as_slice());
+                }
+                if let ClauseRemoval::Removed(freed_words) = clauses.remove_clause(cref) {
+                    stats.deleted_clauses += 1;
+                    stats.freed_words += freed_words as u64;
+                }
+            } else if let Some(mut clause) = clauses.resolve_mut(cref) {
+                clause.header_mut().decay_activity();
+            }
+            keep
+        });
+    }
+
+    /// Bumps the decision level and keeps track of the highest one.
+    fn bump_decision_level(&mut self) {
+        let level = self.assignment.bump_decision_level();
+        self.stats.max_decision_level =
+            self.stats.max_decision_level.max(level.into_index());
+    }
+
+    /// Decides the next literal and enqueues it at a new decision level.
+    ///
+    /// Assumptions are decided before any other variable.
+    ///
+    /// # Note
+    ///
+    /// - Returns `Some(SearchResult::Sat)` if all variables have been assigned.
+    /// - Returns `Some(SearchResult::Unsat)` if an assumption is falsified.
+    fn decide(&mut self) -> Option<SearchResult> {
+        while let Some(&assumption) = self
+            .assumptions
+            .get(self.assignment.current_decision_level().into_index())
+        {
+            match self
+                .assignment
+                .variable_assignment()
+                .is_satisfied(assumption)
+            {
+                Some(true) => {
+                    // Keep the invariant that the n-th assumption is decided
+                    // at the decision level n+1 using an empty decision level.
+                    self.bump_decision_level();
+                }
+                Some(false) => {
+                    self.analyzer.analyze_final(
+                        assumption,
+                        &self.assignment,
+                        &self.clauses,
+                        &self.constraints,
+                        &mut self.failed_assumptions,
+                    );
+                    return Some(SearchResult::Unsat)
+                }
+                None => {
+                    self.bump_decision_level();
+                    self.assignment
+                        .enqueue_assumption(assumption)
+                        .expect("encountered unexpected assigned assumption");
+                    return None
+                }
+            }
+        }
         let next_variable = self
             .decider
             .next_unassigned(self.assignment.variable_assignment());
@@ -386,45 +1472,21 @@ impl Solver {
                 self.last_model
                     .update(self.assignment.variable_assignment())
                     .expect("encountered unexpected indeterminate variable assignment");
-                DecisionResult::Sat
+                Some(SearchResult::Sat)
             }
             Some(unassigned_variable) => {
-                let level = self.assignment.bump_decision_level();
-                let decision = Literal::new(unassigned_variable, Sign::POS);
-                if self.solve_for_decision(decision).is_sat()
-                    || self.solve_for_decision(!decision).is_sat()
-                {
-                    return DecisionResult::Sat
+                self.stats.decisions += 1;
+                self.bump_decision_level();
+                let decision =
+                    Literal::new(unassigned_variable, self.decider.next_phase());
+                if let Some(observer) = self.observer.get() {
+                    observer.on_decision(decision);
                 }
-                self.assignment.pop_decision_level(level, &mut self.decider);
-                DecisionResult::Conflict
+                self.assignment
+                    .enqueue_assumption(decision)
+                    .expect("decision heuristic proposed already assigned variable");
+                None
             }
         }
     }
-
-    /// Tries to find a valid assignment for the given literal decision.
-    fn solve_for_decision(&mut self, decision: Literal) -> DecisionResult {
-        match self.assignment.enqueue_assumption(decision) {
-            Err(AssignmentError::ConflictingAssignment) => {
-                return DecisionResult::Conflict
-            }
-            Err(AssignmentError::AlreadyAssigned) => {
-                panic!(
-                    "decision heuristic proposed already assigned variable for propagation: {:?}",
-                    decision,
-                )
-            }
-            Err(error) => {
-                panic!("encountered unexpected or unknown enqueue error: {}", error)
-            }
-            Ok(_) => (),
-        }
-        let propagation_result = self
-            .assignment
-            .propagate(&mut self.clauses, &mut self.decider);
-        match propagation_result {
-            PropagationResult::Conflict => DecisionResult::Conflict,
-            PropagationResult::Consistent => self.decide_and_propagate(),
-        }
-    }
 }
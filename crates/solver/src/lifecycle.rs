@@ -0,0 +1,207 @@
+use crate::{
+    literal::RegisterVariables,
+    retract,
+    Literal,
+    Solver,
+    Variable,
+};
+use bounded::BoundedArray;
+use core::{
+    fmt,
+    fmt::Display,
+};
+
+/// Errors that can be encountered when releasing a variable.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ReleaseError {
+    /// The variable is frozen.
+    Frozen,
+    /// The variable occurs in a clause, a unit clause or a native constraint
+    /// or is the activation literal of an open scope.
+    InUse,
+    /// The variable has already been released and not been reused since.
+    AlreadyReleased,
+}
+
+impl Display for ReleaseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Frozen => write!(f, "cannot release frozen variable"),
+            Self::InUse => write!(f, "cannot release variable that is still in use"),
+            Self::AlreadyReleased => write!(f, "variable has already been released"),
+        }
+    }
+}
+
+/// Keeps track of the frozen, occurring and released variables of the solver.
+#[derive(Debug, Default, Clone)]
+pub struct VariableLifecycle {
+    /// The number of times each variable has been frozen and not yet melted.
+    frozen: BoundedArray<Variable, u32>,
+    /// The number of occurrences of each variable in the clauses, unit clauses
+    /// and native constraints of the solver.
+    ///
+    /// # Note
+    ///
+    /// Occurrences in learned clauses are not counted.
+    occurrences: BoundedArray<Variable, u32>,
+    /// Yields `true` for released variables that have not been reused, yet.
+    released: BoundedArray<Variable, bool>,
+    /// The released variables in the order in which they are reused.
+    free: Vec<Variable>,
+}
+
+impl RegisterVariables for VariableLifecycle {
+    fn register_variables(&mut self, additional: usize) {
+        let total_variables = self.frozen.len() + additional;
+        self.frozen.resize_with(total_variables, Default::default);
+        self.occurrences
+            .resize_with(total_variables, Default::default);
+        self.released.resize_with(total_variables, Default::default);
+    }
+}
+
+impl VariableLifecycle {
+    /// Increases the freeze count of the variable.
+    pub fn freeze(&mut self, variable: Variable) {
+        *self
+            .frozen
+            .get_mut(variable)
+            .expect("encountered unexpected invalid variable") += 1;
+    }
+
+    /// Decreases the freeze count of the variable.
+    ///
+    /// # Panics
+    ///
+    /// If the variable is not frozen.
+    pub fn melt(&mut self, variable: Variable) {
+        let frozen = self
+            .frozen
+            .get_mut(variable)
+            .expect("encountered unexpected invalid variable");
+        *frozen = frozen.checked_sub(1).unwrap_or_else(|| {
+            panic!("cannot melt variable {} that is not frozen", variable)
+        });
+    }
+
+    /// Returns `true` if the variable is frozen.
+    pub fn is_frozen(&self, variable: Variable) -> bool {
+        *self
+            .frozen
+            .get(variable)
+            .expect("encountered unexpected invalid variable")
+            > 0
+    }
+
+    /// Returns `true` if the variable has been released and not been reused, yet.
+    pub fn is_released(&self, variable: Variable) -> bool {
+        *self
+            .released
+            .get(variable)
+            .expect("encountered unexpected invalid variable")
+    }
+
+    /// Counts the occurrences of the literals.
+    pub fn add_occurrences<'a, I>(&mut self, literals: I)
+    where
+        I: IntoIterator<Item = &'a Literal>,
+    {
+        for literal in literals {
+            *self
+                .occurrences
+                .get_mut(literal.variable())
+                .expect("encountered unexpected invalid variable") += 1;
+        }
+    }
+
+    /// Uncounts the occurrences of the literals.
+    pub fn remove_occurrences<'a, I>(&mut self, literals: I)
+    where
+        I: IntoIterator<Item = &'a Literal>,
+    {
+        for literal in literals {
+            *self
+                .occurrences
+                .get_mut(literal.variable())
+                .expect("encountered unexpected invalid variable") -= 1;
+        }
+    }
+
+    /// Marks the variable as released so that it is reused by [`VariableLifecycle::reuse`].
+    ///
+    /// # Errors
+    ///
+    /// If the variable is frozen, still occurs or has already been released.
+    pub fn release(&mut self, variable: Variable) -> Result<(), ReleaseError> {
+        if self.is_released(variable) {
+            return Err(ReleaseError::AlreadyReleased)
+        }
+        if self.is_frozen(variable) {
+            return Err(ReleaseError::Frozen)
+        }
+        if *self
+            .occurrences
+            .get(variable)
+            .expect("encountered unexpected invalid variable")
+            > 0
+        {
+            return Err(ReleaseError::InUse)
+        }
+        self.released
+            .update(variable, true)
+            .expect("encountered unexpected invalid variable");
+        self.free.push(variable);
+        Ok(())
+    }
+
+    /// Returns a released variable to be reused as a new variable if any.
+    pub fn reuse(&mut self) -> Option<Variable> {
+        let variable = self.free.pop()?;
+        self.released
+            .update(variable, false)
+            .expect("encountered unexpected invalid variable");
+        Some(variable)
+    }
+}
+
+/// Releases the variable so that its slot is reused by a new variable.
+///
+/// # Note
+///
+/// The learned clauses and learned unit clauses of the variable are deleted.
+///
+/// # Errors
+///
+/// - If the variable is frozen.
+/// - If the variable occurs in a clause, a unit clause or a native constraint.
+/// - If the variable is the activation literal of an open scope.
+/// - If the variable has already been released.
+pub(crate) fn release_variable(
+    solver: &mut Solver,
+    variable: Variable,
+) -> Result<(), ReleaseError> {
+    if solver
+        .scopes
+        .activations()
+        .any(|activation| activation.variable() == variable)
+    {
+        return Err(ReleaseError::InUse)
+    }
+    solver.lifecycle.release(variable)?;
+    forget_variable(solver, variable);
+    Ok(())
+}
+
+/// Deletes the learned clauses and learned unit clauses of the variable.
+pub(crate) fn forget_variable(solver: &mut Solver, variable: Variable) {
+    retract::delete_learnt_clauses(solver, |clause| {
+        clause
+            .literals()
+            .iter()
+            .any(|literal| literal.variable() == variable)
+    });
+    solver
+        .hard_facts
+        .retain(|literal| literal.variable() != variable);
+}
@@ -119,14 +119,26 @@ impl Literal {
     pub fn sign(self) -> Sign {
         Sign((self.value & 1) != 0)
     }
+
+    /// Creates a literal from its internal representation.
+    #[inline]
+    pub(crate) fn from_u32(value: u32) -> Self {
+        Self { value }
+    }
+
+    /// Returns the internal representation of the literal.
+    #[inline]
+    pub(crate) fn into_u32(self) -> u32 {
+        self.value
+    }
 }
 
 impl From<i32> for Literal {
     #[inline]
     fn from(x: i32) -> Self {
         debug_assert!(x != 0);
-        let var = x.abs() as u32 - 1;
-        let sign = (x < 0) as u32;
+        let var = x.unsigned_abs() - 1;
+        let sign = (x > 0) as u32;
         Literal {
             value: (var << 1) + sign,
         }
@@ -234,6 +246,11 @@ pub trait RegisterVariables {
     ///
     /// If the implementer cannot afford to registered the given amount of new variables.
     fn register_variables(&mut self, additional: usize);
+
+    /// Informs the implementer that a released variable is reused as a new variable.
+    ///
+    /// Resets the state kept for the variable to that of a newly registered variable.
+    fn recycle_variable(&mut self, _variable: Variable) {}
 }
 
 #[cfg(test)]
@@ -259,14 +276,14 @@ mod tests {
 
         #[test]
         fn is_pos_works() {
-            assert_eq!(Sign::POS.is_pos(), true);
-            assert_eq!(Sign::NEG.is_pos(), false);
+            assert!(Sign::POS.is_pos());
+            assert!(!Sign::NEG.is_pos());
         }
 
         #[test]
         fn is_neg_works() {
-            assert_eq!(Sign::POS.is_neg(), false);
-            assert_eq!(Sign::NEG.is_neg(), true);
+            assert!(!Sign::POS.is_neg());
+            assert!(Sign::NEG.is_neg());
         }
     }
 
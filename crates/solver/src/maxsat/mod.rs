@@ -0,0 +1,267 @@
+//! Weighted partial MaxSAT solving on top of the incremental SAT solver.
+
+#[cfg(test)]
+mod tests;
+mod wcnf;
+
+pub use self::wcnf::{
+    Wcnf,
+    WcnfError,
+};
+use crate::{
+    encodings,
+    Literal,
+    Model,
+    SolveResult,
+    Solver,
+};
+use ahash::AHashMap;
+
+/// The algorithm used to find an optimal solution.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub enum MaxSatAlgorithm {
+    /// Finds better and better solutions until no better solution exists.
+    ///
+    /// Every found solution constrains the cost of the next solution to be lower.
+    LinearSatUnsat,
+    /// The core-guided OLL algorithm.
+    ///
+    /// Assumes all soft clauses to be satisfied and relaxes the unsatisfiable
+    /// cores over the assumptions using totalizers until a solution is found.
+    #[default]
+    Oll,
+}
+
+/// The result of solving a MaxSAT instance.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MaxSatResult {
+    /// The hard clauses are unsatisfiable.
+    Unsat,
+    /// An optimal solution with the sum of the weights of its falsified soft clauses.
+    Optimum { cost: u64, model: Model },
+}
+
+/// A soft literal that is to be `true` and its weight that is paid otherwise.
+#[derive(Debug, Copy, Clone)]
+struct SoftLiteral {
+    literal: Literal,
+    weight: u64,
+}
+
+/// A weighted partial MaxSAT solver.
+///
+/// # Note
+///
+/// Every soft clause with more than one literal is relaxed by a new literal
+/// so that all soft clauses are represented by soft literals that are to be `true`.
+#[derive(Debug, Default)]
+pub struct MaxSatSolver {
+    /// The underlying SAT solver that stores the hard and relaxed soft clauses.
+    solver: Solver,
+    /// The original soft clauses to compute the cost of solutions.
+    soft_clauses: Vec<(u64, Vec<Literal>)>,
+    /// The soft literals with their accumulated weights.
+    soft_literals: Vec<SoftLiteral>,
+    /// The index of every soft literal in `soft_literals`.
+    soft_indices: AHashMap<Literal, usize>,
+    /// The accumulated weight of all empty soft clauses.
+    offset: u64,
+}
+
+impl MaxSatSolver {
+    /// Creates a new MaxSAT solver for the instance.
+    pub fn from_wcnf(wcnf: &Wcnf) -> Self {
+        let mut maxsat = Self::default();
+        maxsat.new_literal_chunk(wcnf.len_variables());
+        for clause in wcnf.hard_clauses() {
+            maxsat.add_hard_clause(clause);
+        }
+        for (weight, clause) in wcnf.soft_clauses() {
+            maxsat.add_soft_clause(*weight, clause);
+        }
+        maxsat
+    }
+
+    /// Allocates the given amount of new literals and returns them.
+    pub fn new_literal_chunk(&mut self, amount: usize) -> Vec<Literal> {
+        self.solver.new_literal_chunk(amount).into_iter().collect()
+    }
+
+    /// Adds a clause that must be satisfied.
+    ///
+    /// # Panics
+    ///
+    /// If any of the literals has not been registered.
+    pub fn add_hard_clause(&mut self, literals: &[Literal]) {
+        encodings::add_clause(&mut self.solver, literals);
+    }
+
+    /// Adds a clause whose weight is paid if it is falsified.
+    ///
+    /// # Panics
+    ///
+    /// If any of the literals has not been registered.
+    pub fn add_soft_clause(&mut self, weight: u64, literals: &[Literal]) {
+        let is_tautological =
+            literals.iter().any(|&literal| literals.contains(&!literal));
+        if weight == 0 || is_tautological {
+            return
+        }
+        self.soft_clauses.push((weight, literals.to_vec()));
+        match *literals {
+            [] => self.offset += weight,
+            [literal] => self.add_soft_literal(literal, weight),
+            _ => {
+                let relaxation = self.solver.new_literal();
+                let mut relaxed = literals.to_vec();
+                relaxed.push(relaxation);
+                self.solver.consume_clause(relaxed);
+                self.add_soft_literal(!relaxation, weight);
+            }
+        }
+    }
+
+    /// Adds the weight to the soft literal.
+    fn add_soft_literal(&mut self, literal: Literal, weight: u64) {
+        match self.soft_indices.get(&literal) {
+            Some(&index) => self.soft_literals[index].weight += weight,
+            None => {
+                self.soft_indices.insert(literal, self.soft_literals.len());
+                self.soft_literals.push(SoftLiteral { literal, weight });
+            }
+        }
+    }
+
+    /// Returns the sum of the weights of all soft clauses falsified by the model.
+    ///
+    /// # Panics
+    ///
+    /// If the model does not assign all variables of the soft clauses.
+    pub fn cost(&self, model: &Model) -> u64 {
+        self.soft_clauses
+            .iter()
+            .filter(|(_, clause)| {
+                !clause.iter().any(|&literal| {
+                    model
+                        .is_satisfied(literal)
+                        .expect("encountered unassigned soft clause literal")
+                })
+            })
+            .map(|&(weight, _)| weight)
+            .sum()
+    }
+
+    /// Solves the instance to optimality using the algorithm.
+    ///
+    /// Calls `on_solution` with the cost of every found solution that improves
+    /// upon the previously found solutions.
+    ///
+    /// # Note
+    ///
+    /// Solving is not incremental: the solver must not be solved again.
+    pub fn solve<F>(&mut self, algorithm: MaxSatAlgorithm, on_solution: F) -> MaxSatResult
+    where
+        F: FnMut(u64),
+    {
+        match algorithm {
+            MaxSatAlgorithm::LinearSatUnsat => self.solve_linear(on_solution),
+            MaxSatAlgorithm::Oll => self.solve_oll(on_solution),
+        }
+    }
+
+    /// Solves the instance using linear SAT-UNSAT search.
+    fn solve_linear<F>(&mut self, mut on_solution: F) -> MaxSatResult
+    where
+        F: FnMut(u64),
+    {
+        let mut best = None;
+        loop {
+            let result = self
+                .solver
+                .solve([])
+                .expect("encountered errors during solving");
+            let model = match result {
+                SolveResult::Sat(sat) => sat.model().clone(),
+                SolveResult::Unsat => break,
+                SolveResult::Unknown => {
+                    panic!("encountered unexpected interrupted search")
+                }
+            };
+            let cost = self.cost(&model);
+            on_solution(cost);
+            best = Some((cost, model));
+            if cost <= self.offset {
+                break
+            }
+            // Require `sum(w_i * !s_i) <= cost - offset - 1` for the next solution.
+            let terms = self.soft_literals.iter().map(|soft| {
+                let weight = i64::try_from(soft.weight)
+                    .expect("encountered too large soft clause weight");
+                (-weight, !soft.literal)
+            });
+            let bound = i64::try_from(cost - self.offset - 1)
+                .expect("encountered too large soft clause weight");
+            self.solver.add_pseudo_boolean(terms, -bound);
+        }
+        match best {
+            Some((cost, model)) => MaxSatResult::Optimum { cost, model },
+            None => MaxSatResult::Unsat,
+        }
+    }
+
+    /// Solves the instance using the core-guided OLL algorithm.
+    fn solve_oll<F>(&mut self, mut on_solution: F) -> MaxSatResult
+    where
+        F: FnMut(u64),
+    {
+        // For every soft literal `!o_k` of a totalizer the next soft literal `!o_(k+1)`.
+        let mut next_bounds = AHashMap::<Literal, Literal>::default();
+        let mut assumptions = Vec::new();
+        loop {
+            assumptions.clear();
+            assumptions.extend(
+                self.soft_literals
+                    .iter()
+                    .filter(|soft| soft.weight > 0)
+                    .map(|soft| soft.literal),
+            );
+            let result = self
+                .solver
+                .solve(assumptions.iter().copied())
+                .expect("encountered errors during solving");
+            if let SolveResult::Sat(sat) = result {
+                let model = sat.model().clone();
+                let cost = self.cost(&model);
+                on_solution(cost);
+                return MaxSatResult::Optimum { cost, model }
+            }
+            let core = self.solver.failed_assumptions().to_vec();
+            if core.is_empty() {
+                return MaxSatResult::Unsat
+            }
+            let min_weight = core
+                .iter()
+                .map(|literal| self.soft_literals[self.soft_indices[literal]].weight)
+                .min()
+                .expect("encountered unexpected empty core");
+            for literal in &core {
+                self.soft_literals[self.soft_indices[literal]].weight -= min_weight;
+                if let Some(&next_bound) = next_bounds.get(literal) {
+                    self.add_soft_literal(next_bound, min_weight);
+                }
+            }
+            if let [literal] = *core {
+                self.solver.consume_clause([!literal]);
+                continue
+            }
+            // At least one literal of the core is falsified. Every additional
+            // falsified literal costs the minimum weight again.
+            let falsified = core.iter().map(|&literal| !literal).collect::<Vec<_>>();
+            let outputs = encodings::totalizer_outputs(&mut self.solver, &falsified);
+            for bounds in outputs[1..].windows(2) {
+                next_bounds.insert(!bounds[0], !bounds[1]);
+            }
+            self.add_soft_literal(!outputs[1], min_weight);
+        }
+    }
+}
@@ -0,0 +1,217 @@
+use super::*;
+use crate::{
+    tests::Lcg,
+    Sign,
+    Variable,
+};
+use bounded::Index as _;
+
+/// Returns the literal for the DIMACS encoded integer.
+fn lit(value: i64) -> Literal {
+    let index = value.unsigned_abs() as usize - 1;
+    let sign = if value > 0 { Sign::POS } else { Sign::NEG };
+    Literal::new(Variable::from_index(index), sign)
+}
+
+/// Returns the literals for the DIMACS encoded integers.
+fn clause<const N: usize>(values: [i64; N]) -> Vec<Literal> {
+    values.into_iter().map(lit).collect()
+}
+
+#[test]
+fn parse_works() {
+    let wcnf = Wcnf::parse(
+        br"
+        c A comment line.
+        h 1 -2 0
+        h 3 0
+        5 -1 0
+        c Another comment line.
+        2 2 4 0
+        ",
+    )
+    .unwrap();
+    assert_eq!(wcnf.len_variables(), 4);
+    assert_eq!(wcnf.hard_clauses(), &[clause([1, -2]), clause([3])]);
+    assert_eq!(
+        wcnf.soft_clauses(),
+        &[(5, clause([-1])), (2, clause([2, 4]))]
+    );
+}
+
+#[test]
+fn parse_old_format_works() {
+    let wcnf = Wcnf::parse(
+        br"
+        c A comment line.
+        p wcnf 5 3 100
+        100 1 -2 0
+        5 -1 0
+        2 2 4 0
+        ",
+    )
+    .unwrap();
+    assert_eq!(wcnf.len_variables(), 5);
+    assert_eq!(wcnf.hard_clauses(), &[clause([1, -2])]);
+    assert_eq!(
+        wcnf.soft_clauses(),
+        &[(5, clause([-1])), (2, clause([2, 4]))]
+    );
+    let wcnf = Wcnf::parse(b"p wcnf 2 2\n3 1 0\n100 -2 0").unwrap();
+    assert!(wcnf.hard_clauses().is_empty());
+    assert_eq!(wcnf.soft_clauses().len(), 2);
+}
+
+#[test]
+fn parse_errors_work() {
+    assert_eq!(
+        Wcnf::parse(b"h 1 2"),
+        Err(WcnfError::UnterminatedClause { line: 1 })
+    );
+    assert_eq!(
+        Wcnf::parse(b"h 1 0\nx 1 0"),
+        Err(WcnfError::UnexpectedToken { line: 2 })
+    );
+    assert_eq!(
+        Wcnf::parse(b"h 1 0 2"),
+        Err(WcnfError::UnexpectedToken { line: 1 })
+    );
+    assert_eq!(
+        Wcnf::parse(b"99999999999999999999 1 0"),
+        Err(WcnfError::OutOfRangeInteger { line: 1 })
+    );
+    assert_eq!(
+        Wcnf::parse(b"h 1 0\np wcnf 1 1"),
+        Err(WcnfError::InvalidHeader { line: 2 })
+    );
+    assert_eq!(
+        Wcnf::parse(b"p cnf 1 1"),
+        Err(WcnfError::InvalidHeader { line: 1 })
+    );
+}
+
+const ALGORITHMS: [MaxSatAlgorithm; 2] =
+    [MaxSatAlgorithm::LinearSatUnsat, MaxSatAlgorithm::Oll];
+
+#[test]
+fn solve_works() {
+    let wcnf = Wcnf::parse(
+        br"
+        h -1 -2 0
+        h -2 -3 0
+        h -1 -3 0
+        3 1 0
+        2 2 0
+        2 3 0
+        1 1 2 3 0
+        ",
+    )
+    .unwrap();
+    for algorithm in ALGORITHMS {
+        let mut solutions = Vec::new();
+        let result =
+            MaxSatSolver::from_wcnf(&wcnf).solve(algorithm, |cost| solutions.push(cost));
+        match result {
+            MaxSatResult::Optimum { cost, model } => {
+                assert_eq!(cost, 4);
+                assert_eq!(model.is_satisfied(lit(1)), Ok(true));
+            }
+            MaxSatResult::Unsat => panic!("encountered unexpected UNSAT result"),
+        }
+        assert_eq!(solutions.last(), Some(&4));
+        assert!(solutions.windows(2).all(|costs| costs[0] > costs[1]));
+    }
+}
+
+#[test]
+fn unsat_hard_clauses_work() {
+    let wcnf = Wcnf::parse(b"h 1 0\nh -1 0\n1 2 0").unwrap();
+    for algorithm in ALGORITHMS {
+        let result = MaxSatSolver::from_wcnf(&wcnf).solve(algorithm, |_| ());
+        assert_eq!(result, MaxSatResult::Unsat);
+    }
+}
+
+#[test]
+fn empty_soft_clauses_work() {
+    let wcnf = Wcnf::parse(b"h 1 0\n3 0\n2 -1 0").unwrap();
+    for algorithm in ALGORITHMS {
+        let result = MaxSatSolver::from_wcnf(&wcnf).solve(algorithm, |_| ());
+        assert!(matches!(result, MaxSatResult::Optimum { cost: 5, .. }));
+    }
+}
+
+/// Returns `true` if the clause is satisfied by the assignment of the bits.
+fn is_satisfied(clause: &[Literal], assignment: u32) -> bool {
+    clause.iter().any(|literal| {
+        let value = assignment & (1 << literal.variable().into_index()) != 0;
+        value == (literal.sign() == Sign::POS)
+    })
+}
+
+/// Returns the cost of the optimal solution by enumerating all assignments.
+fn brute_force_optimum(wcnf: &Wcnf) -> Option<u64> {
+    (0..1_u32 << wcnf.len_variables())
+        .filter(|&assignment| {
+            wcnf.hard_clauses()
+                .iter()
+                .all(|clause| is_satisfied(clause, assignment))
+        })
+        .map(|assignment| {
+            wcnf.soft_clauses()
+                .iter()
+                .filter(|(_, clause)| !is_satisfied(clause, assignment))
+                .map(|&(weight, _)| weight)
+                .sum()
+        })
+        .min()
+}
+
+#[test]
+fn random_instances_agree_with_brute_force() {
+    let mut rng = Lcg(42);
+    for _ in 0..200 {
+        let len_variables = 1 + rng.next(8);
+        let mut input = String::new();
+        let random_clause = |input: &mut String, rng: &mut Lcg| {
+            let mut clause = Vec::new();
+            for _ in 0..1 + rng.next(3) {
+                let value = 1 + rng.next(len_variables) as i64;
+                let value = if rng.next(2) == 0 { -value } else { value };
+                if !clause.contains(&-value) {
+                    clause.push(value);
+                }
+            }
+            for value in clause {
+                input.push_str(&format!(" {}", value));
+            }
+            input.push_str(" 0\n");
+        };
+        for _ in 0..rng.next(2 * len_variables) {
+            input.push('h');
+            random_clause(&mut input, &mut rng);
+        }
+        for _ in 0..1 + rng.next(3 * len_variables) {
+            input.push_str(&format!("{}", 1 + rng.next(5)));
+            random_clause(&mut input, &mut rng);
+        }
+        let wcnf = Wcnf::parse(input.as_bytes()).unwrap();
+        let expected = brute_force_optimum(&wcnf);
+        for algorithm in ALGORITHMS {
+            let mut maxsat = MaxSatSolver::from_wcnf(&wcnf);
+            match maxsat.solve(algorithm, |_| ()) {
+                MaxSatResult::Optimum { cost, model } => {
+                    assert_eq!(Some(cost), expected, "{:?} on {}", algorithm, input);
+                    assert_eq!(maxsat.cost(&model), cost);
+                    for clause in wcnf.hard_clauses() {
+                        let is_satisfied = clause
+                            .iter()
+                            .any(|&literal| model.is_satisfied(literal) == Ok(true));
+                        assert!(is_satisfied);
+                    }
+                }
+                MaxSatResult::Unsat => assert_eq!(expected, None),
+            }
+        }
+    }
+}
@@ -0,0 +1,209 @@
+use crate::{
+    Literal,
+    Sign,
+    Variable,
+};
+use bounded::Index as _;
+use core::{
+    fmt,
+    fmt::Display,
+};
+
+/// Errors that can be encountered when parsing `.wcnf` input.
+///
+/// # Note
+///
+/// All lines are counted starting at `1`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WcnfError {
+    /// Encountered a token that is neither a valid weight nor a valid literal.
+    UnexpectedToken { line: usize },
+    /// An integer does not fit into its type.
+    OutOfRangeInteger { line: usize },
+    /// A variable index exceeds the supported number of variables.
+    InvalidVariable { line: usize },
+    /// A clause is not terminated by `0` at the end of its line.
+    UnterminatedClause { line: usize },
+    /// The `p wcnf` header is malformed or does not precede all clauses.
+    InvalidHeader { line: usize },
+}
+
+impl Display for WcnfError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnexpectedToken { line } => {
+                write!(f, "encountered unexpected token in line {}", line)
+            }
+            Self::OutOfRangeInteger { line } => {
+                write!(f, "encountered out of range integer in line {}", line)
+            }
+            Self::InvalidVariable { line } => {
+                write!(f, "encountered invalid variable in line {}", line)
+            }
+            Self::UnterminatedClause { line } => {
+                write!(f, "encountered unterminated clause in line {}", line)
+            }
+            Self::InvalidHeader { line } => {
+                write!(f, "encountered invalid header in line {}", line)
+            }
+        }
+    }
+}
+
+/// A weighted partial MaxSAT instance.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Wcnf {
+    /// The number of variables used by the instance.
+    len_variables: usize,
+    /// The clauses that must be satisfied.
+    hard_clauses: Vec<Vec<Literal>>,
+    /// The weighted clauses whose weights are to be paid if they are falsified.
+    soft_clauses: Vec<(u64, Vec<Literal>)>,
+}
+
+impl Wcnf {
+    /// Returns the number of variables used by the instance.
+    pub fn len_variables(&self) -> usize {
+        self.len_variables
+    }
+
+    /// Returns the clauses that must be satisfied.
+    pub fn hard_clauses(&self) -> &[Vec<Literal>] {
+        &self.hard_clauses
+    }
+
+    /// Returns the weighted soft clauses.
+    pub fn soft_clauses(&self) -> &[(u64, Vec<Literal>)] {
+        &self.soft_clauses
+    }
+
+    /// Parses `.wcnf` input of the MaxSAT evaluation formats.
+    ///
+    /// # Note
+    ///
+    /// - Comments start with `c` and span until the end of the line.
+    /// - In the format used since 2022 hard clauses start with `h` and soft
+    ///   clauses with their weight.
+    /// - In the older format the `p wcnf <vars> <clauses> [<top>]` header precedes
+    ///   all clauses which start with their weight. Clauses with a weight of at
+    ///   least `top` are hard.
+    /// - Every clause is terminated by `0` at the end of its line.
+    ///
+    /// # Errors
+    ///
+    /// If the input is no valid `.wcnf` format.
+    pub fn parse(input: &[u8]) -> Result<Self, WcnfError> {
+        let mut wcnf = Self::default();
+        let mut top = None;
+        let mut is_header_allowed = true;
+        for (index, line) in input.split(|&byte| byte == b'\n').enumerate() {
+            let line_number = index + 1;
+            let mut tokens = line
+                .split(|byte| byte.is_ascii_whitespace())
+                .filter(|token| !token.is_empty())
+                .peekable();
+            match tokens.peek() {
+                None => continue,
+                Some(token) if token.starts_with(b"c") => continue,
+                Some(&b"p") => {
+                    if !is_header_allowed {
+                        return Err(WcnfError::InvalidHeader { line: line_number })
+                    }
+                    top = wcnf.parse_header(tokens, line_number)?;
+                }
+                Some(_) => wcnf.parse_clause(tokens, top, line_number)?,
+            }
+            is_header_allowed = false;
+        }
+        Ok(wcnf)
+    }
+
+    /// Parses the `p wcnf <vars> <clauses> [<top>]` header and returns `top`.
+    fn parse_header<'a, I>(
+        &mut self,
+        tokens: I,
+        line: usize,
+    ) -> Result<Option<u64>, WcnfError>
+    where
+        I: Iterator<Item = &'a [u8]>,
+    {
+        let tokens = tokens.collect::<Vec<_>>();
+        let invalid_header = WcnfError::InvalidHeader { line };
+        if !(4..=5).contains(&tokens.len()) || tokens[1] != b"wcnf" {
+            return Err(invalid_header)
+        }
+        let len_variables = parse_integer::<usize>(tokens[2], line)?;
+        if len_variables > Variable::MAX_INDEX + 1 {
+            return Err(WcnfError::InvalidVariable { line })
+        }
+        self.len_variables = len_variables;
+        parse_integer::<usize>(tokens[3], line)?;
+        tokens
+            .get(4)
+            .map(|top| parse_integer::<u64>(top, line))
+            .transpose()
+    }
+
+    /// Parses a hard or soft clause.
+    fn parse_clause<'a, I>(
+        &mut self,
+        mut tokens: I,
+        top: Option<u64>,
+        line: usize,
+    ) -> Result<(), WcnfError>
+    where
+        I: Iterator<Item = &'a [u8]>,
+    {
+        let weight = match tokens.next() {
+            Some(b"h") => None,
+            Some(weight) => {
+                Some(parse_integer::<u64>(weight, line)?)
+                    .filter(|&weight| top.is_none_or(|top| weight < top))
+            }
+            None => return Err(WcnfError::UnexpectedToken { line }),
+        };
+        let mut literals = Vec::new();
+        loop {
+            let token = tokens
+                .next()
+                .ok_or(WcnfError::UnterminatedClause { line })?;
+            let value = parse_integer::<i64>(token, line)?;
+            if value == 0 {
+                break
+            }
+            let index = usize::try_from(value.unsigned_abs() - 1)
+                .ok()
+                .filter(|&index| index <= Variable::MAX_INDEX)
+                .ok_or(WcnfError::InvalidVariable { line })?;
+            let sign = if value > 0 { Sign::POS } else { Sign::NEG };
+            literals.push(Literal::new(Variable::from_index(index), sign));
+            self.len_variables = self.len_variables.max(index + 1);
+        }
+        if tokens.next().is_some() {
+            return Err(WcnfError::UnexpectedToken { line })
+        }
+        match weight {
+            None => self.hard_clauses.push(literals),
+            Some(weight) => self.soft_clauses.push((weight, literals)),
+        }
+        Ok(())
+    }
+}
+
+/// Parses the token as a decimal integer.
+fn parse_integer<T>(token: &[u8], line: usize) -> Result<T, WcnfError>
+where
+    T: core::str::FromStr<Err = core::num::ParseIntError>,
+{
+    let token =
+        core::str::from_utf8(token).map_err(|_| WcnfError::UnexpectedToken { line })?;
+    token.parse::<T>().map_err(|error| {
+        match error.kind() {
+            core::num::IntErrorKind::PosOverflow
+            | core::num::IntErrorKind::NegOverflow => {
+                WcnfError::OutOfRangeInteger { line }
+            }
+            _ => WcnfError::UnexpectedToken { line },
+        }
+    })
+}
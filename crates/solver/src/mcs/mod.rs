@@ -0,0 +1,319 @@
+//! Enumeration of minimal correction subsets (MCS) of soft clauses.
+
+#[cfg(test)]
+mod tests;
+
+use crate::{
+    encodings,
+    maxsat::Wcnf,
+    retract,
+    ClauseHandle,
+    Literal,
+    Model,
+    SolveResult,
+    Solver,
+};
+
+/// The algorithm used to compute a single minimal correction subset.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub enum McsAlgorithm {
+    /// Tests the falsified soft clauses one by one.
+    ///
+    /// Every falsified soft clause is assumed together with all satisfied soft
+    /// clauses. It belongs to the MCS if this is unsatisfiable.
+    Linear,
+    /// The clause D algorithm.
+    ///
+    /// Assumes all satisfied soft clauses and requires at least one of the
+    /// falsified soft clauses to be satisfied until this is unsatisfiable.
+    #[default]
+    Cld,
+}
+
+/// Enumerates the minimal correction subsets of soft clauses under hard clauses.
+///
+/// A minimal correction subset is a subset of the soft clauses whose removal
+/// makes the remaining soft clauses satisfiable together with the hard clauses
+/// and that is minimal with respect to this property.
+///
+/// # Note
+///
+/// Every soft clause `C` is added to the underlying solver as `C ∨ ¬s` with its
+/// own selector literal `s` that enforces the clause if it is assumed.
+/// Soft clauses are identified by the index in the order in which they were added.
+#[derive(Debug, Default)]
+pub struct McsSolver {
+    /// The underlying SAT solver that stores the hard and selected soft clauses.
+    solver: Solver,
+    /// The soft clauses in the order in which they were added.
+    soft_clauses: Vec<Vec<Literal>>,
+    /// The selector literal of every soft clause or `None` for tautological clauses.
+    selectors: Vec<Option<Literal>>,
+}
+
+impl McsSolver {
+    /// Creates a new MCS solver for the instance.
+    ///
+    /// # Note
+    ///
+    /// The weights of the soft clauses are ignored.
+    pub fn from_wcnf(wcnf: &Wcnf) -> Self {
+        let mut mcs = Self::default();
+        mcs.new_literal_chunk(wcnf.len_variables());
+        for clause in wcnf.hard_clauses() {
+            mcs.add_hard_clause(clause);
+        }
+        for (_, clause) in wcnf.soft_clauses() {
+            mcs.add_soft_clause(clause);
+        }
+        mcs
+    }
+
+    /// Allocates the given amount of new literals and returns them.
+    pub fn new_literal_chunk(&mut self, amount: usize) -> Vec<Literal> {
+        self.solver.new_literal_chunk(amount).into_iter().collect()
+    }
+
+    /// Adds a clause that must be satisfied.
+    ///
+    /// # Panics
+    ///
+    /// If any of the literals has not been registered.
+    pub fn add_hard_clause(&mut self, literals: &[Literal]) {
+        encodings::add_clause(&mut self.solver, literals);
+    }
+
+    /// Adds a clause that may be dropped and returns its index.
+    ///
+    /// # Panics
+    ///
+    /// If any of the literals has not been registered.
+    pub fn add_soft_clause(&mut self, literals: &[Literal]) -> usize {
+        let index = self.soft_clauses.len();
+        self.soft_clauses.push(literals.to_vec());
+        let is_tautological =
+            literals.iter().any(|&literal| literals.contains(&!literal));
+        if is_tautological {
+            // Tautological clauses are never part of an MCS.
+            self.selectors.push(None);
+            return index
+        }
+        let selector = self.solver.new_literal();
+        self.solver.consume_clause(
+            literals
+                .iter()
+                .copied()
+                .chain([!selector])
+                .collect::<Vec<_>>(),
+        );
+        self.selectors.push(Some(selector));
+        index
+    }
+
+    /// Returns the soft clause with the given index.
+    ///
+    /// # Panics
+    ///
+    /// If there is no soft clause with the index.
+    pub fn soft_clause(&self, index: usize) -> &[Literal] {
+        &self.soft_clauses[index]
+    }
+
+    /// Returns the number of soft clauses.
+    pub fn len_soft_clauses(&self) -> usize {
+        self.soft_clauses.len()
+    }
+
+    /// Returns an iterator over all minimal correction subsets.
+    ///
+    /// Yields nothing if the hard clauses are unsatisfiable and a single empty
+    /// subset if all soft clauses can be satisfied together.
+    pub fn enumerate(&mut self, algorithm: McsAlgorithm) -> McsEnumerator<'_> {
+        McsEnumerator::new(self, algorithm)
+    }
+
+    /// Returns the selector literal of the non-tautological soft clause.
+    fn selector(&self, index: usize) -> Literal {
+        self.selectors[index].expect("encountered unexpected tautological clause")
+    }
+
+    /// Returns `true` if the soft clause is satisfied by the model.
+    fn is_satisfied(&self, index: usize, model: &Model) -> bool {
+        self.soft_clauses[index]
+            .iter()
+            .any(|&literal| model.is_satisfied(literal) == Ok(true))
+    }
+
+    /// Solves under the assumptions and returns the model if any.
+    fn solve(&mut self, assumptions: &[Literal]) -> Option<Model> {
+        let result = self
+            .solver
+            .solve(assumptions.iter().copied())
+            .expect("encountered unexpected error during solving");
+        match result {
+            SolveResult::Sat(sat) => Some(sat.model().clone()),
+            SolveResult::Unsat => None,
+            SolveResult::Unknown => panic!("encountered unexpected interrupted search"),
+        }
+    }
+}
+
+/// Iterator over the minimal correction subsets created by [`McsSolver::enumerate`].
+///
+/// Yields the indices of the soft clauses of every MCS in ascending order.
+///
+/// # Note
+///
+/// Every found MCS is blocked by a clause that requires at least one of its
+/// soft clauses to be satisfied. The blocking clauses are guarded by an
+/// activation literal that is assumed during enumeration. They are removed and
+/// the activation variable is released once the iterator is dropped.
+#[derive(Debug)]
+pub struct McsEnumerator<'a> {
+    /// The solver whose minimal correction subsets are enumerated.
+    mcs: &'a mut McsSolver,
+    /// The algorithm used to compute every single MCS.
+    algorithm: McsAlgorithm,
+    /// The literal that activates the blocking clauses.
+    activation: Literal,
+    /// The handles of the blocking clauses.
+    blocking: Vec<ClauseHandle>,
+    /// Yields `true` if all minimal correction subsets have been enumerated.
+    is_exhausted: bool,
+}
+
+impl<'a> McsEnumerator<'a> {
+    /// Creates a new MCS enumerator for the solver.
+    fn new(mcs: &'a mut McsSolver, algorithm: McsAlgorithm) -> Self {
+        let activation = mcs.solver.new_literal();
+        Self {
+            mcs,
+            algorithm,
+            activation,
+            blocking: Vec::new(),
+            is_exhausted: false,
+        }
+    }
+
+    /// Returns the assumptions that enforce the satisfied soft clauses.
+    fn assumptions(&self, satisfied: &[usize]) -> Vec<Literal> {
+        core::iter::once(self.activation)
+            .chain(satisfied.iter().map(|&index| self.mcs.selector(index)))
+            .collect()
+    }
+
+    /// Moves all falsified soft clauses that are satisfied by the model.
+    fn update(
+        &self,
+        satisfied: &mut Vec<usize>,
+        falsified: &mut Vec<usize>,
+        model: &Model,
+    ) {
+        falsified.retain(|&index| {
+            let is_satisfied = self.mcs.is_satisfied(index, model);
+            if is_satisfied {
+                satisfied.push(index);
+            }
+            !is_satisfied
+        });
+    }
+
+    /// Computes an MCS by testing the falsified soft clauses one by one.
+    fn linear(
+        &mut self,
+        mut satisfied: Vec<usize>,
+        mut remaining: Vec<usize>,
+    ) -> Vec<usize> {
+        let mut mcs = Vec::new();
+        while let Some(index) = remaining.pop() {
+            let mut assumptions = self.assumptions(&satisfied);
+            assumptions.push(self.mcs.selector(index));
+            match self.mcs.solve(&assumptions) {
+                Some(model) => {
+                    satisfied.push(index);
+                    self.update(&mut satisfied, &mut remaining, &model);
+                }
+                None => mcs.push(index),
+            }
+        }
+        mcs
+    }
+
+    /// Computes an MCS by requiring one of the falsified soft clauses to be
+    /// satisfied until this is impossible.
+    ///
+    /// # Note
+    ///
+    /// The clause requiring one of the falsified soft clauses is guarded by a
+    /// literal that is released after every iteration so that it is reused.
+    fn cld(
+        &mut self,
+        mut satisfied: Vec<usize>,
+        mut falsified: Vec<usize>,
+    ) -> Vec<usize> {
+        while !falsified.is_empty() {
+            let guard = self.mcs.solver.new_literal();
+            let mut clause = vec![!guard];
+            for &index in &falsified {
+                for &literal in &self.mcs.soft_clauses[index] {
+                    if !clause.contains(&literal) {
+                        clause.push(literal);
+                    }
+                }
+            }
+            let handle = self.mcs.solver.consume_unscoped_clause(clause);
+            let mut assumptions = self.assumptions(&satisfied);
+            assumptions.push(guard);
+            let model = self.mcs.solve(&assumptions);
+            retract::retract_guarded(&mut self.mcs.solver, [handle], guard);
+            match model {
+                Some(model) => self.update(&mut satisfied, &mut falsified, &model),
+                None => break,
+            }
+        }
+        falsified
+    }
+
+    /// Adds the clause that blocks the MCS and all of its supersets.
+    fn block(&mut self, mcs: &[usize]) {
+        let clause = core::iter::once(!self.activation)
+            .chain(mcs.iter().map(|&index| self.mcs.selector(index)))
+            .collect::<Vec<_>>();
+        let handle = self.mcs.solver.consume_unscoped_clause(clause);
+        self.blocking.push(handle);
+    }
+}
+
+impl<'a> Iterator for McsEnumerator<'a> {
+    type Item = Vec<usize>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.is_exhausted {
+            return None
+        }
+        let model = match self.mcs.solve(&[self.activation]) {
+            Some(model) => model,
+            None => {
+                self.is_exhausted = true;
+                return None
+            }
+        };
+        let (satisfied, falsified) = (0..self.mcs.soft_clauses.len())
+            .filter(|&index| self.mcs.selectors[index].is_some())
+            .partition::<Vec<_>, _>(|&index| self.mcs.is_satisfied(index, &model));
+        let mut mcs = match self.algorithm {
+            McsAlgorithm::Linear => self.linear(satisfied, falsified),
+            McsAlgorithm::Cld => self.cld(satisfied, falsified),
+        };
+        mcs.sort_unstable();
+        self.block(&mcs);
+        Some(mcs)
+    }
+}
+
+impl<'a> Drop for McsEnumerator<'a> {
+    fn drop(&mut self) {
+        let blocking = core::mem::take(&mut self.blocking);
+        retract::retract_guarded(&mut self.mcs.solver, blocking, self.activation);
+    }
+}
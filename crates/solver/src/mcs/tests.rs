@@ -0,0 +1,165 @@
+use super::*;
+use crate::{
+    tests::Lcg,
+    Sign,
+};
+use bounded::Index as _;
+
+/// Returns all minimal correction subsets by brute force.
+fn brute_force_mcses(
+    len_variables: usize,
+    hard: &[Vec<Literal>],
+    soft: &[Vec<Literal>],
+) -> Vec<Vec<usize>> {
+    let is_satisfied = |clause: &[Literal], assignment: u32| {
+        clause.iter().any(|literal| {
+            let value = assignment & (1 << literal.variable().into_index()) != 0;
+            value == (literal.sign() == Sign::POS)
+        })
+    };
+    let mut falsified = (0..1_u32 << len_variables)
+        .filter(|&assignment| hard.iter().all(|clause| is_satisfied(clause, assignment)))
+        .map(|assignment| {
+            (0..soft.len())
+                .filter(|&index| !is_satisfied(&soft[index], assignment))
+                .fold(0_u32, |mask, index| mask | 1 << index)
+        })
+        .collect::<Vec<_>>();
+    falsified.sort_unstable();
+    falsified.dedup();
+    let mut mcses = falsified
+        .iter()
+        .filter(|&&mask| {
+            !falsified
+                .iter()
+                .any(|&other| other != mask && other & mask == other)
+        })
+        .map(|&mask| {
+            (0..soft.len())
+                .filter(|&index| mask & 1 << index != 0)
+                .collect()
+        })
+        .collect::<Vec<Vec<usize>>>();
+    mcses.sort();
+    mcses
+}
+
+#[test]
+fn enumerate_works() {
+    for algorithm in [McsAlgorithm::Linear, McsAlgorithm::Cld] {
+        let mut mcs = McsSolver::default();
+        let vars = mcs.new_literal_chunk(2);
+        let (a, b) = (vars[0], vars[1]);
+        mcs.add_hard_clause(&[!a, !b]);
+        mcs.add_soft_clause(&[a]);
+        mcs.add_soft_clause(&[b]);
+        mcs.add_soft_clause(&[a, b]);
+        mcs.add_soft_clause(&[a, !a]);
+        let mut mcses = mcs.enumerate(algorithm).collect::<Vec<_>>();
+        mcses.sort();
+        assert_eq!(mcses, [vec![0], vec![1]]);
+        // The blocking clauses no longer apply after the enumeration.
+        assert_eq!(mcs.enumerate(algorithm).count(), 2);
+    }
+}
+
+#[test]
+fn special_cases_work() {
+    for algorithm in [McsAlgorithm::Linear, McsAlgorithm::Cld] {
+        let mut mcs = McsSolver::default();
+        let vars = mcs.new_literal_chunk(1);
+        mcs.add_soft_clause(&[vars[0]]);
+        assert_eq!(mcs.enumerate(algorithm).collect::<Vec<_>>(), [vec![]]);
+        mcs.add_soft_clause(&[]);
+        assert_eq!(mcs.enumerate(algorithm).collect::<Vec<_>>(), [vec![1]]);
+        mcs.add_hard_clause(&[!vars[0]]);
+        assert_eq!(mcs.enumerate(algorithm).collect::<Vec<_>>(), [vec![0, 1]]);
+        mcs.add_hard_clause(&[vars[0]]);
+        assert_eq!(mcs.enumerate(algorithm).next(), None);
+    }
+}
+
+#[test]
+fn from_wcnf_works() {
+    let wcnf = Wcnf::parse(b"h 1 2 0\n3 -1 0\n2 -2 0\n1 1 0").unwrap();
+    let mut mcs = McsSolver::from_wcnf(&wcnf);
+    assert_eq!(mcs.len_soft_clauses(), 3);
+    let mut mcses = mcs.enumerate(McsAlgorithm::Cld).collect::<Vec<_>>();
+    mcses.sort();
+    assert_eq!(mcses, [vec![0], vec![1, 2]]);
+}
+
+#[test]
+fn random_mcses_agree_with_brute_force() {
+    let mut rng = Lcg(42);
+    for _ in 0..200 {
+        let len_vars = 1 + rng.next(6);
+        let mut mcs = McsSolver::default();
+        let vars = mcs.new_literal_chunk(len_vars);
+        let random_clause = |rng: &mut Lcg| {
+            (0..1 + rng.next(3))
+                .map(|_| {
+                    let literal = vars[rng.next(len_vars)];
+                    if rng.next(2) == 0 {
+                        !literal
+                    } else {
+                        literal
+                    }
+                })
+                .collect::<Vec<_>>()
+        };
+        let hard = (0..rng.next(2 * len_vars))
+            .map(|_| random_clause(&mut rng))
+            .collect::<Vec<_>>();
+        let soft = (0..1 + rng.next(8))
+            .map(|_| random_clause(&mut rng))
+            .collect::<Vec<_>>();
+        for clause in &hard {
+            mcs.add_hard_clause(clause);
+        }
+        for clause in &soft {
+            mcs.add_soft_clause(clause);
+        }
+        let expected = brute_force_mcses(len_vars, &hard, &soft);
+        for algorithm in [McsAlgorithm::Linear, McsAlgorithm::Cld] {
+            let mut mcses = mcs.enumerate(algorithm).collect::<Vec<_>>();
+            mcses.sort();
+            assert_eq!(mcses, expected);
+        }
+    }
+}
+
+#[test]
+fn enumeration_leaves_no_clauses_and_variables_behind() {
+    for algorithm in [McsAlgorithm::Linear, McsAlgorithm::Cld] {
+        let mut mcs = McsSolver::default();
+        let vars = mcs.new_literal_chunk(3);
+        let (a, b, c) = (vars[0], vars[1], vars[2]);
+        mcs.add_hard_clause(&[!a, !b]);
+        mcs.add_hard_clause(&[!b, !c]);
+        for literal in [a, b, c] {
+            mcs.add_soft_clause(&[literal]);
+        }
+        let len_clauses = mcs
+            .solver
+            .clause_database()
+            .live_clauses()
+            .irredundant()
+            .count();
+        assert_eq!(mcs.enumerate(algorithm).count(), 2);
+        // The variables of the first enumeration are reused by the following ones.
+        let len_variables = mcs.solver.len_variables();
+        for _ in 0..3 {
+            assert_eq!(mcs.enumerate(algorithm).count(), 2);
+            assert_eq!(mcs.solver.len_variables(), len_variables);
+            assert_eq!(
+                mcs.solver
+                    .clause_database()
+                    .live_clauses()
+                    .irredundant()
+                    .count(),
+                len_clauses
+            );
+        }
+    }
+}
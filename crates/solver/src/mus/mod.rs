@@ -0,0 +1,300 @@
+//! Extraction of minimal unsatisfiable subsets (MUS) of clauses.
+
+#[cfg(test)]
+mod tests;
+
+use crate::{
+    builder::{
+        CnfBuilder,
+        ConsumeCnf,
+    },
+    Error,
+    Literal,
+    LiteralChunk,
+    SolveResult,
+    Solver,
+};
+use ahash::AHashMap;
+use bounded::Index as _;
+use cnf_parser::{
+    Error as CnfError,
+    Input,
+};
+
+/// The state of a clause during MUS extraction.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+enum ClauseState {
+    /// The clause may or may not be part of the MUS.
+    Unknown,
+    /// The clause is not part of the MUS.
+    Removed,
+    /// The clause is part of the MUS.
+    Critical,
+}
+
+/// Extracts minimal unsatisfiable subsets of clauses.
+///
+/// # Note
+///
+/// Every clause `C` is added to the underlying solver as `C ∨ ¬s` with its
+/// own selector literal `s` that activates the clause if it is assumed.
+/// Clauses are identified by the index in the order in which they were added.
+#[derive(Debug, Default)]
+pub struct MusExtractor {
+    /// The solver that stores the clauses with their selector literals.
+    solver: Solver,
+    /// The number of variables of the clauses.
+    len_variables: usize,
+    /// The clauses in the order in which they were added.
+    clauses: Vec<Vec<Literal>>,
+    /// The selector literal of every clause or `None` for tautological clauses.
+    selectors: Vec<Option<Literal>>,
+    /// The index of the clause of every selector literal.
+    selected: AHashMap<Literal, usize>,
+}
+
+impl ConsumeCnf for MusExtractor {
+    fn new_variables(&mut self, amount: usize) {
+        self.new_literal_chunk(amount);
+    }
+
+    fn consume_cnf_clause(&mut self, literals: &[Literal]) {
+        self.add_clause(literals);
+    }
+}
+
+impl MusExtractor {
+    /// Consumes the `.cnf` input and feeds it to the returned MUS extractor.
+    ///
+    /// # Errors
+    ///
+    /// - If the input is no valid `.cnf` format.
+    /// - If the input encodes an invalid CNF formula.
+    pub fn from_cnf<I>(input: &mut I) -> Result<Self, CnfError<Error>>
+    where
+        I: Input,
+    {
+        let mut builder = CnfBuilder::default();
+        cnf_parser::parse_cnf(input, &mut builder)?;
+        Ok(builder.finalize())
+    }
+
+    /// Allocates the given amount of new literals and returns them.
+    ///
+    /// # Panics
+    ///
+    /// If any clause has been added before.
+    pub fn new_literal_chunk(&mut self, amount: usize) -> LiteralChunk {
+        assert!(
+            self.clauses.is_empty(),
+            "encountered new literals after adding clauses"
+        );
+        self.len_variables += amount;
+        self.solver.new_literal_chunk(amount)
+    }
+
+    /// Returns the number of variables of the clauses.
+    pub fn len_variables(&self) -> usize {
+        self.len_variables
+    }
+
+    /// Adds the clause and returns its index.
+    ///
+    /// # Panics
+    ///
+    /// If any of the literals has not been registered.
+    pub fn add_clause(&mut self, literals: &[Literal]) -> usize {
+        for literal in literals {
+            if literal.variable().into_index() >= self.len_variables {
+                panic!("encountered invalid literal: {}", literal)
+            }
+        }
+        let index = self.clauses.len();
+        self.clauses.push(literals.to_vec());
+        let is_tautological =
+            literals.iter().any(|&literal| literals.contains(&!literal));
+        if is_tautological {
+            // Tautological clauses are never part of a MUS.
+            self.selectors.push(None);
+            return index
+        }
+        let selector = self.solver.new_literal();
+        self.solver.consume_clause(
+            literals
+                .iter()
+                .copied()
+                .chain([!selector])
+                .collect::<Vec<_>>(),
+        );
+        self.selectors.push(Some(selector));
+        self.selected.insert(selector, index);
+        index
+    }
+
+    /// Returns the clause with the given index.
+    ///
+    /// # Panics
+    ///
+    /// If there is no clause with the index.
+    pub fn clause(&self, index: usize) -> &[Literal] {
+        &self.clauses[index]
+    }
+
+    /// Returns the indices of the clauses of a minimal unsatisfiable subset.
+    ///
+    /// Returns `None` if the clauses are satisfiable. The returned indices are
+    /// sorted in ascending order.
+    ///
+    /// # Note
+    ///
+    /// Uses deletion-based extraction: every clause that is not yet known to
+    /// be part of the MUS is tested by solving without it.
+    ///
+    /// - If the remaining clauses are unsatisfiable the clause is removed and so
+    ///   are all clauses outside of the unsatisfiable core (clause-set refinement).
+    /// - Otherwise the clause is part of the MUS and the found model is rotated
+    ///   by flipping the literals of the clause to find further MUS clauses that
+    ///   are the only clause falsified by the rotated model (model rotation).
+    pub fn extract(&mut self) -> Option<Vec<usize>> {
+        let mut states = self
+            .selectors
+            .iter()
+            .map(|selector| {
+                match selector {
+                    Some(_) => ClauseState::Unknown,
+                    None => ClauseState::Removed,
+                }
+            })
+            .collect::<Vec<_>>();
+        if self.solve_without(&mut states, None).is_some() {
+            return None
+        }
+        let occurrences = self.occurrences();
+        while let Some(index) = states
+            .iter()
+            .position(|&state| state == ClauseState::Unknown)
+        {
+            if let Some(values) = self.solve_without(&mut states, Some(index)) {
+                states[index] = ClauseState::Critical;
+                self.rotate(&mut states, &occurrences, index, values);
+            }
+        }
+        let mus = states
+            .iter()
+            .enumerate()
+            .filter(|(_, &state)| state == ClauseState::Critical)
+            .map(|(index, _)| index)
+            .collect();
+        Some(mus)
+    }
+
+    /// Returns the indices of the non-tautological clauses for every literal.
+    fn occurrences(&self) -> AHashMap<Literal, Vec<usize>> {
+        let mut occurrences = AHashMap::<Literal, Vec<usize>>::default();
+        for (index, clause) in self.clauses.iter().enumerate() {
+            if self.selectors[index].is_none() {
+                continue
+            }
+            for &literal in clause {
+                let indices = occurrences.entry(literal).or_default();
+                if indices.last() != Some(&index) {
+                    indices.push(index);
+                }
+            }
+        }
+        occurrences
+    }
+
+    /// Solves the clauses that have not been removed except for the excluded clause.
+    ///
+    /// Returns the values of the variables of a model if any.
+    /// Otherwise removes the excluded clause as well as all unknown clauses
+    /// that are not part of the unsatisfiable core.
+    fn solve_without(
+        &mut self,
+        states: &mut [ClauseState],
+        excluded: Option<usize>,
+    ) -> Option<Vec<bool>> {
+        let assumptions = states
+            .iter()
+            .enumerate()
+            .filter(|&(index, &state)| {
+                state != ClauseState::Removed && Some(index) != excluded
+            })
+            .map(|(index, _)| {
+                self.selectors[index].expect("encountered unexpected tautological clause")
+            })
+            .collect::<Vec<_>>();
+        let result = self
+            .solver
+            .solve(assumptions)
+            .expect("encountered unexpected error during solving");
+        if let SolveResult::Sat(sat) = result {
+            let values = sat
+                .model()
+                .into_iter()
+                .take(self.len_variables)
+                .map(|literal| literal.sign().is_pos())
+                .collect();
+            return Some(values)
+        }
+        if let Some(excluded) = excluded {
+            states[excluded] = ClauseState::Removed;
+        }
+        let mut is_core = vec![false; states.len()];
+        for selector in self.solver.failed_assumptions() {
+            is_core[self.selected[selector]] = true;
+        }
+        for (state, is_core) in states.iter_mut().zip(is_core) {
+            if *state == ClauseState::Unknown && !is_core {
+                *state = ClauseState::Removed;
+            }
+        }
+        None
+    }
+
+    /// Returns `true` if the literal is satisfied by the values of the variables.
+    fn is_satisfied(literal: Literal, values: &[bool]) -> bool {
+        values[literal.variable().into_index()] == literal.sign().is_pos()
+    }
+
+    /// Finds further MUS clauses by rotating the model that only falsifies
+    /// the critical clause.
+    ///
+    /// Flipping the value of a literal of the critical clause satisfies it.
+    /// If the flipped model falsifies exactly one other clause that clause is
+    /// critical as well and its rotated model is rotated recursively.
+    fn rotate(
+        &self,
+        states: &mut [ClauseState],
+        occurrences: &AHashMap<Literal, Vec<usize>>,
+        critical: usize,
+        values: Vec<bool>,
+    ) {
+        let mut worklist = vec![(critical, values)];
+        while let Some((critical, values)) = worklist.pop() {
+            for &literal in &self.clauses[critical] {
+                let mut rotated = values.clone();
+                let variable = literal.variable().into_index();
+                rotated[variable] = !rotated[variable];
+                let mut falsified = occurrences
+                    .get(&!literal)
+                    .into_iter()
+                    .flatten()
+                    .copied()
+                    .filter(|&index| states[index] != ClauseState::Removed)
+                    .filter(|&index| {
+                        !self.clauses[index]
+                            .iter()
+                            .any(|&literal| Self::is_satisfied(literal, &rotated))
+                    });
+                if let (Some(index), None) = (falsified.next(), falsified.next()) {
+                    if states[index] == ClauseState::Unknown {
+                        states[index] = ClauseState::Critical;
+                        worklist.push((index, rotated));
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,108 @@
+use super::*;
+use crate::{
+    tests::Lcg,
+    Sign,
+};
+
+/// Returns `true` if the clauses with the given indices are satisfiable.
+fn is_satisfiable(mus: &MusExtractor, indices: &[usize]) -> bool {
+    (0..1_u32 << mus.len_variables()).any(|assignment| {
+        indices.iter().all(|&index| {
+            mus.clause(index).iter().any(|literal| {
+                let value = assignment & (1 << literal.variable().into_index()) != 0;
+                value == (literal.sign() == Sign::POS)
+            })
+        })
+    })
+}
+
+#[test]
+fn extract_works() {
+    let mut mus = MusExtractor::default();
+    let vars = mus.new_literal_chunk(3).into_iter().collect::<Vec<_>>();
+    let (a, b, c) = (vars[0], vars[1], vars[2]);
+    mus.add_clause(&[a, b]);
+    mus.add_clause(&[!a, c]);
+    mus.add_clause(&[c, !c]);
+    mus.add_clause(&[!b]);
+    mus.add_clause(&[b, c]);
+    mus.add_clause(&[!c]);
+    // The clauses have exactly the two minimal unsatisfiable subsets.
+    let extracted = mus.extract().unwrap();
+    assert!(extracted == [0, 1, 3, 5] || extracted == [3, 4, 5]);
+}
+
+#[test]
+fn satisfiable_clauses_work() {
+    let mut mus = MusExtractor::default();
+    let vars = mus.new_literal_chunk(2).into_iter().collect::<Vec<_>>();
+    mus.add_clause(&[vars[0], vars[1]]);
+    mus.add_clause(&[!vars[0]]);
+    assert_eq!(mus.extract(), None);
+    assert_eq!(MusExtractor::default().extract(), None);
+}
+
+#[test]
+fn empty_clause_works() {
+    let mut mus = MusExtractor::default();
+    let vars = mus.new_literal_chunk(1).into_iter().collect::<Vec<_>>();
+    mus.add_clause(&[vars[0]]);
+    mus.add_clause(&[]);
+    mus.add_clause(&[!vars[0]]);
+    assert_eq!(mus.extract(), Some(vec![1]));
+}
+
+#[test]
+fn from_cnf_works() {
+    let input = b"p cnf 2 4\n1 0\n-1 2 0\n1 -2 0\n-2 0\n";
+    let mut mus = MusExtractor::from_cnf(&mut &input[..]).unwrap();
+    assert_eq!(mus.extract(), Some(vec![0, 1, 3]));
+    let clause = mus
+        .clause(1)
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>();
+    assert_eq!(clause, ["-1", "2"]);
+}
+
+#[test]
+fn random_muses_are_minimal() {
+    let mut rng = Lcg(42);
+    let mut len_unsat = 0;
+    for _ in 0..300 {
+        let mut mus = MusExtractor::default();
+        let len_vars = 1 + rng.next(8);
+        let vars = mus
+            .new_literal_chunk(len_vars)
+            .into_iter()
+            .collect::<Vec<_>>();
+        for _ in 0..rng.next(6 * len_vars) {
+            let clause = (0..1 + rng.next(3))
+                .map(|_| {
+                    let literal = vars[rng.next(len_vars)];
+                    if rng.next(2) == 0 {
+                        !literal
+                    } else {
+                        literal
+                    }
+                })
+                .collect::<Vec<_>>();
+            mus.add_clause(&clause);
+        }
+        let all = (0..mus.clauses.len()).collect::<Vec<_>>();
+        match mus.extract() {
+            None => assert!(is_satisfiable(&mus, &all)),
+            Some(indices) => {
+                len_unsat += 1;
+                assert!(indices.windows(2).all(|pair| pair[0] < pair[1]));
+                assert!(!is_satisfiable(&mus, &indices));
+                for removed in 0..indices.len() {
+                    let mut subset = indices.clone();
+                    subset.remove(removed);
+                    assert!(is_satisfiable(&mus, &subset));
+                }
+            }
+        }
+    }
+    assert!(len_unsat > 50);
+}
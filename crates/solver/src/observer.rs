@@ -0,0 +1,246 @@
+use crate::Literal;
+use core::fmt;
+use std::{
+    io,
+    io::Write,
+    sync::{
+        Arc,
+        Mutex,
+        MutexGuard,
+    },
+};
+
+/// Receives the events of the search of a solver.
+///
+/// All callbacks do nothing by default so that observers only need to implement
+/// the events they are interested in.
+///
+/// # Note
+///
+/// Literals of events are only valid for the solver that emitted them.
+pub trait SolverObserver {
+    /// Called when the solver decides the literal at a new decision level.
+    ///
+    /// Assumptions are not reported as decisions.
+    fn on_decision(&mut self, _literal: Literal) {}
+
+    /// Called when the literal is implied by a clause or constraint.
+    fn on_propagation(&mut self, _literal: Literal) {}
+
+    /// Called with the clausal explanation of a conflict that occurred during
+    /// propagation.
+    fn on_conflict(&mut self, _clause: &[Literal]) {}
+
+    /// Called with the clause learned from the most recent conflict.
+    ///
+    /// The first literal of the clause is asserted after the backjump.
+    fn on_learn(&mut self, _clause: &[Literal]) {}
+
+    /// Called when the solver backjumps to the decision level after a conflict.
+    fn on_backjump(&mut self, _level: usize) {}
+
+    /// Called when the solver restarts the search at the root level.
+    fn on_restart(&mut self) {}
+
+    /// Called with the literals of a learned clause that is deleted.
+    fn on_clause_deleted(&mut self, _clause: &[Literal]) {}
+}
+
+/// Allows to keep access to an observer after handing it over to a solver.
+impl<T> SolverObserver for Arc<Mutex<T>>
+where
+    T: SolverObserver,
+{
+    fn on_decision(&mut self, literal: Literal) {
+        lock(self).on_decision(literal)
+    }
+
+    fn on_propagation(&mut self, literal: Literal) {
+        lock(self).on_propagation(literal)
+    }
+
+    fn on_conflict(&mut self, clause: &[Literal]) {
+        lock(self).on_conflict(clause)
+    }
+
+    fn on_learn(&mut self, clause: &[Literal]) {
+        lock(self).on_learn(clause)
+    }
+
+    fn on_backjump(&mut self, level: usize) {
+        lock(self).on_backjump(level)
+    }
+
+    fn on_restart(&mut self) {
+        lock(self).on_restart()
+    }
+
+    fn on_clause_deleted(&mut self, clause: &[Literal]) {
+        lock(self).on_clause_deleted(clause)
+    }
+}
+
+/// Locks the shared observer.
+///
+/// # Panics
+///
+/// If another user of the observer panicked while holding the lock.
+fn lock<T>(observer: &Mutex<T>) -> MutexGuard<'_, T> {
+    observer
+        .lock()
+        .expect("encountered unexpected poisoned observer")
+}
+
+/// The optional observer of a solver.
+///
+/// # Note
+///
+/// Cloned solvers do not inherit the observer of the original solver.
+#[derive(Default)]
+pub struct Observer {
+    observer: Option<Box<dyn SolverObserver + Send>>,
+}
+
+impl Observer {
+    /// Replaces the observer and returns the previous one if any.
+    pub fn replace(
+        &mut self,
+        observer: Option<Box<dyn SolverObserver + Send>>,
+    ) -> Option<Box<dyn SolverObserver + Send>> {
+        core::mem::replace(&mut self.observer, observer)
+    }
+
+    /// Returns the observer if any.
+    #[inline]
+    pub fn get(&mut self) -> Option<&mut (dyn SolverObserver + Send + 'static)> {
+        self.observer.as_deref_mut()
+    }
+
+    /// Returns `true` if there is an observer.
+    #[inline]
+    pub fn is_some(&self) -> bool {
+        self.observer.is_some()
+    }
+}
+
+impl Clone for Observer {
+    fn clone(&self) -> Self {
+        Self::default()
+    }
+}
+
+impl fmt::Debug for Observer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Observer")
+            .field("is_some", &self.is_some())
+            .finish()
+    }
+}
+
+/// Writes the events of the search as JSON objects, one per line.
+///
+/// # Example
+///
+/// ```text
+/// {"event":"decision","literal":-3}
+/// {"event":"propagation","literal":4}
+/// {"event":"conflict","clause":[3,-4,1]}
+/// {"event":"learn","clause":[-1,3]}
+/// {"event":"backjump","level":0}
+/// {"event":"restart"}
+/// {"event":"clause_deleted","clause":[-1,3]}
+/// ```
+///
+/// Literals are written as DIMACS literals.
+#[derive(Debug)]
+pub struct JsonTrace<W> {
+    writer: W,
+    /// The first error encountered while writing if any.
+    error: Option<io::Error>,
+}
+
+impl<W> JsonTrace<W>
+where
+    W: Write,
+{
+    /// Creates a new trace writing to the writer.
+    ///
+    /// # Note
+    ///
+    /// Every event causes a write so the writer should usually be buffered.
+    pub fn new(writer: W) -> Self {
+        Self {
+            writer,
+            error: None,
+        }
+    }
+
+    /// Flushes the trace and returns the underlying writer.
+    ///
+    /// # Errors
+    ///
+    /// If any of the events could not be written.
+    pub fn into_inner(mut self) -> io::Result<W> {
+        if let Some(error) = self.error.take() {
+            return Err(error)
+        }
+        self.writer.flush()?;
+        Ok(self.writer)
+    }
+
+    /// Writes the event unless writing failed before.
+    fn write_event(&mut self, event: fmt::Arguments) {
+        if self.error.is_some() {
+            return
+        }
+        if let Err(error) = writeln!(self.writer, "{{\"event\":{}}}", event) {
+            self.error = Some(error);
+        }
+    }
+
+    /// Writes the event with the literals of the clause.
+    fn write_clause(&mut self, event: &str, clause: &[Literal]) {
+        let literals = clause
+            .iter()
+            .map(|literal| literal.to_string())
+            .collect::<Vec<_>>();
+        self.write_event(format_args!(
+            "\"{}\",\"clause\":[{}]",
+            event,
+            literals.join(",")
+        ))
+    }
+}
+
+impl<W> SolverObserver for JsonTrace<W>
+where
+    W: Write,
+{
+    fn on_decision(&mut self, literal: Literal) {
+        self.write_event(format_args!("\"decision\",\"literal\":{}", literal))
+    }
+
+    fn on_propagation(&mut self, literal: Literal) {
+        self.write_event(format_args!("\"propagation\",\"literal\":{}", literal))
+    }
+
+    fn on_conflict(&mut self, clause: &[Literal]) {
+        self.write_clause("conflict", clause)
+    }
+
+    fn on_learn(&mut self, clause: &[Literal]) {
+        self.write_clause("learn", clause)
+    }
+
+    fn on_backjump(&mut self, level: usize) {
+        self.write_event(format_args!("\"backjump\",\"level\":{}", level))
+    }
+
+    fn on_restart(&mut self) {
+        self.write_event(format_args!("\"restart\""))
+    }
+
+    fn on_clause_deleted(&mut self, clause: &[Literal]) {
+        self.write_clause("clause_deleted", clause)
+    }
+}
@@ -0,0 +1,462 @@
+use crate::{
+    Literal,
+    Model,
+    Sign,
+    Solver,
+    Variable,
+};
+use bounded::Index as _;
+use core::{
+    fmt,
+    fmt::Display,
+};
+
+/// Errors that can be encountered when parsing `.opb` input.
+///
+/// # Note
+///
+/// All lines are counted starting at `1`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum OpbError {
+    /// Encountered an unexpected byte or the unexpected end of the input.
+    UnexpectedByte {
+        line: usize,
+        encountered: Option<u8>,
+    },
+    /// An integer does not fit into an `i64`.
+    OutOfRangeInteger { line: usize },
+    /// A variable index is `0` or exceeds the supported number of variables.
+    InvalidVariable { line: usize },
+    /// A term is a product of multiple literals which is not supported.
+    NonLinearTerm { line: usize },
+    /// The objective function is not the first statement of the input.
+    MisplacedObjective { line: usize },
+}
+
+impl Display for OpbError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnexpectedByte {
+                line,
+                encountered: Some(byte),
+            } => {
+                write!(
+                    f,
+                    "encountered unexpected byte {:?} in line {}",
+                    char::from(*byte),
+                    line
+                )
+            }
+            Self::UnexpectedByte {
+                line,
+                encountered: None,
+            } => write!(f, "encountered unexpected end of input in line {}", line),
+            Self::OutOfRangeInteger { line } => {
+                write!(f, "encountered out of range integer in line {}", line)
+            }
+            Self::InvalidVariable { line } => {
+                write!(f, "encountered invalid variable in line {}", line)
+            }
+            Self::NonLinearTerm { line } => {
+                write!(
+                    f,
+                    "encountered unsupported non-linear term in line {}",
+                    line
+                )
+            }
+            Self::MisplacedObjective { line } => {
+                write!(
+                    f,
+                    "encountered misplaced objective function in line {}",
+                    line
+                )
+            }
+        }
+    }
+}
+
+/// A linear objective function over literals that is to be minimized.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Objective {
+    /// The weighted literals of the objective function.
+    terms: Vec<(i64, Literal)>,
+}
+
+impl Objective {
+    /// Returns the weighted literals of the objective function.
+    pub fn terms(&self) -> &[(i64, Literal)] {
+        &self.terms
+    }
+
+    /// Returns the value of the objective function under the given model.
+    ///
+    /// # Panics
+    ///
+    /// If the model does not assign all literals of the objective function.
+    pub fn evaluate(&self, model: &Model) -> i64 {
+        self.terms
+            .iter()
+            .filter(|&&(_, literal)| {
+                model
+                    .is_satisfied(literal)
+                    .expect("encountered unassigned objective literal")
+            })
+            .map(|&(coefficient, _)| coefficient)
+            .sum()
+    }
+}
+
+/// The relational operator of a constraint.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+enum Relation {
+    /// The left-hand side must be greater than or equal to the right-hand side.
+    GreaterEqual,
+    /// The left-hand side must be less than or equal to the right-hand side.
+    LessEqual,
+    /// The left-hand side must be equal to the right-hand side.
+    Equal,
+}
+
+/// Parses `.opb` input of the pseudo-Boolean competition format.
+///
+/// # Note
+///
+/// - Comments start with `*` and span until the end of the line.
+/// - Variables are named `x1`, `x2`, etc. and negated with a `~` prefix.
+/// - Constraints are linear with the relational operators `>=`, `<=` or `=`.
+/// - An optional `min:` objective function may precede all constraints.
+pub struct OpbParser<'a> {
+    /// The remaining input.
+    input: &'a [u8],
+    /// The current line for error reporting.
+    line: usize,
+    /// Buffer for the terms of the currently parsed statement.
+    terms: Vec<(i64, Literal)>,
+}
+
+impl<'a> OpbParser<'a> {
+    /// Creates a new `.opb` parser for the input.
+    pub fn new(input: &'a [u8]) -> Self {
+        Self {
+            input,
+            line: 1,
+            terms: Vec::new(),
+        }
+    }
+
+    /// Parses the input and feeds all its constraints to the solver.
+    ///
+    /// Returns the objective function if the input has one.
+    ///
+    /// # Errors
+    ///
+    /// If the input is no valid `.opb` format.
+    pub fn parse_into(
+        mut self,
+        solver: &mut Solver,
+    ) -> Result<Option<Objective>, OpbError> {
+        let mut objective = None;
+        let mut is_first_statement = true;
+        while self.peek().is_some() {
+            if self.input.starts_with(b"min:") {
+                if !is_first_statement {
+                    return Err(OpbError::MisplacedObjective { line: self.line })
+                }
+                self.input = &self.input[4..];
+                self.parse_terms(solver)?;
+                self.expect(b';')?;
+                objective = Some(Objective {
+                    terms: self.terms.clone(),
+                });
+            } else {
+                self.parse_terms(solver)?;
+                let relation = self.parse_relation()?;
+                let degree = self.parse_integer()?;
+                self.expect(b';')?;
+                self.feed_constraint(solver, relation, degree)?;
+            }
+            is_first_statement = false;
+        }
+        Ok(objective)
+    }
+
+    /// Feeds the constraint made up of the parsed terms to the solver.
+    fn feed_constraint(
+        &mut self,
+        solver: &mut Solver,
+        relation: Relation,
+        degree: i64,
+    ) -> Result<(), OpbError> {
+        if matches!(relation, Relation::GreaterEqual | Relation::Equal) {
+            solver.add_pseudo_boolean(self.terms.iter().copied(), degree);
+        }
+        if matches!(relation, Relation::LessEqual | Relation::Equal) {
+            let out_of_range = || OpbError::OutOfRangeInteger { line: self.line };
+            let negated = self
+                .terms
+                .iter()
+                .map(|&(coefficient, literal)| {
+                    coefficient
+                        .checked_neg()
+                        .map(|coefficient| (coefficient, literal))
+                        .ok_or_else(out_of_range)
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            let degree = degree.checked_neg().ok_or_else(out_of_range)?;
+            solver.add_pseudo_boolean(negated, degree);
+        }
+        Ok(())
+    }
+
+    /// Skips whitespace and comments and returns the next byte if any.
+    fn peek(&mut self) -> Option<u8> {
+        loop {
+            match self.input.first().copied() {
+                Some(b'\n') => {
+                    self.line += 1;
+                    self.input = &self.input[1..];
+                }
+                Some(byte) if byte.is_ascii_whitespace() => {
+                    self.input = &self.input[1..];
+                }
+                Some(b'*') => {
+                    let len_comment = self
+                        .input
+                        .iter()
+                        .position(|&byte| byte == b'\n')
+                        .unwrap_or(self.input.len());
+                    self.input = &self.input[len_comment..];
+                }
+                next => return next,
+            }
+        }
+    }
+
+    /// Returns an error for the unexpected next byte.
+    fn unexpected(&self) -> OpbError {
+        OpbError::UnexpectedByte {
+            line: self.line,
+            encountered: self.input.first().copied(),
+        }
+    }
+
+    /// Consumes the expected byte.
+    ///
+    /// # Errors
+    ///
+    /// If the next byte is not the expected byte.
+    fn expect(&mut self, expected: u8) -> Result<(), OpbError> {
+        if self.peek() != Some(expected) {
+            return Err(self.unexpected())
+        }
+        self.input = &self.input[1..];
+        Ok(())
+    }
+
+    /// Consumes all leading decimal digits and returns them.
+    fn digits(&mut self) -> &'a [u8] {
+        let len_digits = self
+            .input
+            .iter()
+            .position(|byte| !byte.is_ascii_digit())
+            .unwrap_or(self.input.len());
+        let (digits, rest) = self.input.split_at(len_digits);
+        self.input = rest;
+        digits
+    }
+
+    /// Parses an integer with an optional sign.
+    fn parse_integer(&mut self) -> Result<i64, OpbError> {
+        let is_negative = match self.peek() {
+            Some(b'-') => true,
+            Some(b'+') => false,
+            Some(byte) if byte.is_ascii_digit() => return self.parse_magnitude(false),
+            _ => return Err(self.unexpected()),
+        };
+        self.input = &self.input[1..];
+        self.parse_magnitude(is_negative)
+    }
+
+    /// Parses the digits of an integer with the given sign.
+    fn parse_magnitude(&mut self, is_negative: bool) -> Result<i64, OpbError> {
+        let digits = self.digits();
+        if digits.is_empty() {
+            return Err(self.unexpected())
+        }
+        let out_of_range = OpbError::OutOfRangeInteger { line: self.line };
+        digits.iter().try_fold(0_i64, |value, &digit| {
+            let digit = i64::from(digit - b'0');
+            value
+                .checked_mul(10)
+                .and_then(|value| {
+                    if is_negative {
+                        value.checked_sub(digit)
+                    } else {
+                        value.checked_add(digit)
+                    }
+                })
+                .ok_or_else(|| out_of_range.clone())
+        })
+    }
+
+    /// Parses a literal and registers its variable at the solver if necessary.
+    fn parse_literal(&mut self, solver: &mut Solver) -> Result<Literal, OpbError> {
+        let sign = match self.peek() {
+            Some(b'~') => {
+                self.input = &self.input[1..];
+                Sign::NEG
+            }
+            _ => Sign::POS,
+        };
+        if self.input.first() != Some(&b'x') {
+            return Err(self.unexpected())
+        }
+        self.input = &self.input[1..];
+        let invalid_variable = OpbError::InvalidVariable { line: self.line };
+        let digits = self.digits();
+        if digits.is_empty() {
+            return Err(self.unexpected())
+        }
+        let index = digits
+            .iter()
+            .try_fold(0_usize, |value, &digit| {
+                value
+                    .checked_mul(10)
+                    .and_then(|value| value.checked_add(usize::from(digit - b'0')))
+            })
+            .and_then(|index| index.checked_sub(1))
+            .filter(|&index| index <= Variable::MAX_INDEX)
+            .ok_or(invalid_variable)?;
+        let len_variables = solver.len_variables();
+        if index >= len_variables {
+            solver.new_literal_chunk(index + 1 - len_variables);
+        }
+        Ok(Literal::new(Variable::from_index(index), sign))
+    }
+
+    /// Parses all terms up to the next relational operator or `;`.
+    fn parse_terms(&mut self, solver: &mut Solver) -> Result<(), OpbError> {
+        self.terms.clear();
+        while let Some(b'+' | b'-' | b'0'..=b'9') = self.peek() {
+            let coefficient = self.parse_integer()?;
+            let literal = self.parse_literal(solver)?;
+            if let Some(b'~' | b'x') = self.peek() {
+                return Err(OpbError::NonLinearTerm { line: self.line })
+            }
+            self.terms.push((coefficient, literal));
+        }
+        Ok(())
+    }
+
+    /// Parses the relational operator of a constraint.
+    fn parse_relation(&mut self) -> Result<Relation, OpbError> {
+        let relation = match self.peek() {
+            Some(b'>') if self.input.starts_with(b">=") => Relation::GreaterEqual,
+            Some(b'<') if self.input.starts_with(b"<=") => Relation::LessEqual,
+            Some(b'=') => Relation::Equal,
+            _ => return Err(self.unexpected()),
+        };
+        let len_operator = match relation {
+            Relation::Equal => 1,
+            _ => 2,
+        };
+        self.input = &self.input[len_operator..];
+        Ok(relation)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::SolveResult;
+
+    /// Parses the `.opb` input into a new solver.
+    fn parse(input: &str) -> Result<(Solver, Option<Objective>), OpbError> {
+        Solver::from_opb(input.as_bytes())
+    }
+
+    #[test]
+    fn parse_works() {
+        let (mut solver, objective) = parse(
+            r"
+            * #variable= 4 #constraint= 3
+            * A comment line.
+            +1 x1 +2 x2 -1 ~x3 >= 1 ;
+            +1 x1 +1 x4 = 1;
+            3 x2 +1 x3 <= 3 ;
+            ",
+        )
+        .unwrap();
+        assert_eq!(objective, None);
+        assert_eq!(solver.len_variables(), 4);
+        let vars = (0..4)
+            .map(|index| Literal::new(Variable::from_index(index), Sign::POS))
+            .collect::<Vec<_>>();
+        assert!(solver.solve([vars[0], vars[3]]).unwrap().is_unsat());
+        assert!(solver.solve([vars[1], vars[2]]).unwrap().is_unsat());
+        assert!(solver.solve([!vars[0], !vars[1]]).unwrap().is_unsat());
+        assert!(solver.solve([vars[0], vars[2]]).unwrap().is_sat());
+    }
+
+    #[test]
+    fn parse_objective_works() {
+        let (mut solver, objective) = parse(
+            r"
+            min: +2 x1 -1 x2 +3 ~x3 ;
+            +1 x1 +1 x2 +1 x3 >= 2 ;
+            ",
+        )
+        .unwrap();
+        let objective = objective.unwrap();
+        let x1 = Literal::new(Variable::from_index(0), Sign::POS);
+        let x2 = Literal::new(Variable::from_index(1), Sign::POS);
+        let x3 = Literal::new(Variable::from_index(2), Sign::POS);
+        assert_eq!(objective.terms(), &[(2, x1), (-1, x2), (3, !x3)]);
+        match solver.solve([x1, !x2, x3]).unwrap() {
+            SolveResult::Sat(result) => {
+                assert_eq!(objective.evaluate(result.model()), 2);
+            }
+            _ => panic!("expected satisfied solve result"),
+        }
+    }
+
+    #[test]
+    fn parse_empty_objective_works() {
+        let (_solver, objective) = parse("min: ;\n+1 x1 >= 1 ;").unwrap();
+        assert_eq!(objective, Some(Objective::default()));
+    }
+
+    #[test]
+    fn parse_errors_work() {
+        assert_eq!(
+            parse("+1 x1 x2 >= 1 ;").err(),
+            Some(OpbError::NonLinearTerm { line: 1 })
+        );
+        assert_eq!(
+            parse("+1 x1 >= 1 ;\nmin: +1 x1 ;").err(),
+            Some(OpbError::MisplacedObjective { line: 2 })
+        );
+        assert_eq!(
+            parse("+1 x0 >= 1 ;").err(),
+            Some(OpbError::InvalidVariable { line: 1 })
+        );
+        assert_eq!(
+            parse("+1 x1 >= 99999999999999999999 ;").err(),
+            Some(OpbError::OutOfRangeInteger { line: 1 })
+        );
+        assert_eq!(
+            parse("+1 x1 > 1 ;").err(),
+            Some(OpbError::UnexpectedByte {
+                line: 1,
+                encountered: Some(b'>')
+            })
+        );
+        assert_eq!(
+            parse("\n+1 x1 >= 1").err(),
+            Some(OpbError::UnexpectedByte {
+                line: 2,
+                encountered: None
+            })
+        );
+    }
+}
@@ -0,0 +1,174 @@
+use crate::Literal;
+use std::sync::{
+    atomic::{
+        fence,
+        AtomicU32,
+        AtomicU64,
+        Ordering,
+    },
+    Arc,
+};
+
+/// The number of words of the ring buffer of every thread.
+const RING_CAPACITY: usize = 1 << 16;
+
+/// Clauses with more literals are never shared.
+pub const MAX_RING_CLAUSE_LEN: usize = RING_CAPACITY / 16;
+
+/// A lock-free ring buffer of clauses with a single writer and any number of readers.
+///
+/// # Note
+///
+/// Every clause is stored as its length followed by its literals. Readers that
+/// fall behind the writer by more than the capacity of the buffer skip the
+/// overwritten clauses. Readers detect overwritten clauses like readers of a
+/// sequence lock: the writer announces the words it is about to overwrite
+/// before writing them.
+#[derive(Debug)]
+struct ClauseRing {
+    words: Box<[AtomicU32]>,
+    /// The number of words the writer started to write.
+    reserved: AtomicU64,
+    /// The number of words the writer finished to write.
+    published: AtomicU64,
+}
+
+impl Default for ClauseRing {
+    fn default() -> Self {
+        Self {
+            words: (0..RING_CAPACITY).map(|_| AtomicU32::new(0)).collect(),
+            reserved: AtomicU64::new(0),
+            published: AtomicU64::new(0),
+        }
+    }
+}
+
+impl ClauseRing {
+    /// Returns the word at the absolute position.
+    fn word(&self, position: u64) -> &AtomicU32 {
+        &self.words[position as usize % RING_CAPACITY]
+    }
+
+    /// Appends the clause to the buffer.
+    ///
+    /// # Note
+    ///
+    /// Must only be called by the single writer of the buffer.
+    fn push(&self, clause: &[Literal]) {
+        debug_assert!(clause.len() <= MAX_RING_CLAUSE_LEN);
+        let start = self.published.load(Ordering::Relaxed);
+        let end = start + 1 + clause.len() as u64;
+        self.reserved.store(end, Ordering::Relaxed);
+        fence(Ordering::Release);
+        self.word(start)
+            .store(clause.len() as u32, Ordering::Relaxed);
+        for (offset, literal) in (start + 1..).zip(clause) {
+            self.word(offset)
+                .store(literal.into_u32(), Ordering::Relaxed);
+        }
+        self.published.store(end, Ordering::Release);
+    }
+
+    /// Reads the clause at the position into the buffer and advances the
+    /// position to the next clause.
+    ///
+    /// Returns `false` if there is no clause at the position, yet.
+    fn read(&self, position: &mut u64, clause: &mut Vec<Literal>) -> bool {
+        loop {
+            let published = self.published.load(Ordering::Acquire);
+            if *position >= published {
+                return false
+            }
+            let len = u64::from(self.word(*position).load(Ordering::Relaxed));
+            let end = *position + 1 + len;
+            clause.clear();
+            if end <= published {
+                clause.extend((*position + 1..end).map(|offset| {
+                    Literal::from_u32(self.word(offset).load(Ordering::Relaxed))
+                }));
+            }
+            fence(Ordering::Acquire);
+            let reserved = self.reserved.load(Ordering::Relaxed);
+            if end > published || reserved > *position + RING_CAPACITY as u64 {
+                // The writer overwrote the clause: skip all clauses written so far.
+                *position = published;
+                continue
+            }
+            *position = end;
+            return true
+        }
+    }
+}
+
+/// The clauses shared between the threads of a portfolio.
+#[derive(Debug)]
+pub struct SharedClauses {
+    /// The ring buffer written by every thread.
+    rings: Box<[ClauseRing]>,
+}
+
+impl SharedClauses {
+    /// Creates the shared clauses of the given number of threads.
+    pub fn new(len_threads: usize) -> Self {
+        Self {
+            rings: (0..len_threads).map(|_| ClauseRing::default()).collect(),
+        }
+    }
+
+    /// Shares the clause of the thread with all other threads.
+    ///
+    /// # Note
+    ///
+    /// Must only be called by the thread itself.
+    pub fn push(&self, thread: usize, clause: &[Literal]) {
+        if clause.len() <= MAX_RING_CLAUSE_LEN {
+            self.rings[thread].push(clause)
+        }
+    }
+}
+
+/// Reads the clauses shared by all other threads of a portfolio.
+#[derive(Debug)]
+pub struct ClauseReader {
+    shared: Arc<SharedClauses>,
+    /// The thread of the reader whose own clauses are skipped.
+    thread: usize,
+    /// The read position of every ring buffer.
+    positions: Vec<u64>,
+    /// The ring buffer that is read next.
+    next_ring: usize,
+}
+
+impl ClauseReader {
+    /// Creates a reader for the thread that starts at the oldest shared clauses.
+    pub fn new(shared: Arc<SharedClauses>, thread: usize) -> Self {
+        let len_threads = shared.rings.len();
+        Self {
+            shared,
+            thread,
+            positions: vec![0; len_threads],
+            next_ring: 0,
+        }
+    }
+
+    /// Returns the next clause shared by any of the other threads if any.
+    ///
+    /// # Note
+    ///
+    /// Reads the ring buffers in round robin order.
+    pub fn next_clause(&mut self) -> Option<Vec<Literal>> {
+        let len_rings = self.shared.rings.len();
+        let mut clause = Vec::new();
+        for _ in 0..len_rings {
+            let ring = self.next_ring;
+            self.next_ring = (ring + 1) % len_rings;
+            if ring == self.thread {
+                continue
+            }
+            if self.shared.rings[ring].read(&mut self.positions[ring], &mut clause) {
+                return Some(clause)
+            }
+        }
+        None
+    }
+}
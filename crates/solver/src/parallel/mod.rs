@@ -0,0 +1,287 @@
+//! Portfolio solving with diversified solvers on separate threads.
+//!
+//! Every thread solves its own copy of the instance with a different
+//! configuration. The threads share their short and low literal block distance
+//! learned clauses including learned unit clauses through lock-free buffers.
+//! The first thread to determine the satisfiability of the instance wins and
+//! interrupts all other threads.
+
+mod buffer;
+
+#[cfg(test)]
+mod tests;
+
+use self::buffer::{
+    ClauseReader,
+    SharedClauses,
+};
+use crate::{
+    DecisionHeuristic,
+    Error,
+    Interrupter,
+    Literal,
+    Model,
+    Phase,
+    RestartStrategy,
+    SolveResult,
+    Solver,
+    Statistics,
+};
+use std::{
+    sync::{
+        Arc,
+        OnceLock,
+    },
+    thread,
+    time::Instant,
+};
+
+/// Learned clauses with up to this many literals are shared.
+const MAX_SHARED_LEN: usize = 8;
+
+/// Learned clauses with up to this literal block distance are shared.
+const MAX_SHARED_LBD: u32 = 3;
+
+/// The answer of the thread that first determined the satisfiability.
+#[derive(Debug)]
+enum Answer {
+    /// The instance is satisfiable with the model.
+    Sat(Model),
+    /// The instance is unsatisfiable under the failed assumptions.
+    Unsat(Vec<Literal>),
+}
+
+/// Solves copies of a solver with diversified configurations on separate threads.
+///
+/// # Note
+///
+/// - The first thread keeps the configuration of the original solver.
+///   All other threads use different seeds, decision heuristics, phases and
+///   restart strategies.
+/// - Limits set on the original solver apply to every thread.
+/// - Clauses learned by the threads are not kept after a call to `solve`.
+/// - With a single thread the original solver solves on the calling thread.
+#[derive(Debug)]
+pub struct ParallelSolver {
+    solver: Solver,
+    len_threads: usize,
+    /// The model found by the winning thread of the last call to `solve`.
+    model: Model,
+    /// The failed assumptions of the winning thread of the last call to `solve`.
+    failed_assumptions: Vec<Literal>,
+    /// The statistics accumulated over all threads.
+    stats: Statistics,
+}
+
+impl ParallelSolver {
+    /// Creates a portfolio that solves the solver on the given number of threads.
+    ///
+    /// # Panics
+    ///
+    /// If the number of threads is zero.
+    pub fn new(solver: Solver, len_threads: usize) -> Self {
+        assert!(len_threads > 0, "encountered unexpected zero threads");
+        Self {
+            solver,
+            len_threads,
+            model: Model::default(),
+            failed_assumptions: Vec::new(),
+            stats: Statistics::default(),
+        }
+    }
+
+    /// Returns the number of threads of the portfolio.
+    pub fn len_threads(&self) -> usize {
+        self.len_threads
+    }
+
+    /// Returns the original solver.
+    pub fn solver(&self) -> &Solver {
+        &self.solver
+    }
+
+    /// Returns the original solver to add further clauses or constraints.
+    pub fn solver_mut(&mut self) -> &mut Solver {
+        &mut self.solver
+    }
+
+    /// Returns the original solver.
+    pub fn into_inner(self) -> Solver {
+        self.solver
+    }
+
+    /// Returns a handle to interrupt all threads of calls to `solve`.
+    pub fn interrupter(&self) -> Interrupter {
+        self.solver.interrupter()
+    }
+
+    /// Returns the assumptions of the last call to `solve` that are responsible
+    /// for its unsatisfiability.
+    pub fn failed_assumptions(&self) -> &[Literal] {
+        match self.len_threads {
+            1 => self.solver.failed_assumptions(),
+            _ => &self.failed_assumptions,
+        }
+    }
+
+    /// Returns the statistics about the work of all threads so far.
+    ///
+    /// # Note
+    ///
+    /// With multiple threads the counters and phase times are summed over all
+    /// threads whereas the solve time is the elapsed wall-clock time.
+    pub fn stats(&self) -> Statistics {
+        match self.len_threads {
+            1 => self.solver.stats(),
+            _ => self.stats,
+        }
+    }
+
+    /// Solves the instance under the assumptions on all threads.
+    ///
+    /// Returns the answer of the first thread that determined the satisfiability.
+    ///
+    /// # Errors
+    ///
+    /// If no thread determined the satisfiability and any thread encountered
+    /// an error.
+    pub fn solve<L>(&mut self, assumptions: L) -> Result<SolveResult<'_>, Error>
+    where
+        L: IntoIterator<Item = Literal>,
+    {
+        if self.len_threads == 1 {
+            return self.solver.solve(assumptions)
+        }
+        let assumptions = assumptions.into_iter().collect::<Vec<_>>();
+        let shared = Arc::new(SharedClauses::new(self.len_threads));
+        let interrupter = self.solver.interrupter();
+        let answer = OnceLock::new();
+        let error = OnceLock::new();
+        let started = Instant::now();
+        let stats = thread::scope(|scope| {
+            let threads = (0..self.len_threads)
+                .map(|index| {
+                    let mut solver = self.portfolio_solver(index, &shared);
+                    let (answer, error, interrupter, assumptions) =
+                        (&answer, &error, &interrupter, &assumptions);
+                    scope.spawn(move || {
+                        let result = solver.solve(assumptions.iter().copied());
+                        let found = match result {
+                            Ok(SolveResult::Sat(sat)) => {
+                                Some(Answer::Sat(sat.model().clone()))
+                            }
+                            Ok(SolveResult::Unsat) => {
+                                Some(Answer::Unsat(solver.failed_assumptions().to_vec()))
+                            }
+                            Ok(SolveResult::Unknown) => None,
+                            Err(found_error) => {
+                                let _ = error.set(found_error);
+                                None
+                            }
+                        };
+                        if let Some(found) = found {
+                            if answer.set(found).is_ok() {
+                                interrupter.interrupt();
+                            }
+                        }
+                        solver.stats()
+                    })
+                })
+                .collect::<Vec<_>>();
+            threads
+                .into_iter()
+                .map(|thread| {
+                    thread
+                        .join()
+                        .expect("encountered unexpected panic of a portfolio thread")
+                })
+                .collect::<Vec<_>>()
+        });
+        for stats in &stats {
+            accumulate(&mut self.stats, stats);
+        }
+        self.stats.solve_time += started.elapsed();
+        self.failed_assumptions.clear();
+        match answer.into_inner() {
+            Some(Answer::Sat(model)) => {
+                interrupter.reset();
+                self.model = model;
+                Ok(SolveResult::sat(&self.model))
+            }
+            Some(Answer::Unsat(failed_assumptions)) => {
+                interrupter.reset();
+                self.failed_assumptions = failed_assumptions;
+                Ok(SolveResult::Unsat)
+            }
+            None => {
+                match error.into_inner() {
+                    Some(error) => Err(error),
+                    None => Ok(SolveResult::Unknown),
+                }
+            }
+        }
+    }
+
+    /// Returns the diversified copy of the original solver for the thread that
+    /// shares its learned clauses through the shared clauses.
+    fn portfolio_solver(&self, index: usize, shared: &Arc<SharedClauses>) -> Solver {
+        let mut solver = self.solver.clone();
+        solver.reset_stats();
+        diversify(&mut solver, index);
+        let sink = shared.clone();
+        solver.set_share_callback(MAX_SHARED_LEN, MAX_SHARED_LBD, move |clause| {
+            sink.push(index, clause)
+        });
+        let mut reader = ClauseReader::new(shared.clone(), index);
+        solver.set_import_callback(move || reader.next_clause());
+        solver
+    }
+}
+
+/// Diversifies the configuration of the solver of the thread with the index.
+///
+/// The first thread keeps the configuration of the original solver.
+fn diversify(solver: &mut Solver, index: usize) {
+    if index == 0 {
+        return
+    }
+    solver.set_seed(index as u64);
+    solver.set_decision_heuristic(match index % 2 {
+        0 => DecisionHeuristic::Acids,
+        _ => DecisionHeuristic::RecentBump,
+    });
+    solver.set_phase(match index % 3 {
+        0 => Phase::Positive,
+        1 => Phase::Negative,
+        _ => Phase::Random,
+    });
+    solver.set_restart_strategy(match index % 4 {
+        0 | 1 => {
+            RestartStrategy::Luby {
+                unit: 50 * (1 + index as u64 % 3),
+            }
+        }
+        _ => {
+            RestartStrategy::Geometric {
+                initial: 100,
+                factor: 1.5,
+            }
+        }
+    });
+}
+
+/// Adds the statistics of a thread to the total statistics except for the solve time.
+fn accumulate(total: &mut Statistics, stats: &Statistics) {
+    total.decisions += stats.decisions;
+    total.propagations += stats.propagations;
+    total.conflicts += stats.conflicts;
+    total.restarts += stats.restarts;
+    total.learned_clauses += stats.learned_clauses;
+    total.deleted_clauses += stats.deleted_clauses;
+    total.gc_runs += stats.gc_runs;
+    total.freed_words += stats.freed_words;
+    total.max_decision_level = total.max_decision_level.max(stats.max_decision_level);
+    total.propagation_time += stats.propagation_time;
+    total.analysis_time += stats.analysis_time;
+    total.reduction_time += stats.reduction_time;
+}
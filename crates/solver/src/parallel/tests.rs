@@ -0,0 +1,138 @@
+use super::{
+    buffer::{
+        ClauseReader,
+        SharedClauses,
+    },
+    *,
+};
+use crate::tests::{
+    pigeon_hole_clauses,
+    Lcg,
+};
+use bounded::Index as _;
+
+/// Returns `true` if the assignment of the variables satisfies the clause.
+fn is_satisfied(clause: &[Literal], assignment: u32) -> bool {
+    clause.iter().any(|literal| {
+        let value = assignment & (1 << literal.variable().into_index()) != 0;
+        value == literal.sign().is_pos()
+    })
+}
+
+#[test]
+fn shared_clauses_work() {
+    let shared = Arc::new(SharedClauses::new(3));
+    let mut reader = ClauseReader::new(shared.clone(), 0);
+    let clause = |literals: &[i32]| {
+        literals
+            .iter()
+            .map(|&literal| Literal::from(literal))
+            .collect::<Vec<_>>()
+    };
+    shared.push(0, &clause(&[1, 2]));
+    assert_eq!(reader.next_clause(), None);
+    shared.push(1, &clause(&[-1]));
+    shared.push(2, &clause(&[3, -4, 5]));
+    shared.push(1, &clause(&[]));
+    let mut read = (0..3)
+        .filter_map(|_| reader.next_clause())
+        .collect::<Vec<_>>();
+    read.sort();
+    assert_eq!(read, [clause(&[]), clause(&[-1]), clause(&[3, -4, 5])]);
+    assert_eq!(reader.next_clause(), None);
+}
+
+#[test]
+fn overwritten_clauses_are_skipped() {
+    let shared = Arc::new(SharedClauses::new(2));
+    let mut reader = ClauseReader::new(shared.clone(), 0);
+    let clause = (1..=100).map(Literal::from).collect::<Vec<_>>();
+    // Write more words than fit into the ring buffer.
+    for _ in 0..1000 {
+        shared.push(1, &clause);
+    }
+    while let Some(read) = reader.next_clause() {
+        assert_eq!(read, clause);
+    }
+    shared.push(1, &clause[..1]);
+    assert_eq!(reader.next_clause(), Some(clause[..1].to_vec()));
+}
+
+#[test]
+fn random_cnf_agrees_with_brute_force() {
+    let mut rng = Lcg(42);
+    for _ in 0..50 {
+        let mut solver = Solver::default();
+        let vars = solver.new_literal_chunk(10).into_iter().collect::<Vec<_>>();
+        let clauses = (0..40 + rng.next(15))
+            .map(|_| {
+                (0..1 + rng.next(3))
+                    .map(|_| {
+                        let literal = vars[rng.next(vars.len())];
+                        if rng.next(2) == 0 {
+                            !literal
+                        } else {
+                            literal
+                        }
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+        for clause in &clauses {
+            solver.consume_clause(clause.iter().copied());
+        }
+        let is_model = |assignment: u32| {
+            clauses
+                .iter()
+                .all(|clause| is_satisfied(clause, assignment))
+        };
+        let assumption = vars[rng.next(vars.len())];
+        let expected = (0..1_u32 << vars.len()).any(|assignment| {
+            is_model(assignment) && is_satisfied(&[assumption], assignment)
+        });
+        let mut parallel = ParallelSolver::new(solver, 4);
+        match parallel.solve([assumption]).unwrap() {
+            SolveResult::Sat(sat) => {
+                assert!(expected);
+                let model = sat.model();
+                let assignment = vars
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, &var)| model.is_satisfied(var) == Ok(true))
+                    .fold(0, |assignment, (n, _)| assignment | 1 << n);
+                assert!(is_model(assignment));
+                assert!(is_satisfied(&[assumption], assignment));
+            }
+            SolveResult::Unsat => {
+                assert!(!expected);
+                assert!(parallel
+                    .failed_assumptions()
+                    .iter()
+                    .all(|&failed| failed == assumption));
+            }
+            SolveResult::Unknown => panic!("encountered unexpected unknown result"),
+        }
+    }
+}
+
+#[test]
+fn pigeon_hole_works() {
+    let solver = pigeon_hole_clauses(6);
+    let mut parallel = ParallelSolver::new(solver, 4);
+    assert!(parallel.solve([]).unwrap().is_unsat());
+    let stats = parallel.stats();
+    assert!(stats.conflicts > 0);
+    // The winner resets the interruption of the other threads.
+    assert!(!parallel.interrupter().is_interrupted());
+    assert!(parallel.solve([]).unwrap().is_unsat());
+    assert!(parallel.stats().conflicts > stats.conflicts);
+}
+
+#[test]
+fn interrupter_works() {
+    let mut parallel = ParallelSolver::new(pigeon_hole_clauses(6), 3);
+    parallel.interrupter().interrupt();
+    assert!(parallel.solve([]).unwrap().is_unknown());
+    parallel.interrupter().reset();
+    assert!(parallel.solve([]).unwrap().is_unsat());
+}
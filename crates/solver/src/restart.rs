@@ -0,0 +1,127 @@
+/// Returns the `n`-th element of the Luby sequence starting at `n = 1`.
+///
+/// The sequence is: `1, 1, 2, 1, 1, 2, 4, 1, 1, 2, 1, 1, 2, 4, 8, ...`
+fn luby(mut n: u64) -> u64 {
+    loop {
+        // Find the smallest `k` so that `n <= 2^k - 1`.
+        let mut k = 1;
+        while (1 << k) - 1 < n {
+            k += 1;
+        }
+        if n == (1 << k) - 1 {
+            return 1 << (k - 1)
+        }
+        n -= (1 << (k - 1)) - 1;
+    }
+}
+
+/// The sequence of the numbers of conflicts between two restarts.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum RestartStrategy {
+    /// The Luby sequence scaled by the number of conflicts of a single unit.
+    Luby { unit: u64 },
+    /// The geometric sequence starting at the initial number of conflicts
+    /// that grows by the factor with every restart.
+    Geometric { initial: u64, factor: f64 },
+}
+
+impl Default for RestartStrategy {
+    fn default() -> Self {
+        Self::Luby { unit: 100 }
+    }
+}
+
+/// Decides when the solver restarts its search.
+///
+/// # Note
+///
+/// By default restarts follow the Luby sequence scaled by a constant number of conflicts.
+#[derive(Debug, Clone)]
+pub struct RestartPolicy {
+    /// The sequence of restart intervals.
+    strategy: RestartStrategy,
+    /// The index into the sequence for the current restart interval.
+    index: u64,
+    /// The number of conflicts since the last restart.
+    conflicts: u64,
+}
+
+impl Default for RestartPolicy {
+    fn default() -> Self {
+        Self::new(RestartStrategy::default())
+    }
+}
+
+impl RestartPolicy {
+    /// Creates a new restart policy following the strategy.
+    pub fn new(strategy: RestartStrategy) -> Self {
+        Self {
+            strategy,
+            index: 1,
+            conflicts: 0,
+        }
+    }
+
+    /// Returns the number of conflicts of the current restart interval.
+    fn interval(&self) -> u64 {
+        match self.strategy {
+            RestartStrategy::Luby { unit } => unit.saturating_mul(luby(self.index)),
+            RestartStrategy::Geometric { initial, factor } => {
+                let exponent = (self.index - 1).min(i32::MAX as u64) as i32;
+                // Saturates at `u64::MAX` for huge intervals.
+                (initial as f64 * factor.powi(exponent)) as u64
+            }
+        }
+    }
+
+    /// Informs the policy about a conflict and returns `true` if the solver shall restart.
+    pub fn on_conflict(&mut self) -> bool {
+        self.conflicts += 1;
+        if self.conflicts < self.interval() {
+            return false
+        }
+        self.conflicts = 0;
+        self.index += 1;
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn luby_works() {
+        let expected = [1, 1, 2, 1, 1, 2, 4, 1, 1, 2, 1, 1, 2, 4, 8, 1];
+        for (n, expected) in expected.iter().copied().enumerate() {
+            assert_eq!(luby(n as u64 + 1), expected);
+        }
+    }
+
+    #[test]
+    fn restart_policy_works() {
+        let mut policy = RestartPolicy::default();
+        let mut restarts = Vec::new();
+        for conflict in 1..=800 {
+            if policy.on_conflict() {
+                restarts.push(conflict);
+            }
+        }
+        assert_eq!(restarts, vec![100, 200, 400, 500, 600, 800]);
+    }
+
+    #[test]
+    fn geometric_restart_policy_works() {
+        let mut policy = RestartPolicy::new(RestartStrategy::Geometric {
+            initial: 100,
+            factor: 1.5,
+        });
+        let mut restarts = Vec::new();
+        for conflict in 1..=1000 {
+            if policy.on_conflict() {
+                restarts.push(conflict);
+            }
+        }
+        assert_eq!(restarts, vec![100, 250, 475, 812]);
+    }
+}
@@ -0,0 +1,239 @@
+use crate::{
+    clause_db::{
+        ClauseRef,
+        ClauseRemoval,
+        ResolvedClause,
+    },
+    gc,
+    lifecycle,
+    Literal,
+    Solver,
+};
+
+/// A handle to a clause given to [`Solver::consume_clause`].
+///
+/// Allows to remove the clause from the solver via [`Solver::remove_clause`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct ClauseHandle(usize);
+
+/// How a clause given to [`Solver::consume_clause`] is kept by the solver.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub(crate) enum ConsumedClause {
+    /// The clause is stored in the clause database.
+    Stored(ClauseRef),
+    /// The clause is a unit clause that is kept as hard fact.
+    Unit(Literal),
+    /// The clause is the empty clause.
+    Empty,
+    /// The clause is tautological and therefore not kept.
+    Tautological,
+    /// The clause has been removed.
+    Removed,
+}
+
+/// The clauses given to [`Solver::consume_clause`] indexed by their handles.
+#[derive(Debug, Default, Clone)]
+pub struct ConsumedClauses {
+    clauses: Vec<ConsumedClause>,
+}
+
+impl ConsumedClauses {
+    /// Pushes a clause and returns its handle.
+    fn push(&mut self, clause: ConsumedClause) -> ClauseHandle {
+        let handle = ClauseHandle(self.clauses.len());
+        self.clauses.push(clause);
+        handle
+    }
+
+    /// Pushes a clause stored in the clause database and returns its handle.
+    pub fn push_stored(&mut self, cref: ClauseRef) -> ClauseHandle {
+        self.push(ConsumedClause::Stored(cref))
+    }
+
+    /// Pushes a unit clause and returns its handle.
+    pub fn push_unit(&mut self, unit: Literal) -> ClauseHandle {
+        self.push(ConsumedClause::Unit(unit))
+    }
+
+    /// Pushes the empty clause and returns its handle.
+    pub fn push_empty(&mut self) -> ClauseHandle {
+        self.push(ConsumedClause::Empty)
+    }
+
+    /// Pushes a tautological clause and returns its handle.
+    pub fn push_tautological(&mut self) -> ClauseHandle {
+        self.push(ConsumedClause::Tautological)
+    }
+
+    /// Returns the unit clauses that have not been removed.
+    pub fn units(&self) -> impl Iterator<Item = Literal> + '_ {
+        self.clauses.iter().filter_map(|clause| {
+            match clause {
+                ConsumedClause::Unit(unit) => Some(*unit),
+                _ => None,
+            }
+        })
+    }
+
+    /// Returns `true` if the empty clause has been consumed and not been removed.
+    pub fn contains_empty(&self) -> bool {
+        self.clauses.contains(&ConsumedClause::Empty)
+    }
+
+    /// Replaces the clause references of the stored clauses.
+    pub fn relocate<F>(&mut self, relocate: F)
+    where
+        F: Fn(ClauseRef) -> ClauseRef,
+    {
+        for clause in &mut self.clauses {
+            if let ConsumedClause::Stored(cref) = clause {
+                *cref = relocate(*cref);
+            }
+        }
+    }
+}
+
+/// Removes the clause of the handle from the solver.
+///
+/// Returns `false` if the clause has already been removed.
+///
+/// # Note
+///
+/// All learned clauses and learned unit clauses are forgotten since they
+/// may have been derived from the removed clause. This is deferred to
+/// [`forget_learnt_if_removed`] so that a batch of removals forgets them once.
+///
+/// # Panics
+///
+/// If the handle has not been returned by the solver.
+pub(crate) fn remove_clause(solver: &mut Solver, handle: ClauseHandle) -> bool {
+    match retract(solver, handle) {
+        None => false,
+        Some(ConsumedClause::Tautological) => true,
+        Some(_) => {
+            solver.has_removed_clauses = true;
+            true
+        }
+    }
+}
+
+/// Forgets the learned clauses if clauses have been removed via
+/// [`Solver::remove_clause`] since they have last been forgotten.
+///
+/// # Note
+///
+/// Must be called before the learned clauses or the hard facts are used.
+pub(crate) fn forget_learnt_if_removed(solver: &mut Solver) {
+    if !core::mem::take(&mut solver.has_removed_clauses) {
+        return
+    }
+    forget_learnt(solver);
+    if solver.gc.is_due(&solver.clauses) {
+        gc::collect_garbage(solver);
+    }
+}
+
+/// Marks the clause of the handle as removed and deletes it from the clause database.
+///
+/// Returns how the clause has been kept or `None` if it has already been removed.
+///
+/// # Note
+///
+/// This neither removes unit clauses from the hard facts nor forgets
+/// the learned clauses that may have been derived from the clause.
+///
+/// # Panics
+///
+/// If the handle has not been returned by the solver.
+pub(crate) fn retract(
+    solver: &mut Solver,
+    handle: ClauseHandle,
+) -> Option<ConsumedClause> {
+    let consumed = solver
+        .consumed
+        .clauses
+        .get_mut(handle.0)
+        .expect("encountered invalid clause handle");
+    let retracted = core::mem::replace(consumed, ConsumedClause::Removed);
+    match retracted {
+        ConsumedClause::Removed => return None,
+        ConsumedClause::Stored(cref) => {
+            let clause = solver
+                .clauses
+                .resolve(cref)
+                .expect("encountered unexpected invalid consumed clause");
+            solver.lifecycle.remove_occurrences(clause.literals());
+            if let ClauseRemoval::Removed(freed_words) =
+                solver.clauses.remove_clause(cref)
+            {
+                solver.stats.freed_words += freed_words as u64;
+            }
+        }
+        ConsumedClause::Unit(unit) => {
+            solver.lifecycle.remove_occurrences([&unit]);
+        }
+        ConsumedClause::Empty | ConsumedClause::Tautological => (),
+    }
+    Some(retracted)
+}
+
+/// Removes the clauses of the handles that are guarded by the activation literal
+/// and releases the activation variable.
+///
+/// # Note
+///
+/// Learned clauses derived from the guarded clauses contain the negated
+/// activation literal and are deleted as well. The activation variable is
+/// kept if it has been frozen by the user.
+pub(crate) fn retract_guarded<I>(solver: &mut Solver, handles: I, activation: Literal)
+where
+    I: IntoIterator<Item = ClauseHandle>,
+{
+    for handle in handles {
+        retract(solver, handle);
+    }
+    let variable = activation.variable();
+    lifecycle::forget_variable(solver, variable);
+    let _ = solver.lifecycle.release(variable);
+    if solver.gc.is_due(&solver.clauses) {
+        gc::collect_garbage(solver);
+    }
+}
+
+/// Deletes the learned clauses for which `is_deleted` returns `true`.
+pub(crate) fn delete_learnt_clauses<F>(solver: &mut Solver, mut is_deleted: F)
+where
+    F: FnMut(ResolvedClause) -> bool,
+{
+    let Solver {
+        clauses,
+        learnt_clauses,
+        observer,
+        stats,
+        ..
+    } = solver;
+    learnt_clauses.retain(|&cref| {
+        let clause = clauses
+            .resolve(cref)
+            .expect("encountered unexpected invalid learned clause");
+        if !is_deleted(clause) {
+            return true
+        }
+        if let Some(observer) = observer.get() {
+            observer.on_clause_deleted(clause.literals().as_slice());
+        }
+        if let ClauseRemoval::Removed(freed_words) = clauses.remove_clause(cref) {
+            stats.freed_words += freed_words as u64;
+        }
+        false
+    });
+}
+
+/// Deletes the learned clauses and restores the hard facts and the empty clause
+/// to those of the clauses given to [`Solver::consume_clause`].
+fn forget_learnt(solver: &mut Solver) {
+    delete_learnt_clauses(solver, |_| true);
+    solver.hard_facts.clear();
+    solver.hard_facts.extend(solver.consumed.units());
+    solver.encountered_empty_clause = solver.consumed.contains_empty();
+}
@@ -1,19 +1,14 @@
 use crate::{
     literal::RegisterVariables,
     Literal,
-    Variable,
 };
 use bounded::Index;
 use core::slice;
 
-/// A slightly faster hash set due to usage of `ahash` hasher.
-type HashSet<T> = std::collections::HashSet<T, ahash::RandomState>;
-
 /// A clause sanitizer.
 #[derive(Debug, Default, Clone)]
 pub struct ClauseSanitizer {
     literals: Vec<Literal>,
-    tautologies: HashSet<Variable>,
     registered_variables: usize,
 }
 
@@ -28,9 +23,9 @@ impl ClauseSanitizer {
     ///
     /// # Note
     ///
-    /// This removes duplicate literals as well as literals where both polarities occur.
-    /// Furthermore this signals empty clauses as well as tautological clauses.
-    pub fn sanitize<I, T>(&mut self, literals: I) -> SanitizedLiterals
+    /// This removes duplicate literals. Furthermore this signals empty clauses as well
+    /// as tautological clauses, i.e. clauses in which both polarities of a variable occur.
+    pub fn sanitize<I, T>(&mut self, literals: I) -> SanitizedLiterals<'_>
     where
         I: IntoIterator<IntoIter = T>,
         T: ExactSizeIterator<Item = Literal>,
@@ -40,7 +35,6 @@ impl ClauseSanitizer {
             return SanitizedLiterals::EmptyClause
         }
         self.literals.clear();
-        self.tautologies.clear();
         self.literals.extend(literals);
         for lit in &self.literals {
             if lit.variable().into_index() >= self.registered_variables {
@@ -48,26 +42,22 @@ impl ClauseSanitizer {
             }
         }
         self.literals.sort_unstable();
-        let tautologies = &mut self.tautologies;
-        self.literals.dedup_by(|l, r| {
-            if l.variable() == r.variable() {
-                if l != r {
-                    tautologies.insert(l.variable());
-                }
-                return true
-            }
-            false
-        });
-        self.literals
-            .retain(|lit| !tautologies.contains(&lit.variable()));
+        self.literals.dedup();
+        // Both polarities of a variable are adjacent after sorting.
+        if self
+            .literals
+            .windows(2)
+            .any(|pair| pair[0].variable() == pair[1].variable())
+        {
+            return SanitizedLiterals::TautologicalClause
+        }
         match self.literals.split_first() {
             Some((&unit, &[])) => SanitizedLiterals::UnitClause(unit),
-            Some(_) => {
+            _ => {
                 SanitizedLiterals::Literals(LiteralIter {
                     literals: self.literals.iter(),
                 })
             }
-            None => SanitizedLiterals::TautologicalClause,
         }
     }
 }
@@ -90,7 +80,7 @@ pub enum SanitizedLiterals<'a> {
 impl SanitizedLiterals<'_> {
     /// Returns a literals iterator over the literals if any.
     #[cfg(test)]
-    pub fn literals(&self) -> LiteralIter {
+    pub fn literals(&self) -> LiteralIter<'_> {
         match self {
             Self::EmptyClause | Self::TautologicalClause | Self::InvalidLiteral(_) => {
                 LiteralIter::default()
@@ -180,18 +170,20 @@ mod tests {
             clause([1, 2, 3])
         );
         assert_eq!(
-            sanitizer.sanitize(clause([1, 2, -2, 3, -3])).literals(),
-            clause([1])
+            sanitizer.sanitize(clause([1, 2, -2, 3, -3])),
+            SanitizedLiterals::TautologicalClause,
+        );
+        assert_eq!(
+            sanitizer.sanitize(clause([-4, -5, 4])),
+            SanitizedLiterals::TautologicalClause,
         );
         assert_eq!(
             sanitizer.sanitize(clause([1, 2, 3, -1, -1, -2, -2, -3, -3])),
             SanitizedLiterals::TautologicalClause,
         );
         assert_eq!(
-            sanitizer
-                .sanitize(clause([1, 2, 3, -1, -1, -2, -2, -3, -3, 4]))
-                .literals(),
-            clause([4])
+            sanitizer.sanitize(clause([1, 2, 3, -1, -1, -2, -2, -3, -3, 4])),
+            SanitizedLiterals::TautologicalClause,
         );
     }
 }
@@ -0,0 +1,78 @@
+use crate::{
+    retract,
+    retract::ClauseHandle,
+    Literal,
+    Solver,
+};
+
+/// A scope opened by [`Solver::push`].
+#[derive(Debug, Clone)]
+struct Scope {
+    /// The literal that is assumed while the scope is open.
+    activation: Literal,
+    /// The clauses that have been consumed while the scope was the innermost scope.
+    clauses: Vec<ClauseHandle>,
+}
+
+/// The stack of the scopes opened by [`Solver::push`].
+#[derive(Debug, Default, Clone)]
+pub struct Scopes {
+    scopes: Vec<Scope>,
+}
+
+impl Scopes {
+    /// Returns the number of open scopes.
+    pub fn len(&self) -> usize {
+        self.scopes.len()
+    }
+
+    /// Returns the activation literal of the innermost scope if any.
+    pub fn activation(&self) -> Option<Literal> {
+        self.scopes.last().map(|scope| scope.activation)
+    }
+
+    /// Returns the activation literals of all open scopes from the outermost scope on.
+    pub fn activations(&self) -> impl Iterator<Item = Literal> + '_ {
+        self.scopes.iter().map(|scope| scope.activation)
+    }
+
+    /// Records the clause consumed within the innermost scope.
+    ///
+    /// # Panics
+    ///
+    /// If there is no open scope.
+    pub fn record(&mut self, handle: ClauseHandle) {
+        self.scopes
+            .last_mut()
+            .expect("encountered unexpected missing scope")
+            .clauses
+            .push(handle);
+    }
+}
+
+/// Opens a new scope guarded by a newly allocated activation literal.
+pub(crate) fn push(solver: &mut Solver) {
+    let activation = solver.new_literal();
+    solver.scopes.scopes.push(Scope {
+        activation,
+        clauses: Vec::new(),
+    });
+}
+
+/// Closes the innermost scope and removes the clauses consumed within it.
+///
+/// Returns `false` if there is no open scope.
+///
+/// # Note
+///
+/// Learned clauses derived from the removed clauses contain the negated
+/// activation literal of the scope and are deleted as well.
+/// The activation variable is released so that its slot is reused.
+pub(crate) fn pop(solver: &mut Solver) -> bool {
+    let scope = match solver.scopes.scopes.pop() {
+        Some(scope) => scope,
+        None => return false,
+    };
+    retract::retract_guarded(solver, scope.clauses, scope.activation);
+    true
+}
@@ -0,0 +1,86 @@
+use crate::Literal;
+use core::fmt;
+
+/// The callback that receives learned clauses.
+type ExportCallback = Box<dyn FnMut(&[Literal]) + Send>;
+
+/// The callback that yields external clauses.
+type ImportCallback = Box<dyn FnMut() -> Option<Vec<Literal>> + Send>;
+
+/// The filter and receiver of exported clauses.
+struct Export {
+    /// Clauses with at most this many literals are exported.
+    max_len: usize,
+    /// Clauses with at most this literal block distance are exported.
+    max_lbd: u32,
+    callback: ExportCallback,
+}
+
+/// The optional callbacks to share clauses with other solvers.
+///
+/// # Note
+///
+/// Cloned solvers do not inherit the callbacks of the original solver.
+#[derive(Default)]
+pub struct ClauseSharing {
+    /// The receiver of exported clauses if any.
+    export: Option<Export>,
+    /// The source of imported clauses if any.
+    import: Option<ImportCallback>,
+}
+
+impl ClauseSharing {
+    /// Sets the callback that receives learned clauses of up to `max_len` literals
+    /// or with a literal block distance of up to `max_lbd`.
+    pub fn set_export(&mut self, max_len: usize, max_lbd: u32, callback: ExportCallback) {
+        self.export = Some(Export {
+            max_len,
+            max_lbd,
+            callback,
+        });
+    }
+
+    /// Sets the callback that yields clauses to import until it yields `None`.
+    pub fn set_import(&mut self, callback: ImportCallback) {
+        self.import = Some(callback);
+    }
+
+    /// Exports the learned clause if it is short enough or of low literal
+    /// block distance.
+    #[inline]
+    pub fn export(&mut self, clause: &[Literal], lbd: u32) {
+        if let Some(export) = &mut self.export {
+            if clause.len() <= export.max_len || lbd <= export.max_lbd {
+                (export.callback)(clause)
+            }
+        }
+    }
+
+    /// Returns the next clause to import if any.
+    #[inline]
+    pub fn import(&mut self) -> Option<Vec<Literal>> {
+        self.import.as_mut().and_then(|callback| callback())
+    }
+}
+
+impl Clone for ClauseSharing {
+    fn clone(&self) -> Self {
+        Self::default()
+    }
+}
+
+impl fmt::Debug for ClauseSharing {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ClauseSharing")
+            .field(
+                "max_export_len",
+                &self.export.as_ref().map(|export| export.max_len),
+            )
+            .field(
+                "max_export_lbd",
+                &self.export.as_ref().map(|export| export.max_lbd),
+            )
+            .field("has_import", &self.import.is_some())
+            .finish()
+    }
+}
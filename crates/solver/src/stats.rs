@@ -0,0 +1,90 @@
+use core::{
+    fmt,
+    fmt::Display,
+    time::Duration,
+};
+
+/// Statistics about the work of a solver accumulated over all calls to `solve`.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub struct Statistics {
+    /// The number of decided variables excluding assumptions.
+    pub decisions: u64,
+    /// The number of propagated literals.
+    pub propagations: u64,
+    /// The number of encountered conflicts.
+    pub conflicts: u64,
+    /// The number of restarts.
+    pub restarts: u64,
+    /// The number of learned clauses including unit clauses.
+    pub learned_clauses: u64,
+    /// The number of learned clauses deleted by clause database reductions.
+    pub deleted_clauses: u64,
+    /// The number of garbage collection sweeps of the clause database.
+    pub gc_runs: u64,
+    /// The number of 32-bit clause words freed by deleted clauses.
+    pub freed_words: u64,
+    /// The highest decision level reached.
+    pub max_decision_level: usize,
+    /// The time spent in calls to `solve`.
+    pub solve_time: Duration,
+    /// The time spent propagating assignments.
+    pub propagation_time: Duration,
+    /// The time spent analyzing conflicts and learning clauses.
+    pub analysis_time: Duration,
+    /// The time spent reducing learned clauses and collecting garbage.
+    pub reduction_time: Duration,
+}
+
+impl Display for Statistics {
+    /// Writes every statistic on its own line.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let seconds = self.solve_time.as_secs_f64();
+        let per_second = |count: u64| {
+            if seconds > 0.0 {
+                count as f64 / seconds
+            } else {
+                0.0
+            }
+        };
+        writeln!(
+            f,
+            "decisions:          {:>12} ({:.0}/s)",
+            self.decisions,
+            per_second(self.decisions)
+        )?;
+        writeln!(
+            f,
+            "propagations:       {:>12} ({:.0}/s)",
+            self.propagations,
+            per_second(self.propagations)
+        )?;
+        writeln!(
+            f,
+            "conflicts:          {:>12} ({:.0}/s)",
+            self.conflicts,
+            per_second(self.conflicts)
+        )?;
+        writeln!(f, "restarts:           {:>12}", self.restarts)?;
+        writeln!(f, "learned clauses:    {:>12}", self.learned_clauses)?;
+        writeln!(f, "deleted clauses:    {:>12}", self.deleted_clauses)?;
+        writeln!(f, "gc runs:            {:>12}", self.gc_runs)?;
+        writeln!(f, "freed words:        {:>12}", self.freed_words)?;
+        writeln!(f, "max decision level: {:>12}", self.max_decision_level)?;
+        writeln!(f, "solve time:         {:>12.3}s", seconds)?;
+        writeln!(
+            f,
+            "propagation time:   {:>12.3}s",
+            self.propagation_time.as_secs_f64()
+        )?;
+        writeln!(
+            f,
+            "analysis time:      {:>12.3}s",
+            self.analysis_time.as_secs_f64()
+        )?;
+        write!(
+            f,
+            "reduction time:     {:>12.3}s",
+            self.reduction_time.as_secs_f64()
+        )
+    }
+}
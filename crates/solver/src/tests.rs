@@ -1,11 +1,25 @@
 use crate::{
+    gc,
+    DimacsOptions,
+    JsonTrace,
     Literal,
+    Model,
+    Phase,
+    ReleaseError,
+    RestartStrategy,
     Sign,
     SolveResult,
     Solver,
+    SolverObserver,
+    Statistics,
     Variable,
 };
 use bounded::Index as _;
+use std::sync::{
+    mpsc,
+    Arc,
+    Mutex,
+};
 #[cfg(not(miri))]
 use std::{
     fs,
@@ -61,6 +75,24 @@ fn simple_3sat_works() {
     assert_eq!(solver.solve(vec![]).map(|res| res.is_sat()), Ok(true));
 }
 
+#[test]
+fn cnf_literal_polarity_works() {
+    let mut solver = Solver::from_cnf(
+        &mut &br"
+        p cnf 2 2
+        1 0
+        -2 0
+    "[..],
+    )
+    .unwrap();
+    let model = match solver.solve(vec![]).unwrap() {
+        SolveResult::Sat(sat) => sat.model().clone(),
+        _ => panic!("encountered unexpected unsatisfiable instance"),
+    };
+    let literals = model.into_iter().map(|literal| literal.to_string());
+    assert_eq!(literals.collect::<Vec<_>>(), ["1", "-2"]);
+}
+
 #[test]
 fn simple_unsat_works() {
     let mut solver = Solver::from_cnf(
@@ -112,6 +144,16 @@ fn solve_problem_with_contradictory_unit_clauses() {
     assert!(solver.solve([]).unwrap().is_unsat());
 }
 
+#[test]
+fn tautological_clause_with_further_literals_is_dropped() {
+    let mut solver = Solver::default();
+    let vars = solver.new_literal_chunk(5).into_iter().collect::<Vec<_>>();
+    // Used to be sanitized to the unit clause `-5`.
+    solver.consume_clause([!vars[3], !vars[4], vars[3]]);
+    assert!(solver.solve([vars[4]]).unwrap().is_sat());
+    assert_eq!(solver.clause_database().len(), 0);
+}
+
 #[test]
 #[rustfmt::skip]
 fn test_solve_satisfiable_3sat_problem() {
@@ -289,7 +331,6 @@ fn test_cnf_input() {
 ///
 /// The benchmarks are returned alphabetically sorted by their file names.
 #[cfg(not(miri))]
-
 fn collect_tests_in_path<P>(path: P) -> Vec<Vec<u8>>
 where
     P: AsRef<Path>,
@@ -397,3 +438,1408 @@ fn test_3sat_v150_c645_unsat() {
         );
     }
 }
+
+/// A tiny deterministic pseudo random number generator for randomized tests.
+pub(crate) struct Lcg(pub(crate) u64);
+
+impl Lcg {
+    /// Returns the next pseudo random number in the range `0..bound`.
+    pub(crate) fn next(&mut self, bound: usize) -> usize {
+        self.0 = self
+            .0
+            .wrapping_mul(6364136223846793005)
+            .wrapping_add(1442695040888963407);
+        ((self.0 >> 33) % bound as u64) as usize
+    }
+
+    /// Returns a random literal of one of the given variables.
+    fn literal(&mut self, vars: &[Literal]) -> Literal {
+        let literal = vars[self.next(vars.len())];
+        if self.next(2) == 0 {
+            !literal
+        } else {
+            literal
+        }
+    }
+
+    /// Returns a random non-tautological clause of up to `len` literals.
+    fn clause(&mut self, vars: &[Literal], len: usize) -> Vec<Literal> {
+        let mut clause = Vec::new();
+        for _ in 0..len {
+            let literal = self.literal(vars);
+            if !clause.contains(&!literal) {
+                clause.push(literal);
+            }
+        }
+        clause
+    }
+}
+
+/// Returns `true` if the literal is satisfied by the assignment.
+///
+/// The `n`-th bit of the assignment is the value of the `n`-th variable.
+fn eval_literal(literal: Literal, assignment: u32) -> bool {
+    let value = assignment & (1 << literal.variable().into_index()) != 0;
+    value == (literal.sign() == Sign::POS)
+}
+
+/// Returns the number of literals satisfied by the assignment.
+fn count_satisfied(literals: &[Literal], assignment: u32) -> usize {
+    literals
+        .iter()
+        .filter(|&&literal| eval_literal(literal, assignment))
+        .count()
+}
+
+/// Asserts that the solver agrees with brute force for all assumptions of single variables.
+///
+/// The closure returns `true` if the given assignment satisfies the instance.
+fn assert_agrees_with_brute_force<F>(solver: &mut Solver, vars: &[Literal], is_model: F)
+where
+    F: Fn(u32) -> bool,
+{
+    let assumptions = core::iter::once(None)
+        .chain(vars.iter().flat_map(|&var| [Some(var), Some(!var)]));
+    for assumption in assumptions {
+        let expected = (0..1_u32 << vars.len()).any(|assignment| {
+            is_model(assignment)
+                && assumption.is_none_or(|lit| eval_literal(lit, assignment))
+        });
+        let result = solver.solve(assumption).unwrap();
+        assert_eq!(result.is_sat(), expected);
+        if let SolveResult::Sat(sat_result) = result {
+            let model = sat_result.model();
+            let assignment = vars
+                .iter()
+                .enumerate()
+                .filter(|(_, &var)| model.is_satisfied(var) == Ok(true))
+                .fold(0, |assignment, (n, _)| assignment | 1 << n);
+            assert!(is_model(assignment));
+            assert!(assumption.is_none_or(|lit| eval_literal(lit, assignment)));
+        }
+    }
+}
+
+#[test]
+fn random_cnf_agrees_with_brute_force() {
+    let mut rng = Lcg(42);
+    for _ in 0..200 {
+        let mut solver = Solver::default();
+        let vars = solver.new_literal_chunk(10).into_iter().collect::<Vec<_>>();
+        let len_clauses = 30 + rng.next(20);
+        let clauses = (0..len_clauses)
+            .map(|_| {
+                let len = 1 + rng.next(4);
+                rng.clause(&vars, len)
+            })
+            .collect::<Vec<_>>();
+        for clause in &clauses {
+            solver.consume_clause(clause.iter().copied());
+        }
+        assert_agrees_with_brute_force(&mut solver, &vars, |assignment| {
+            clauses
+                .iter()
+                .all(|clause| count_satisfied(clause, assignment) > 0)
+        });
+    }
+}
+
+/// Returns a solver for the pigeon hole problem with one more pigeon than holes.
+///
+/// The problem is unsatisfiable and requires many conflicts to be refuted.
+pub(crate) fn pigeon_hole_clauses(len_holes: usize) -> Solver {
+    let mut solver = Solver::default();
+    let len_pigeons = len_holes + 1;
+    let vars = solver
+        .new_literal_chunk(len_pigeons * len_holes)
+        .into_iter()
+        .collect::<Vec<_>>();
+    let placed = |pigeon: usize, hole: usize| vars[pigeon * len_holes + hole];
+    for pigeon in 0..len_pigeons {
+        solver.consume_clause((0..len_holes).map(|hole| placed(pigeon, hole)));
+    }
+    for hole in 0..len_holes {
+        for a in 0..len_pigeons {
+            for b in a + 1..len_pigeons {
+                solver.consume_clause([!placed(a, hole), !placed(b, hole)]);
+            }
+        }
+    }
+    solver
+}
+
+#[test]
+fn conflict_limit_works() {
+    let mut solver = pigeon_hole_clauses(6);
+    solver.set_conflict_limit(Some(10));
+    assert!(solver.solve([]).unwrap().is_unknown());
+    assert!(solver.failed_assumptions().is_empty());
+    // The limit applies to every single call.
+    assert!(solver.solve([]).unwrap().is_unknown());
+    solver.set_conflict_limit(None);
+    assert!(solver.solve([]).unwrap().is_unsat());
+}
+
+#[test]
+fn deadline_works() {
+    let mut solver = pigeon_hole_clauses(6);
+    solver.set_deadline(Some(std::time::Instant::now()));
+    assert!(solver.solve([]).unwrap().is_unknown());
+    solver.set_deadline(None);
+    assert!(solver.solve([]).unwrap().is_unsat());
+}
+
+#[test]
+fn interrupter_works() {
+    let mut solver = pigeon_hole_clauses(6);
+    let interrupter = solver.interrupter();
+    interrupter.interrupt();
+    assert!(solver.solve([]).unwrap().is_unknown());
+    // The interruption applies until the handle is reset.
+    assert!(solver.clone().solve([]).unwrap().is_unknown());
+    interrupter.reset();
+    assert!(solver.solve([]).unwrap().is_unsat());
+}
+
+#[test]
+fn stats_work() {
+    let mut solver = pigeon_hole_clauses(6);
+    assert_eq!(solver.stats(), Statistics::default());
+    assert!(solver.solve([]).unwrap().is_unsat());
+    let stats = solver.stats();
+    assert!(stats.decisions > 0);
+    assert!(stats.propagations >= stats.decisions);
+    assert!(stats.conflicts > 0);
+    assert!(stats.learned_clauses <= stats.conflicts);
+    assert!(stats.deleted_clauses <= stats.learned_clauses);
+    assert!(stats.max_decision_level > 0);
+    assert!(stats.solve_time >= stats.propagation_time);
+    // Statistics accumulate over all calls to `solve`.
+    assert!(solver.solve([]).unwrap().is_unsat());
+    assert!(solver.stats().solve_time >= stats.solve_time);
+    assert!(solver.stats().conflicts >= stats.conflicts);
+}
+
+/// Counts the events of the search.
+#[derive(Debug, Default)]
+struct CountingObserver {
+    decisions: u64,
+    propagations: u64,
+    conflicts: u64,
+    learned_clauses: u64,
+    backjumps: u64,
+    restarts: u64,
+    deleted_clauses: u64,
+}
+
+impl SolverObserver for CountingObserver {
+    fn on_decision(&mut self, _literal: Literal) {
+        self.decisions += 1;
+    }
+
+    fn on_propagation(&mut self, _literal: Literal) {
+        self.propagations += 1;
+    }
+
+    fn on_conflict(&mut self, clause: &[Literal]) {
+        assert!(!clause.is_empty());
+        self.conflicts += 1;
+    }
+
+    fn on_learn(&mut self, clause: &[Literal]) {
+        assert!(!clause.is_empty());
+        self.learned_clauses += 1;
+    }
+
+    fn on_backjump(&mut self, _level: usize) {
+        self.backjumps += 1;
+    }
+
+    fn on_restart(&mut self) {
+        self.restarts += 1;
+    }
+
+    fn on_clause_deleted(&mut self, _clause: &[Literal]) {
+        self.deleted_clauses += 1;
+    }
+}
+
+#[test]
+fn observer_works() {
+    let mut solver = pigeon_hole_clauses(7);
+    let observer = Arc::new(Mutex::new(CountingObserver::default()));
+    solver.set_observer(observer.clone());
+    assert!(solver.clone().solve([]).unwrap().is_unsat());
+    assert_eq!(observer.lock().unwrap().decisions, 0);
+    assert!(solver.solve([]).unwrap().is_unsat());
+    let stats = solver.stats();
+    let observer = observer.lock().unwrap();
+    assert_eq!(observer.decisions, stats.decisions);
+    assert!(observer.propagations > 0);
+    assert_eq!(observer.conflicts, stats.conflicts);
+    assert_eq!(observer.learned_clauses, stats.learned_clauses);
+    assert_eq!(observer.backjumps, stats.learned_clauses);
+    assert_eq!(observer.restarts, stats.restarts);
+    assert_eq!(observer.deleted_clauses, stats.deleted_clauses);
+    assert!(solver.take_observer().is_some());
+    assert!(solver.take_observer().is_none());
+}
+
+#[test]
+fn json_trace_works() {
+    let mut solver = pigeon_hole_clauses(3);
+    let trace = Arc::new(Mutex::new(JsonTrace::new(Vec::new())));
+    solver.set_observer(trace.clone());
+    assert!(solver.solve([]).unwrap().is_unsat());
+    drop(solver);
+    let trace = Arc::try_unwrap(trace).unwrap().into_inner().unwrap();
+    let trace = String::from_utf8(trace.into_inner().unwrap()).unwrap();
+    assert!(trace
+        .lines()
+        .any(|line| line.starts_with(r#"{"event":"decision","literal":"#)));
+    assert!(trace
+        .lines()
+        .any(|line| line.starts_with(r#"{"event":"learn","clause":["#)));
+    for line in trace.lines() {
+        assert!(line.starts_with(r#"{"event":""#) && line.ends_with('}'));
+    }
+}
+
+/// Returns a random satisfiable or unsatisfiable instance over the variables.
+fn random_clauses(rng: &mut Lcg, vars: &[Literal]) -> Vec<Vec<Literal>> {
+    (0..4 * vars.len() + rng.next(10))
+        .map(|_| {
+            let len = 1 + rng.next(4);
+            rng.clause(vars, len)
+        })
+        .collect()
+}
+
+#[test]
+fn learn_callback_works() {
+    let mut rng = Lcg(7);
+    for _ in 0..100 {
+        let mut solver = Solver::default();
+        let vars = solver.new_literal_chunk(10).into_iter().collect::<Vec<_>>();
+        let clauses = random_clauses(&mut rng, &vars);
+        for clause in &clauses {
+            solver.consume_clause(clause.iter().copied());
+        }
+        let learned = Arc::new(Mutex::new(Vec::new()));
+        let sink = learned.clone();
+        solver.set_learn_callback(4, move |clause| {
+            sink.lock().unwrap().push(clause.to_vec())
+        });
+        solver.solve([]).unwrap();
+        // Learned clauses are satisfied by all models of the instance.
+        for clause in learned.lock().unwrap().iter() {
+            assert!(!clause.is_empty() && clause.len() <= 4);
+            for assignment in 0..1_u32 << vars.len() {
+                let is_model = clauses
+                    .iter()
+                    .all(|clause| count_satisfied(clause, assignment) > 0);
+                assert!(!is_model || count_satisfied(clause, assignment) > 0);
+            }
+        }
+    }
+}
+
+#[test]
+fn import_callback_works() {
+    let mut solver = Solver::default();
+    let vars = solver.new_literal_chunk(3).into_iter().collect::<Vec<_>>();
+    let (a, b, c) = (vars[0], vars[1], vars[2]);
+    solver.consume_clause([a, b]);
+    let imports = Arc::new(Mutex::new(vec![
+        vec![!c],
+        vec![!b, c, c],
+        vec![!a, Literal::from(100)],
+        vec![!a, a],
+    ]));
+    let source = imports.clone();
+    solver.set_import_callback(move || source.lock().unwrap().pop());
+    match solver.solve([]).unwrap() {
+        SolveResult::Sat(sat) => {
+            assert_eq!(sat.model().is_satisfied(a), Ok(true));
+            assert_eq!(sat.model().is_satisfied(b), Ok(false));
+            assert_eq!(sat.model().is_satisfied(c), Ok(false));
+        }
+        _ => panic!("expected satisfiable instance"),
+    }
+    assert!(imports.lock().unwrap().is_empty());
+    imports.lock().unwrap().push(vec![!a]);
+    assert!(solver.solve([]).unwrap().is_unsat());
+    assert!(solver.solve([]).unwrap().is_unsat());
+}
+
+#[test]
+fn shared_clauses_agree_with_brute_force() {
+    let mut rng = Lcg(11);
+    for _ in 0..100 {
+        let mut exporter = Solver::default();
+        let vars = exporter
+            .new_literal_chunk(8)
+            .into_iter()
+            .collect::<Vec<_>>();
+        let clauses = random_clauses(&mut rng, &vars);
+        for clause in &clauses {
+            exporter.consume_clause(clause.iter().copied());
+        }
+        let mut importer = exporter.clone();
+        let (sender, receiver) = mpsc::channel();
+        exporter.set_learn_callback(usize::MAX, move |clause| {
+            sender.send(clause.to_vec()).unwrap()
+        });
+        importer.set_import_callback(move || receiver.try_recv().ok());
+        exporter.solve([]).unwrap();
+        assert_agrees_with_brute_force(&mut importer, &vars, |assignment| {
+            clauses
+                .iter()
+                .all(|clause| count_satisfied(clause, assignment) > 0)
+        });
+    }
+}
+
+#[test]
+fn seeded_solving_agrees_with_brute_force() {
+    let mut rng = Lcg(42);
+    let mut models = Vec::new();
+    for seed in 0..50 {
+        let mut solver = Solver::default();
+        solver.set_seed(seed);
+        let vars = solver.new_literal_chunk(10).into_iter().collect::<Vec<_>>();
+        let clauses = (0..30)
+            .map(|_| {
+                let len = 2 + rng.next(3);
+                rng.clause(&vars, len)
+            })
+            .collect::<Vec<_>>();
+        for clause in &clauses {
+            solver.consume_clause(clause.iter().copied());
+        }
+        assert_agrees_with_brute_force(&mut solver, &vars, |assignment| {
+            clauses
+                .iter()
+                .all(|clause| count_satisfied(clause, assignment) > 0)
+        });
+        // Different seeds lead to different models of the same instance
+        // since the first decided variable is the only satisfied one.
+        let mut solver = Solver::default();
+        solver.set_seed(seed);
+        let vars = solver.new_literal_chunk(10).into_iter().collect::<Vec<_>>();
+        solver.consume_clause(vars.iter().copied());
+        solver.add_at_most(vars.iter().copied(), 1);
+        if let SolveResult::Sat(sat) = solver.solve([]).unwrap() {
+            models.push(sat.model().clone());
+        }
+    }
+    models.sort_by_key(|model| model.to_string());
+    models.dedup();
+    assert!(models.len() > 1);
+}
+
+#[test]
+#[rustfmt::skip]
+fn incremental_solving_works() {
+    let mut solver = Solver::default();
+    let vars = solver.new_literal_chunk(3).into_iter().collect::<Vec<_>>();
+    solver.consume_clause([vars[0], vars[1], vars[2]]);
+    assert!(solver.solve([!vars[0], !vars[1]]).unwrap().is_sat());
+    solver.consume_clause([!vars[2]]);
+    assert!(solver.solve([!vars[0], !vars[1]]).unwrap().is_unsat());
+    assert!(solver.solve([!vars[0]]).unwrap().is_sat());
+    solver.consume_clause([!vars[1], vars[0]]);
+    assert!(solver.solve([!vars[0]]).unwrap().is_unsat());
+    assert!(solver.solve([]).unwrap().is_sat());
+}
+
+#[test]
+#[rustfmt::skip]
+fn at_most_works() {
+    let mut solver = Solver::default();
+    let vars = solver.new_literal_chunk(5).into_iter().collect::<Vec<_>>();
+    solver.add_at_most(vars.iter().copied(), 2);
+    assert!(solver.solve([vars[0], vars[1]]).unwrap().is_sat());
+    assert!(solver.solve([vars[0], vars[1], vars[4]]).unwrap().is_unsat());
+    solver.consume_clause([vars[2], vars[3]]);
+    assert!(solver.solve([vars[0], vars[1]]).unwrap().is_unsat());
+    assert!(solver.solve([vars[0]]).unwrap().is_sat());
+}
+
+#[test]
+#[rustfmt::skip]
+fn at_least_works() {
+    let mut solver = Solver::default();
+    let vars = solver.new_literal_chunk(5).into_iter().collect::<Vec<_>>();
+    solver.add_at_least(vars.iter().copied(), 3);
+    assert!(solver.solve([!vars[0], !vars[1]]).unwrap().is_sat());
+    assert!(solver.solve([!vars[0], !vars[1], !vars[2]]).unwrap().is_unsat());
+    solver.consume_clause([!vars[2], !vars[3]]);
+    assert!(solver.solve([!vars[0], !vars[1]]).unwrap().is_unsat());
+    assert!(solver.solve([!vars[0]]).unwrap().is_sat());
+}
+
+#[test]
+#[rustfmt::skip]
+fn trivial_cardinality_constraints_work() {
+    let mut solver = Solver::default();
+    let vars = solver.new_literal_chunk(3).into_iter().collect::<Vec<_>>();
+    // Trivially satisfied constraints.
+    solver.add_at_most(vars.iter().copied(), 3);
+    solver.add_at_least(vars.iter().copied(), 0);
+    // Complementary literals always contribute one satisfied literal.
+    solver.add_at_least([vars[0], !vars[0], vars[1]], 2);
+    assert!(solver.solve([!vars[1]]).unwrap().is_unsat());
+    // Equivalent to unit clauses.
+    solver.add_at_most([vars[1], vars[2]], 0);
+    assert!(solver.solve([]).unwrap().is_unsat());
+    let mut solver = Solver::default();
+    let vars = solver.new_literal_chunk(3).into_iter().collect::<Vec<_>>();
+    // Unsatisfiable constraint.
+    solver.add_at_least(vars.iter().copied(), 4);
+    assert!(solver.solve([]).unwrap().is_unsat());
+}
+
+#[test]
+fn pigeon_hole_with_cardinality_constraints_is_unsat() {
+    let len_holes = 5;
+    let len_pigeons = len_holes + 1;
+    let mut solver = Solver::default();
+    let vars = solver
+        .new_literal_chunk(len_pigeons * len_holes)
+        .into_iter()
+        .collect::<Vec<_>>();
+    let placed = |pigeon: usize, hole: usize| vars[pigeon * len_holes + hole];
+    for pigeon in 0..len_pigeons {
+        solver.add_at_least((0..len_holes).map(|hole| placed(pigeon, hole)), 1);
+    }
+    for hole in 0..len_holes {
+        solver.add_at_most((0..len_pigeons).map(|pigeon| placed(pigeon, hole)), 1);
+    }
+    assert!(solver.solve([]).unwrap().is_unsat());
+    // With as many pigeons as holes the problem becomes satisfiable.
+    let mut solver = Solver::default();
+    let vars = solver
+        .new_literal_chunk(len_holes * len_holes)
+        .into_iter()
+        .collect::<Vec<_>>();
+    let placed = |pigeon: usize, hole: usize| vars[pigeon * len_holes + hole];
+    for pigeon in 0..len_holes {
+        solver.add_at_least((0..len_holes).map(|hole| placed(pigeon, hole)), 1);
+    }
+    for hole in 0..len_holes {
+        solver.add_at_most((0..len_holes).map(|pigeon| placed(pigeon, hole)), 1);
+    }
+    assert!(solver.solve([]).unwrap().is_sat());
+}
+
+#[test]
+fn random_cardinality_constraints_agree_with_brute_force() {
+    let mut rng = Lcg(7);
+    for _ in 0..200 {
+        let mut solver = Solver::default();
+        let vars = solver.new_literal_chunk(8).into_iter().collect::<Vec<_>>();
+        let mut clauses = Vec::new();
+        for _ in 0..rng.next(10) {
+            let len = 2 + rng.next(2);
+            let clause = rng.clause(&vars, len);
+            solver.consume_clause(clause.iter().copied());
+            clauses.push(clause);
+        }
+        let mut at_least = Vec::new();
+        let mut at_most = Vec::new();
+        for _ in 0..1 + rng.next(4) {
+            let mut literals = vars.clone();
+            for n in 0..literals.len() {
+                let other = rng.next(literals.len());
+                literals.swap(n, other);
+            }
+            literals.truncate(2 + rng.next(vars.len() - 1));
+            for literal in &mut literals {
+                if rng.next(2) == 0 {
+                    *literal = !*literal;
+                }
+            }
+            let k = rng.next(literals.len() + 1);
+            if rng.next(2) == 0 {
+                solver.add_at_least(literals.iter().copied(), k);
+                at_least.push((literals, k));
+            } else {
+                solver.add_at_most(literals.iter().copied(), k);
+                at_most.push((literals, k));
+            }
+        }
+        assert_agrees_with_brute_force(&mut solver, &vars, |assignment| {
+            clauses
+                .iter()
+                .all(|clause| count_satisfied(clause, assignment) > 0)
+                && at_least
+                    .iter()
+                    .all(|(literals, k)| count_satisfied(literals, assignment) >= *k)
+                && at_most
+                    .iter()
+                    .all(|(literals, k)| count_satisfied(literals, assignment) <= *k)
+        });
+    }
+}
+
+#[test]
+#[rustfmt::skip]
+fn pseudo_boolean_works() {
+    let mut solver = Solver::default();
+    let vars = solver.new_literal_chunk(4).into_iter().collect::<Vec<_>>();
+    // 3*x0 + 2*x1 + 2*x2 - x3 >= 4
+    solver.add_pseudo_boolean([(3, vars[0]), (2, vars[1]), (2, vars[2]), (-1, vars[3])], 4);
+    assert!(solver.solve([!vars[0], vars[3]]).unwrap().is_unsat());
+    assert!(solver.solve([!vars[0], !vars[3]]).unwrap().is_sat());
+    assert!(solver.solve([!vars[1], !vars[2]]).unwrap().is_unsat());
+    assert!(solver.solve([!vars[1], vars[3]]).unwrap().is_sat());
+}
+
+#[test]
+#[rustfmt::skip]
+fn at_least_with_duplicate_literals_works() {
+    let mut solver = Solver::default();
+    let vars = solver.new_literal_chunk(3).into_iter().collect::<Vec<_>>();
+    // Equivalent to: 2*x0 + x1 + x2 >= 3
+    solver.add_at_least([vars[0], vars[1], vars[0], vars[2]], 3);
+    assert!(solver.solve([!vars[0]]).unwrap().is_unsat());
+    assert!(solver.solve([!vars[1]]).unwrap().is_sat());
+    assert!(solver.solve([!vars[1], !vars[2]]).unwrap().is_unsat());
+}
+
+#[test]
+fn random_pseudo_boolean_constraints_agree_with_brute_force() {
+    let mut rng = Lcg(13);
+    for _ in 0..200 {
+        let mut solver = Solver::default();
+        let vars = solver.new_literal_chunk(8).into_iter().collect::<Vec<_>>();
+        let mut constraints = Vec::new();
+        for _ in 0..1 + rng.next(4) {
+            let terms = (0..1 + rng.next(8))
+                .map(|_| (rng.next(9) as i64 - 4, rng.literal(&vars)))
+                .collect::<Vec<_>>();
+            let degree = rng.next(10) as i64 - 2;
+            solver.add_pseudo_boolean(terms.iter().copied(), degree);
+            constraints.push((terms, degree));
+        }
+        assert_agrees_with_brute_force(&mut solver, &vars, |assignment| {
+            constraints.iter().all(|(terms, degree)| {
+                let sum = terms
+                    .iter()
+                    .filter(|(_, literal)| eval_literal(*literal, assignment))
+                    .map(|(coefficient, _)| coefficient)
+                    .sum::<i64>();
+                sum >= *degree
+            })
+        });
+    }
+}
+
+#[test]
+fn failed_assumptions_work() {
+    let mut solver = Solver::default();
+    #[rustfmt::skip]
+    let [a, b, c, d] = [0, 1, 2, 3].map(|_| solver.new_literal());
+    solver.consume_clause([!a, !b, d]);
+    solver.consume_clause([!d, !c]);
+    assert!(solver.solve([a, b]).unwrap().is_sat());
+    assert!(solver.failed_assumptions().is_empty());
+    assert!(solver.solve([!d, a, c, b]).unwrap().is_unsat());
+    let mut failed = solver.failed_assumptions().to_vec();
+    failed.sort();
+    let mut expected = vec![a, b, !d];
+    expected.sort();
+    assert_eq!(failed, expected);
+    assert!(solver.solve([a, !a]).unwrap().is_unsat());
+    assert_eq!(solver.failed_assumptions().len(), 2);
+    solver.consume_clause([!a]);
+    assert!(solver.solve([a, b]).unwrap().is_unsat());
+    assert_eq!(solver.failed_assumptions(), &[a]);
+    solver.consume_clause([a]);
+    assert!(solver.solve([a, b]).unwrap().is_unsat());
+    assert!(solver.failed_assumptions().is_empty());
+}
+
+#[test]
+fn random_failed_assumptions_are_unsatisfiable() {
+    let mut rng = Lcg(7);
+    for _ in 0..200 {
+        let mut solver = Solver::default();
+        let vars = solver.new_literal_chunk(12).into_iter().collect::<Vec<_>>();
+        for _ in 0..20 + rng.next(10) {
+            let len = 2 + rng.next(2);
+            let clause = rng.clause(&vars, len);
+            solver.consume_clause(clause);
+        }
+        for _ in 0..rng.next(3) {
+            let terms = (0..4)
+                .map(|_| (1 + rng.next(3) as i64, rng.literal(&vars)))
+                .collect::<Vec<_>>();
+            solver.add_pseudo_boolean(terms, 3);
+        }
+        for _ in 0..10 {
+            let assumptions = (0..6).map(|_| rng.literal(&vars)).collect::<Vec<_>>();
+            if solver.solve(assumptions.clone()).unwrap().is_sat() {
+                continue
+            }
+            let failed = solver.failed_assumptions().to_vec();
+            assert!(failed.iter().all(|literal| assumptions.contains(literal)));
+            assert!(solver.solve(failed).unwrap().is_unsat());
+        }
+    }
+}
+
+/// Returns the assignment of the variables under the model.
+///
+/// The `n`-th bit of the assignment is the value of the `n`-th variable.
+fn model_assignment(model: &Model, vars: &[Literal]) -> u32 {
+    vars.iter()
+        .enumerate()
+        .filter(|(_, &var)| model.is_satisfied(var) == Ok(true))
+        .fold(0, |assignment, (n, _)| assignment | 1 << n)
+}
+
+#[test]
+fn random_model_enumeration_agrees_with_brute_force() {
+    let mut rng = Lcg(42);
+    for _ in 0..50 {
+        let mut solver = Solver::default();
+        let vars = solver.new_literal_chunk(8).into_iter().collect::<Vec<_>>();
+        let len_clauses = rng.next(20);
+        let clauses = (0..len_clauses)
+            .map(|_| {
+                let len = 1 + rng.next(3);
+                rng.clause(&vars, len)
+            })
+            .collect::<Vec<_>>();
+        for clause in &clauses {
+            solver.consume_clause(clause.iter().copied());
+        }
+        let is_model = |assignment| {
+            clauses
+                .iter()
+                .all(|clause| count_satisfied(clause, assignment) > 0)
+        };
+        let projection = vars[..1 + rng.next(vars.len())]
+            .iter()
+            .map(|var| var.variable())
+            .collect::<Vec<_>>();
+        let mask = (1_u32 << projection.len()) - 1;
+        let mut expected = (0..1_u32 << vars.len())
+            .filter(|&assignment| is_model(assignment))
+            .map(|assignment| assignment & mask)
+            .collect::<Vec<_>>();
+        expected.sort_unstable();
+        expected.dedup();
+        for (projection, mask) in [(None, u32::MAX), (Some(&projection[..]), mask)] {
+            let mut found = solver
+                .enumerate_models(projection, None)
+                .map(|model| {
+                    let assignment = model_assignment(&model, &vars);
+                    assert!(is_model(assignment));
+                    assignment & mask
+                })
+                .collect::<Vec<_>>();
+            let len_found = found.len();
+            found.sort_unstable();
+            found.dedup();
+            assert_eq!(found.len(), len_found);
+            if projection.is_some() {
+                assert_eq!(found, expected);
+            } else {
+                let len_models = (0..1_u32 << vars.len())
+                    .filter(|&assignment| is_model(assignment))
+                    .count();
+                assert_eq!(found.len(), len_models);
+            }
+        }
+        // The solver is left with the same models after the enumeration.
+        assert_eq!(solver.solve([]).unwrap().is_sat(), !expected.is_empty());
+    }
+}
+
+#[test]
+fn model_enumeration_limit_works() {
+    let mut solver = Solver::default();
+    let vars = solver.new_literal_chunk(4).into_iter().collect::<Vec<_>>();
+    solver.consume_clause([vars[0], vars[1]]);
+    let models = solver.enumerate_models(Some(&[]), None).collect::<Vec<_>>();
+    assert_eq!(models.len(), 1);
+    assert_eq!(models[0].into_iter().count(), vars.len());
+    assert_eq!(solver.enumerate_models(None, Some(5)).count(), 5);
+    assert_eq!(solver.enumerate_models(None, Some(0)).count(), 0);
+    let projection = vars.iter().map(|var| var.variable()).collect::<Vec<_>>();
+    assert_eq!(solver.enumerate_models(Some(&projection), None).count(), 12);
+}
+
+#[test]
+fn model_enumeration_leaves_no_clauses_and_variables_behind() {
+    let mut solver = Solver::default();
+    let vars = solver.new_literal_chunk(2).into_iter().collect::<Vec<_>>();
+    let (a, b) = (vars[0], vars[1]);
+    let handle = solver.consume_clause([a, b]);
+    assert_eq!(solver.enumerate_models(None, None).count(), 3);
+    // The guard variable of the first enumeration is reused by the following ones.
+    let len_variables = solver.len_variables();
+    let len_clauses = solver
+        .clause_database()
+        .live_clauses()
+        .irredundant()
+        .count();
+    assert_eq!(len_clauses, 1);
+    for _ in 0..3 {
+        assert_eq!(solver.enumerate_models(None, None).count(), 3);
+        assert_eq!(solver.enumerate_models(None, Some(2)).count(), 2);
+        assert_eq!(solver.len_variables(), len_variables);
+        assert_eq!(
+            solver
+                .clause_database()
+                .live_clauses()
+                .irredundant()
+                .count(),
+            len_clauses
+        );
+    }
+    assert!(solver.remove_clause(handle));
+    assert_eq!(
+        solver
+            .clause_database()
+            .live_clauses()
+            .irredundant()
+            .count(),
+        0
+    );
+    assert_eq!(solver.release_variable(a), Ok(()));
+    assert_eq!(solver.enumerate_models(None, None).count(), 2);
+}
+
+#[test]
+fn model_enumeration_omits_internal_variables() {
+    let mut solver = Solver::default();
+    let vars = solver.new_literal_chunk(2).into_iter().collect::<Vec<_>>();
+    solver.consume_clause([vars[0], vars[1]]);
+    solver.push();
+    solver.consume_clause([vars[0]]);
+    let third = solver.new_literal();
+    let released = solver.new_literal();
+    assert_eq!(solver.release_variable(released), Ok(()));
+    let models = solver
+        .enumerate_models(None, None)
+        .map(|model| model.into_iter().collect::<Vec<_>>())
+        .collect::<Vec<_>>();
+    assert_eq!(models.len(), 4);
+    for model in &models {
+        assert_eq!(model.len(), 3);
+        assert!(model.contains(&vars[0]));
+        assert!(model
+            .iter()
+            .any(|literal| literal.variable() == third.variable()));
+    }
+    let projection = [vars[1].variable()];
+    for model in solver.enumerate_models(Some(&projection), None) {
+        assert_eq!(model.into_iter().count(), 3);
+    }
+    assert!(solver.pop());
+    assert_eq!(solver.enumerate_models(None, None).count(), 6);
+}
+
+#[test]
+#[rustfmt::skip]
+fn backbone_works() {
+    let mut solver = Solver::default();
+    let vars = solver.new_literal_chunk(10).into_iter().collect::<Vec<_>>();
+    solver.consume_clause([ vars[1],  vars[3],  vars[5]]);
+    solver.consume_clause([!vars[1], !vars[7],  vars[5]]);
+    solver.consume_clause([!vars[3], !vars[7], !vars[0]]);
+    solver.consume_clause([!vars[9], !vars[6], !vars[1]]);
+    assert_eq!(solver.backbone([]), Some(vec![]));
+    assert_eq!(
+        solver.backbone([vars[1], vars[7], vars[6]]),
+        Some(vec![vars[1], vars[5], vars[6], vars[7], !vars[9]])
+    );
+    solver.consume_clause([vars[2]]);
+    solver.consume_clause([!vars[2], !vars[4]]);
+    assert_eq!(solver.backbone([]), Some(vec![vars[2], !vars[4]]));
+    assert_eq!(solver.backbone([vars[4]]), None);
+}
+
+#[test]
+fn backbone_omits_internal_variables() {
+    let mut solver = Solver::default();
+    let vars = solver.new_literal_chunk(3).into_iter().collect::<Vec<_>>();
+    solver.consume_clause([vars[0], vars[1]]);
+    solver.consume_clause([!vars[2]]);
+    solver.push();
+    solver.consume_clause([vars[0]]);
+    assert_eq!(solver.backbone([]), Some(vec![vars[0], !vars[2]]));
+    solver.push();
+    assert_eq!(
+        solver.backbone([!vars[1]]),
+        Some(vec![vars[0], !vars[1], !vars[2]])
+    );
+    assert!(solver.pop());
+    assert!(solver.pop());
+    assert_eq!(solver.backbone([]), Some(vec![!vars[2]]));
+}
+
+#[test]
+fn random_backbones_agree_with_single_literal_tests() {
+    let mut rng = Lcg(42);
+    for _ in 0..100 {
+        let mut solver = Solver::default();
+        let len_vars = 1 + rng.next(30);
+        let vars = solver
+            .new_literal_chunk(len_vars)
+            .into_iter()
+            .collect::<Vec<_>>();
+        let clauses = (0..rng.next(3 * len_vars))
+            .map(|_| {
+                let len = 1 + rng.next(3);
+                rng.clause(&vars, len)
+            })
+            .collect::<Vec<_>>();
+        for clause in &clauses {
+            solver.consume_clause(clause.iter().copied());
+        }
+        let assumptions = (0..rng.next(3))
+            .map(|_| rng.literal(&vars))
+            .collect::<Vec<_>>();
+        let backbone = solver.backbone(assumptions.iter().copied());
+        // Each backbone literal is forced and each other literal of a model is not.
+        let mut solve =
+            |assumptions: Vec<Literal>| solver.solve(assumptions).unwrap().is_sat();
+        let Some(backbone) = backbone else {
+            assert!(!solve(assumptions));
+            continue
+        };
+        for &var in &vars {
+            let is_pos_sat = solve(assumptions.iter().copied().chain([var]).collect());
+            let is_neg_sat = solve(assumptions.iter().copied().chain([!var]).collect());
+            let expected = match (is_pos_sat, is_neg_sat) {
+                (true, false) => Some(var),
+                (false, true) => Some(!var),
+                (true, true) => None,
+                (false, false) => panic!("encountered unexpected unsatisfiable formula"),
+            };
+            let found = backbone
+                .iter()
+                .copied()
+                .find(|literal| literal.variable() == var.variable());
+            assert_eq!(found, expected);
+        }
+    }
+}
+
+#[test]
+fn write_dimacs_works() {
+    let mut solver = Solver::default();
+    let vars = solver.new_literal_chunk(4).into_iter().collect::<Vec<_>>();
+    let (a, b, c, d) = (vars[0], vars[1], vars[2], vars[3]);
+    solver.consume_clause([a, b, b]);
+    solver.consume_clause([!a]);
+    solver.consume_clause([!a]);
+    solver.consume_clause([!b, c]);
+    solver.consume_clause([c, !c]);
+    solver.consume_clause([c, d]);
+    // Propagation may reorder the literals of clauses so that they are sorted.
+    let write = |solver: &mut Solver, options| {
+        let mut output = Vec::new();
+        solver.write_dimacs(&mut output, options).unwrap();
+        let mut lines = String::new();
+        for line in String::from_utf8(output).unwrap().lines() {
+            let mut tokens = line.split(' ').collect::<Vec<_>>();
+            if tokens[0] != "p" {
+                tokens.sort_by_key(|token| token.parse::<i32>().unwrap().abs());
+                tokens.rotate_left(1);
+            }
+            lines.push_str(&tokens.join(" "));
+            lines.push('\n');
+        }
+        lines
+    };
+    assert_eq!(
+        write(&mut solver, DimacsOptions::default()),
+        "p cnf 4 4\n-1 0\n1 2 0\n-2 3 0\n3 4 0\n",
+    );
+    assert_eq!(
+        write(&mut solver, DimacsOptions::default().root_assignment(true)),
+        "p cnf 4 6\n-1 0\n2 0\n3 0\n1 2 0\n-2 3 0\n3 4 0\n",
+    );
+    // Writing the root assignment leaves the solver ready to solve.
+    assert!(solver.solve([!c]).unwrap().is_unsat());
+    solver.consume_clause([!c]);
+    assert_eq!(
+        write(&mut solver, DimacsOptions::default().root_assignment(true)),
+        "p cnf 4 6\n0\n-1 0\n-3 0\n1 2 0\n-2 3 0\n3 4 0\n",
+    );
+    solver.consume_clause([]);
+    assert!(write(&mut solver, DimacsOptions::default()).starts_with("p cnf 4 6\n0\n"));
+}
+
+#[test]
+fn write_dimacs_omits_learned_unit_clauses_by_default() {
+    let mut solver = Solver::default();
+    solver.set_phase(Phase::Negative);
+    let vars = solver.new_literal_chunk(3).into_iter().collect::<Vec<_>>();
+    let (a, b, c) = (vars[0], vars[1], vars[2]);
+    for clause in [[a, b, c], [a, b, !c], [a, !b, c], [a, !b, !c]] {
+        solver.consume_clause(clause);
+    }
+    assert!(solver.solve([]).unwrap().is_sat());
+    assert!(solver.hard_facts.contains(&a));
+    let write = |solver: &mut Solver, options| {
+        let mut output = Vec::new();
+        solver.write_dimacs(&mut output, options).unwrap();
+        String::from_utf8(output).unwrap()
+    };
+    let written = write(&mut solver, DimacsOptions::default());
+    assert!(written.starts_with("p cnf 3 4\n"));
+    assert!(!written.lines().any(|line| line == "1 0"));
+    let written = write(&mut solver, DimacsOptions::default().learnt_clauses(true));
+    assert!(written.lines().any(|line| line == "1 0"));
+    let written = write(&mut solver, DimacsOptions::default().root_assignment(true));
+    assert!(written.lines().any(|line| line == "1 0"));
+}
+
+#[test]
+fn written_dimacs_agrees_with_brute_force() {
+    let mut rng = Lcg(44);
+    for _ in 0..100 {
+        let mut solver = Solver::default();
+        let vars = solver.new_literal_chunk(10).into_iter().collect::<Vec<_>>();
+        let clauses = random_clauses(&mut rng, &vars);
+        for clause in &clauses {
+            solver.consume_clause(clause.iter().copied());
+        }
+        let is_model = |assignment: u32| {
+            clauses
+                .iter()
+                .all(|clause| count_satisfied(clause, assignment) > 0)
+        };
+        solver.solve([]).unwrap();
+        let options = DimacsOptions::default()
+            .learnt_clauses(rng.next(2) == 0)
+            .root_assignment(rng.next(2) == 0);
+        let mut output = Vec::new();
+        solver.write_dimacs(&mut output, options).unwrap();
+        let mut written = Solver::from_cnf(&mut &output[..]).unwrap();
+        assert_agrees_with_brute_force(&mut written, &vars, is_model);
+    }
+}
+
+#[test]
+fn live_clauses_of_solver_work() {
+    let (len_holes, len_pigeons) = (5, 6);
+    let mut solver = Solver::default();
+    let vars = solver
+        .new_literal_chunk(len_pigeons * len_holes)
+        .into_iter()
+        .collect::<Vec<_>>();
+    let placed = |pigeon: usize, hole: usize| vars[pigeon * len_holes + hole];
+    for pigeon in 0..len_pigeons {
+        solver.consume_clause((0..len_holes).map(|hole| placed(pigeon, hole)));
+    }
+    for hole in 0..len_holes {
+        for a in 0..len_pigeons {
+            for b in a + 1..len_pigeons {
+                solver.consume_clause([!placed(a, hole), !placed(b, hole)]);
+            }
+        }
+    }
+    let len_irredundant = len_pigeons + len_holes * len_pigeons * (len_pigeons - 1) / 2;
+    assert_eq!(
+        solver.clause_database().live_clauses().count(),
+        len_irredundant
+    );
+    assert!(solver.solve([]).unwrap().is_unsat());
+    let clauses = solver.clause_database();
+    assert_eq!(
+        clauses.live_clauses().irredundant().count(),
+        len_irredundant
+    );
+    assert!(clauses
+        .live_clauses()
+        .irredundant()
+        .all(|(_, clause)| !clause.is_learnt() && clause.activity() == 0));
+    let learnt = clauses.live_clauses().learnt().collect::<Vec<_>>();
+    assert!(!learnt.is_empty());
+    assert!(learnt.iter().all(|(cref, clause)| {
+        clause.is_learnt()
+            && clause.lbd() >= 1
+            && clauses.resolve(*cref).unwrap().literals().as_slice()
+                == clause.literals().as_slice()
+    }));
+    // Learned clauses that took part in conflict analysis gained activity.
+    assert!(learnt.iter().any(|(_, clause)| clause.activity() > 0));
+    assert_eq!(
+        clauses.live_clauses().len_range(2..=2).count(),
+        len_irredundant - len_pigeons
+            + learnt
+                .iter()
+                .filter(|(_, clause)| clause.literals().len() == 2)
+                .count()
+    );
+}
+
+/// Returns a solver that reduces its learned clauses and collects the garbage
+/// of its clause database upon almost every restart after every conflict.
+fn garbage_collecting_solver() -> Solver {
+    let mut solver = Solver {
+        max_learnt_clauses: 2,
+        ..Solver::default()
+    };
+    solver.set_restart_strategy(RestartStrategy::Luby { unit: 1 });
+    solver.set_gc_fraction(0.0);
+    solver
+}
+
+#[test]
+fn gc_with_heavy_clause_deletion_works() {
+    let (len_holes, len_pigeons) = (6, 7);
+    let mut solver = garbage_collecting_solver();
+    let vars = solver
+        .new_literal_chunk(len_pigeons * len_holes)
+        .into_iter()
+        .collect::<Vec<_>>();
+    let placed = |pigeon: usize, hole: usize| vars[pigeon * len_holes + hole];
+    for pigeon in 0..len_pigeons - 1 {
+        solver.consume_clause((0..len_holes).map(|hole| placed(pigeon, hole)));
+    }
+    for hole in 0..len_holes {
+        for a in 0..len_pigeons {
+            for b in a + 1..len_pigeons {
+                solver.consume_clause([!placed(a, hole), !placed(b, hole)]);
+            }
+        }
+    }
+    // Every pigeon except the last one fits into a hole.
+    for hole in 0..len_holes {
+        assert!(solver
+            .solve([placed(len_pigeons - 1, hole)])
+            .unwrap()
+            .is_unsat());
+    }
+    assert!(solver.solve([]).unwrap().is_sat());
+    solver.consume_clause((0..len_holes).map(|hole| placed(len_pigeons - 1, hole)));
+    assert!(solver.solve([]).unwrap().is_unsat());
+    let stats = solver.stats();
+    assert!(stats.deleted_clauses > 0);
+    assert!(stats.gc_runs > 0);
+    // The learned clauses kept by the solver are the living learned clauses.
+    let clauses = solver.clause_database();
+    assert!(clauses.len_freed_words() < clauses.len_words());
+    assert_eq!(
+        solver.learnt_clauses,
+        clauses
+            .live_clauses()
+            .learnt()
+            .map(|(cref, _)| cref)
+            .collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn random_incremental_solving_with_gc_agrees_with_brute_force() {
+    let mut rng = Lcg(46);
+    for _ in 0..50 {
+        let mut solver = garbage_collecting_solver();
+        let vars = solver.new_literal_chunk(10).into_iter().collect::<Vec<_>>();
+        let mut clauses = Vec::new();
+        for round in 1..=5 {
+            for _ in 0..6 + rng.next(6) {
+                let len = 2 + rng.next(2);
+                let clause = rng.clause(&vars, len);
+                solver.consume_clause(clause.iter().copied());
+                clauses.push(clause);
+            }
+            assert_agrees_with_brute_force(&mut solver, &vars, |assignment| {
+                clauses
+                    .iter()
+                    .all(|clause| count_satisfied(clause, assignment) > 0)
+            });
+            // Deletes about half of the learned clauses and sweeps them.
+            let Solver {
+                clauses: clause_db,
+                learnt_clauses,
+                ..
+            } = &mut solver;
+            learnt_clauses.retain(|&cref| {
+                rng.next(2) == 0 || {
+                    clause_db.remove_clause(cref);
+                    false
+                }
+            });
+            gc::collect_garbage(&mut solver);
+            assert!(solver.stats().gc_runs as usize >= round);
+            assert_eq!(solver.clause_database().len_freed_words(), 0);
+        }
+    }
+}
+
+#[test]
+fn remove_clause_works() {
+    let mut solver = Solver::default();
+    let vars = solver.new_literal_chunk(3).into_iter().collect::<Vec<_>>();
+    let (a, b, c) = (vars[0], vars[1], vars[2]);
+    solver.consume_clause([a, b]);
+    let not_a = solver.consume_clause([!a]);
+    let not_b = solver.consume_clause([!b, c]);
+    let not_c = solver.consume_clause([!c]);
+    let tautology = solver.consume_clause([c, !c]);
+    assert!(solver.solve([]).unwrap().is_unsat());
+    assert!(solver.remove_clause(not_b));
+    assert!(!solver.remove_clause(not_b));
+    assert!(solver.solve([]).unwrap().is_sat());
+    assert!(solver.solve([a]).unwrap().is_unsat());
+    assert!(solver.remove_clause(not_a));
+    assert!(solver.solve([a, !b, !c]).unwrap().is_sat());
+    assert!(solver.solve([c]).unwrap().is_unsat());
+    assert!(solver.remove_clause(not_c));
+    assert!(solver.solve([c]).unwrap().is_sat());
+    assert!(solver.remove_clause(tautology));
+    let empty = solver.consume_clause([]);
+    assert!(solver.solve([]).unwrap().is_unsat());
+    assert!(solver.remove_clause(empty));
+    assert!(solver.solve([]).unwrap().is_sat());
+    assert_eq!(solver.clause_database().len(), 1);
+}
+
+#[test]
+fn removed_clauses_forget_learned_clauses_once() {
+    let mut solver = Solver::default();
+    let vars = solver.new_literal_chunk(3).into_iter().collect::<Vec<_>>();
+    let (a, b, c) = (vars[0], vars[1], vars[2]);
+    let handles =
+        [[a, b], [a, !b], [!a, c], [!a, !c]].map(|clause| solver.consume_clause(clause));
+    solver.set_phase(Phase::Positive);
+    assert!(solver.solve([]).unwrap().is_unsat());
+    let len_learnt =
+        |solver: &Solver| solver.clause_database().live_clauses().learnt().count();
+    assert!(solver.hard_facts.len() + len_learnt(&solver) > 0);
+    // The learned clauses are kept until the next call to `solve`.
+    let learnt = (solver.hard_facts.clone(), len_learnt(&solver));
+    assert!(solver.remove_clause(handles[0]));
+    assert!(solver.remove_clause(handles[3]));
+    assert_eq!((solver.hard_facts.clone(), len_learnt(&solver)), learnt);
+    assert!(solver.solve([]).unwrap().is_sat());
+    assert!(solver.solve([a, !c]).unwrap().is_unsat());
+    assert!(solver.solve([!a, !b]).unwrap().is_sat());
+}
+
+#[test]
+fn random_clause_removal_agrees_with_brute_force() {
+    let mut rng = Lcg(48);
+    for _ in 0..50 {
+        let mut solver = garbage_collecting_solver();
+        let vars = solver.new_literal_chunk(10).into_iter().collect::<Vec<_>>();
+        let mut clauses = Vec::new();
+        for _ in 0..5 {
+            for _ in 0..10 + rng.next(10) {
+                let len = rng.next(4);
+                let clause = rng.clause(&vars, len);
+                let handle = solver.consume_clause(clause.iter().copied());
+                clauses.push((handle, clause));
+            }
+            assert_agrees_with_brute_force(&mut solver, &vars, |assignment| {
+                clauses
+                    .iter()
+                    .all(|(_, clause)| count_satisfied(clause, assignment) > 0)
+            });
+            // Retracts about a third of the clauses.
+            clauses.retain(|&(handle, _)| {
+                rng.next(3) != 0 || {
+                    assert!(solver.remove_clause(handle));
+                    false
+                }
+            });
+            assert_agrees_with_brute_force(&mut solver, &vars, |assignment| {
+                clauses
+                    .iter()
+                    .all(|(_, clause)| count_satisfied(clause, assignment) > 0)
+            });
+        }
+    }
+}
+
+#[test]
+fn push_pop_works() {
+    let mut solver = Solver::default();
+    let vars = solver.new_literal_chunk(3).into_iter().collect::<Vec<_>>();
+    let (a, b, c) = (vars[0], vars[1], vars[2]);
+    assert!(!solver.pop());
+    solver.consume_clause([a, b]);
+    solver.push();
+    solver.consume_clause([!a]);
+    solver.push();
+    solver.consume_clause([!b]);
+    assert!(solver.solve([]).unwrap().is_unsat());
+    assert!(solver.failed_assumptions().is_empty());
+    assert!(solver.pop());
+    assert!(solver.solve([]).unwrap().is_sat());
+    assert!(solver.solve([c, a]).unwrap().is_unsat());
+    assert_eq!(solver.failed_assumptions(), [a]);
+    assert!(solver.pop());
+    assert!(solver.solve([a, !b]).unwrap().is_sat());
+    // The empty clause only makes the scope unsatisfiable.
+    solver.push();
+    solver.consume_clause([]);
+    solver.consume_clause([c, !c]);
+    assert!(solver.solve([]).unwrap().is_unsat());
+    assert!(solver.pop());
+    assert!(!solver.pop());
+    assert!(solver.solve([!a]).unwrap().is_sat());
+    // No clause refers to the activation literals of the closed scopes.
+    assert!(solver.clause_database().live_clauses().all(|(_, clause)| {
+        clause
+            .literals()
+            .iter()
+            .all(|literal| vars.contains(&Literal::new(literal.variable(), Sign::POS)))
+    }));
+    // Native constraints are not scoped.
+    solver.push();
+    solver.add_at_least([a, c], 2);
+    assert!(solver.pop());
+    assert!(solver.solve([!c]).unwrap().is_unsat());
+}
+
+#[test]
+fn random_push_pop_agrees_with_brute_force() {
+    let mut rng = Lcg(49);
+    for _ in 0..30 {
+        let mut solver = garbage_collecting_solver();
+        let vars = solver.new_literal_chunk(10).into_iter().collect::<Vec<_>>();
+        // The clauses of the open scopes starting with the clauses outside of any scope.
+        let mut scopes = vec![Vec::new()];
+        for _ in 0..12 {
+            if scopes.len() > 1 && rng.next(3) == 0 {
+                assert!(solver.pop());
+                scopes.pop();
+            } else {
+                solver.push();
+                scopes.push(Vec::new());
+            }
+            for _ in 0..5 + rng.next(10) {
+                let len = rng.next(4);
+                let clause = rng.clause(&vars, len);
+                solver.consume_clause(clause.iter().copied());
+                scopes.last_mut().unwrap().push(clause);
+            }
+            assert_agrees_with_brute_force(&mut solver, &vars, |assignment| {
+                scopes
+                    .iter()
+                    .flatten()
+                    .all(|clause| count_satisfied(clause, assignment) > 0)
+            });
+        }
+    }
+}
+
+#[test]
+fn freeze_and_release_variable_works() {
+    let mut solver = Solver::default();
+    let vars = solver.new_literal_chunk(3).into_iter().collect::<Vec<_>>();
+    let (a, b, c) = (vars[0], vars[1], vars[2]);
+    let clause = solver.consume_clause([a, b]);
+    let unit = solver.consume_clause([!c]);
+    solver.add_at_least([b, c], 1);
+    assert_eq!(solver.release_variable(a), Err(ReleaseError::InUse));
+    assert!(solver.remove_clause(clause));
+    solver.freeze(!a);
+    solver.freeze(a);
+    assert!(solver.is_frozen(a));
+    assert_eq!(solver.release_variable(a), Err(ReleaseError::Frozen));
+    solver.melt(a);
+    assert_eq!(solver.release_variable(!a), Err(ReleaseError::Frozen));
+    solver.melt(!a);
+    assert!(!solver.is_frozen(a));
+    assert_eq!(solver.release_variable(!a), Ok(()));
+    assert_eq!(
+        solver.release_variable(a),
+        Err(ReleaseError::AlreadyReleased)
+    );
+    // Constraints cannot be removed so that their variables stay in use.
+    assert!(solver.remove_clause(unit));
+    assert_eq!(solver.release_variable(c), Err(ReleaseError::InUse));
+    // The released variable is reused as a fresh variable.
+    let d = solver.new_literal();
+    assert_eq!(d.variable(), a.variable());
+    assert_eq!(solver.len_variables(), 3);
+    assert!(!solver.is_frozen(d));
+    solver.consume_clause([!d, !b]);
+    solver.consume_clause([d]);
+    assert!(solver.solve([]).unwrap().is_sat());
+    assert!(solver.solve([b]).unwrap().is_unsat());
+    assert_eq!(solver.new_literal().variable().into_index(), 3);
+}
+
+#[test]
+#[should_panic]
+fn melt_unfrozen_variable_fails() {
+    let mut solver = Solver::default();
+    let a = solver.new_literal();
+    solver.melt(a);
+}
+
+#[test]
+fn push_pop_reuses_activation_variables() {
+    let mut solver = Solver::default();
+    let vars = solver.new_literal_chunk(3).into_iter().collect::<Vec<_>>();
+    let (a, b, c) = (vars[0], vars[1], vars[2]);
+    solver.consume_clause([a, b, c]);
+    for _ in 0..100 {
+        solver.push();
+        solver.consume_clause([!a]);
+        solver.push();
+        solver.consume_clause([!b]);
+        assert!(solver.solve([!c]).unwrap().is_unsat());
+        assert!(solver.pop());
+        assert!(solver.solve([!c]).unwrap().is_sat());
+        assert!(solver.pop());
+    }
+    assert_eq!(solver.len_variables(), 5);
+    assert!(solver.solve([!a, !b]).unwrap().is_sat());
+    // Released variables are not projected upon by the model enumeration.
+    assert_eq!(solver.enumerate_models(None, None).count(), 7);
+}
+
+#[test]
+fn random_variable_release_agrees_with_brute_force() {
+    let mut rng = Lcg(50);
+    for _ in 0..30 {
+        let mut solver = garbage_collecting_solver();
+        let mut vars = solver.new_literal_chunk(8).into_iter().collect::<Vec<_>>();
+        let mut clauses = Vec::new();
+        for _ in 0..8 {
+            for _ in 0..5 + rng.next(10) {
+                let len = rng.next(4);
+                let clause = rng.clause(&vars, len);
+                let handle = solver.consume_clause(clause.iter().copied());
+                clauses.push((handle, clause));
+            }
+            assert_agrees_with_brute_force(&mut solver, &vars, |assignment| {
+                clauses
+                    .iter()
+                    .all(|(_, clause)| count_satisfied(clause, assignment) > 0)
+            });
+            // Replaces a variable by a recycled one after removing its clauses.
+            let index = rng.next(vars.len());
+            let variable = vars[index].variable();
+            clauses.retain(|(handle, clause)| {
+                !clause.iter().any(|literal| literal.variable() == variable) || {
+                    assert!(solver.remove_clause(*handle));
+                    false
+                }
+            });
+            assert_eq!(solver.release_variable(vars[index]), Ok(()));
+            vars[index] = solver.new_literal();
+            assert_eq!(vars[index].variable(), variable);
+            assert_eq!(solver.len_variables(), 8);
+            assert_agrees_with_brute_force(&mut solver, &vars, |assignment| {
+                clauses
+                    .iter()
+                    .all(|(_, clause)| count_satisfied(clause, assignment) > 0)
+            });
+        }
+    }
+}